@@ -0,0 +1,62 @@
+//! Benchmarks the computation of the bounded Voronoi diagramm on a large point set.
+//!
+//! Run with `cargo bench` for sequential and with `cargo bench --features rayon`
+//! for parallel clipping of the cells.
+
+use std::time::Instant;
+
+use geo_bounded_voronoi::{compute_voronoi, BoundedPointSet};
+
+/// The number of sites the Voronoi diagramm is computed for.
+const NUMBER_OF_SITES: usize = 100_000;
+/// The number of repetitions of the benchmark.
+const REPETITIONS: usize = 3;
+
+/// Returns a jittered grid of points, so that the point set is not degenerate.
+fn jittered_grid(number_of_points: usize) -> Vec<[f64; 2]> {
+    let points_per_side = (number_of_points as f64).sqrt().ceil() as usize;
+    // A simple linear congruential generator keeps the benchmark deterministic.
+    let mut state: u64 = 42;
+    let mut next_jitter = move || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 11) as f64 / (1u64 << 53) as f64 * 0.5 - 0.25
+    };
+    (0..number_of_points)
+        .map(|i| {
+            let x = (i % points_per_side) as f64 + next_jitter();
+            let y = (i / points_per_side) as f64 + next_jitter();
+            [x, y]
+        })
+        .collect()
+}
+
+fn main() {
+    let bounded_point_set = BoundedPointSet::new(
+        jittered_grid(NUMBER_OF_SITES),
+        vec![
+            [-0.4, -0.4],
+            [0.4, -0.4],
+            [0.4, 0.4],
+            [-0.4, 0.4],
+            [-0.4, -0.4],
+        ],
+    );
+    let mode = if cfg!(feature = "rayon") {
+        "parallel"
+    } else {
+        "sequential"
+    };
+    for repetition in 0..REPETITIONS {
+        let start = Instant::now();
+        let cells = compute_voronoi(&bounded_point_set).unwrap();
+        println!(
+            "[{}] repetition {}: {} cells in {:?}",
+            mode,
+            repetition + 1,
+            cells.len(),
+            start.elapsed()
+        );
+    }
+}
@@ -1,100 +1,1163 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use clap::{crate_authors, Parser};
+use clap::{crate_authors, parser::ValueSource, ArgMatches, Parser, ValueEnum};
+use geo_bounded_voronoi::{SitePlacement, SortKey};
 use getset::{CopyGetters, Getters};
+use serde::Deserialize;
 
-/// The default name of the output file.
-const DEFAULT_OUTPUT_FILE_NAME: &str = "geo_bound_voronoi.json";
+/// The default stem of the output file.
+const DEFAULT_OUTPUT_FILE_STEM: &str = "geo_bound_voronoi";
+/// The suffix appended to the stem of the point set file to derive the default output file name.
+const DEFAULT_OUTPUT_FILE_SUFFIX: &str = "voronoi";
+/// The path that indicates reading from stdin or writing to stdout.
+const STANDARD_STREAM_PATH: &str = "-";
+/// The file extension of gzip-compressed files.
+const GZIP_EXTENSION: &str = "gz";
+/// The maximum number of Lloyd relaxation iterations if only a tolerance has been specified.
+const DEFAULT_MAX_LLOYD_ITERATIONS: usize = 1000;
 
 /// A tool for generating the Voronoi diagramm of a point set bound by an arbitrary geometry.
 #[derive(Parser, CopyGetters, Getters, Debug)]
 #[command(author = crate_authors!("\n"), version, about, long_about = None)]
 pub struct CommandLineArguments {
-    /// The path to the JSON file containing the point set and bounding geometry.
+    /// The paths to the JSON files containing the point set and bounding geometry or `-` to read a single point set from stdin.
     ///
     /// The structure must be as follows:
     /// ```json
     /// {
-    ///     "points": [[0.0, 1.0], [1.0, 1.0], ...],
-    ///     "bound": [[-0.5, -0.5], [0.0, 0.0], [1.0, 0.5], [-0.5, -0.5]]
+    ///     "points": [[0.0, 1.0], [1.0, 1.0]],
+    ///     "bound": [[-0.5, -0.5], [0.0, 0.0], [1.0, 0.5], [-0.5, -0.5]],
+    ///     "holes": [[[0.0, 0.1], [0.1, 0.1], [0.1, 0.2], [0.0, 0.1]]],
+    ///     "labels": ["first", "second"]
     /// }
     /// ```
+    /// The `holes` and `labels` keys are optional.
+    /// For backwards compatibility the points can also be specified with the key `point_set`.
+    #[arg(required_unless_present = "print_schema", num_args = 1..)]
     #[getset(get = "pub")]
-    point_set_file: PathBuf,
-    /// The output path for the result JSON file [default: the output file is generated in the directory the point set file resides in]
+    point_set_files: Vec<PathBuf>,
+    /// The output path for the result JSON file or `-` to write to stdout [default: `<stem>.voronoi.<format extension>` in the directory the point set file resides in or stdout if reading from stdin]
+    ///
+    /// If multiple point set files are specified, this is the directory the output files are
+    /// generated in, which are named after their point set files.
     #[arg(short, long)]
     output_path: Option<PathBuf>,
+    /// Writes each cell to its own file `cell_<index>.json` or `cell_<index>.geojson` named
+    /// after the index of its site in the specified directory instead of a single output file.
+    /// Only the JSON and GeoJSON formats are supported.
+    #[arg(long, value_name = "DIR")]
+    #[getset(get = "pub")]
+    split_output: Option<PathBuf>,
+    /// The path to a TOML file specifying options like `lloyd = 3` or `site-placement = "drop"`
+    /// with the names of the corresponding flags.
+    ///
+    /// Options specified on the command line take precedence over the configuration file.
+    #[arg(long, value_name = "PATH")]
+    #[getset(get = "pub")]
+    config: Option<PathBuf>,
+    /// The path to a JSON file containing the bound as bare ring, e.g.
+    /// `[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]`, an array of rings or a circle,
+    /// which is used for all point set files instead of the bound they specify.
+    #[arg(long, value_name = "PATH")]
+    #[getset(get = "pub")]
+    bound: Option<PathBuf>,
+    /// Clips the cells to the convex hull of the points expanded by the optional margin
+    /// if the point set file does not specify a bound, e.g. `--hull-bound` or
+    /// `--hull-bound 0.5`. In contrast to a specified bound, the hull is not centered
+    /// around each point.
+    #[arg(long, value_name = "MARGIN", num_args = 0..=1, default_missing_value = "0")]
+    #[getset(get_copy = "pub")]
+    hull_bound: Option<f64>,
+    /// The format of the result file.
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Json)]
+    #[getset(get_copy = "pub")]
+    format: OutputFormat,
+    /// Includes the length of the boundary shared by each pair of neighboring cells
+    /// as third column of the adjacency output.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    boundary_length: bool,
+    /// Includes the area of each cell in the JSON output.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    area: bool,
+    /// Includes the index of each site in the unique points ordered like the cells
+    /// in the JSON, GeoJSON and TopoJSON output.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    index: bool,
+    /// Includes the cell polygon before the bound is applied in the JSON output.
+    /// With `--vertices` the vertices outside the bound are kept.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    unbounded: bool,
+    /// Repeats the first vertex of each cell ring at its end in the JSON output.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    close_rings: bool,
+    /// Orders the output cells like the first occurrences of their points in the
+    /// input instead of by their coordinates.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    input_order: bool,
+    /// Sorts the output cells by the specified key after the bound has been applied,
+    /// e.g. to render the largest cells first. Ties are broken by the coordinates of the points.
+    #[arg(long, value_enum, value_name = "KEY")]
+    sort_by: Option<SortKeyArgument>,
+    /// The number of Lloyd relaxation iterations applied to the sites.
+    /// With 0 iterations the input points are kept as sites exactly.
+    #[arg(long, default_value_t = 0)]
+    #[getset(get_copy = "pub")]
+    lloyd: usize,
+    /// Runs Lloyd relaxation until no site moves further than the specified distance,
+    /// with the value of `--lloyd` as maximum number of iterations or 1000 iterations
+    /// if `--lloyd` is not set.
+    /// The sites are moved to the centroids of their cells after the bound has been applied.
+    #[arg(long, value_name = "TOLERANCE")]
+    #[getset(get_copy = "pub")]
+    lloyd_tolerance: Option<f64>,
+    /// The behaviour for points that do not lie within the bound centered around them.
+    #[arg(long, value_enum, default_value_t = SitePlacementArgument::Keep)]
+    site_placement: SitePlacementArgument,
+    /// Snaps the coordinates of the cells and the bound to a grid with the
+    /// specified spacing before clipping to avoid floating point errors.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    snap_precision: Option<f64>,
+    /// Shuffles the points with a random number generator seeded with the specified
+    /// seed after they have been sorted, which decides the order of the output cells
+    /// and how ties between cocircular points are broken. The cells are still sorted
+    /// by `--sort-by`. Cannot be combined with `--input-order`.
+    #[arg(long, value_name = "SEED")]
+    #[getset(get_copy = "pub")]
+    seed: Option<u64>,
+    /// Clips the cells to the bound shrunk inwards by the specified distance, e.g. to
+    /// leave gutters along the boundary. Non-positive distances leave the bound unchanged.
+    #[arg(long, value_name = "DISTANCE", allow_hyphen_values = true)]
+    #[getset(get_copy = "pub")]
+    inset: Option<f64>,
+    /// Subdivides the edges of the cells, so that no segment is longer than the
+    /// specified length, e.g. for smoother rendering. Non-positive lengths are ignored.
+    #[arg(long, value_name = "LEN")]
+    #[getset(get_copy = "pub")]
+    densify: Option<f64>,
+    /// Removes vertices of the cells deviating less than the specified tolerance from
+    /// the simplified edges with the Douglas-Peucker algorithm, e.g. for cells clipped
+    /// to detailed bounds. Non-positive tolerances are ignored.
+    #[arg(long, value_name = "EPS")]
+    #[getset(get_copy = "pub")]
+    simplify: Option<f64>,
+    /// Merges points whose coordinates both differ by at most the specified tolerance
+    /// into the point occurring first, e.g. to prevent degenerate triangulations
+    /// caused by near-duplicate points.
+    #[arg(long, value_name = "EPS")]
+    #[getset(get_copy = "pub")]
+    merge_tolerance: Option<f64>,
+    /// Aborts with an error if a point set contains more than the specified number of
+    /// unique sites before building the diagramm, e.g. to guard against huge inputs.
+    #[arg(long, value_name = "N")]
+    #[getset(get_copy = "pub")]
+    max_sites: Option<usize>,
+    /// Applies the bound to the cells with the specified number of threads instead of
+    /// the global thread pool, where 1 forces sequential execution, e.g. for
+    /// reproducible CI runs. This requires the `rayon` feature.
+    #[arg(long, value_name = "N")]
+    #[getset(get_copy = "pub")]
+    threads: Option<usize>,
+    /// Rounds all output coordinates including the sites to the specified number of
+    /// decimal places and warns about cells that collapse into a degenerate shape.
+    #[arg(long, value_name = "N")]
+    #[getset(get_copy = "pub")]
+    decimals: Option<u32>,
+    /// Removes degenerate cells with fewer than 3 distinct vertices or a negligible
+    /// area, e.g. slivers created by clipping, instead of only reporting them.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    drop_degenerate: bool,
+    /// Wraps the JSON output in an object, which records metadata about the generation
+    /// of the cells under the key `metadata` and the cells under the key `cells`.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    with_metadata: bool,
+    /// Includes the validated and reoriented bound of the input, e.g. for drawing the
+    /// outer frame, under the key `bound` of the output object written by `--with-metadata`,
+    /// which is implied.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    echo_bound: bool,
+    /// Writes the coordinates of the JSON output as objects `{"x": .., "y": ..}`
+    /// instead of the more compact arrays `[x, y]`.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    coord_object: bool,
+    /// Projects the longitude / latitude input coordinates (EPSG:4326) to the specified
+    /// planar coordinate reference system before the computation and the output
+    /// coordinates back. This requires the `proj` feature.
+    #[arg(long, value_name = "PROJ_STRING")]
+    #[getset(get = "pub")]
+    project: Option<String>,
+    /// Writes the Delaunay triangulation of the point set as JSON instead of the Voronoi cells.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    delaunay: bool,
+    /// Writes the vertices of the Voronoi diagramm within the bound as JSON
+    /// instead of the cells.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    vertices: bool,
+    /// Reads point set files containing several groups of points that share one bound,
+    /// e.g. `{"bound": [...], "groups": [[...], [...]]}`, and writes one array of
+    /// cells per group as JSON.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    groups: bool,
+    /// Writes the total cell area and its ratio to the area covered by the bound
+    /// centered around each site to stderr.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    stats: bool,
+    /// Compares the union of all cells with the union of the bound centered around each
+    /// site and warns on stderr if gaps or overlaps indicate an error while clipping.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    verify: bool,
+    /// Indents the JSON and GeoJSON output for readability.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    pretty: bool,
+    /// Aborts at the first point set file that cannot be processed
+    /// instead of continuing with the remaining files.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    fail_fast: bool,
+    /// Fails if sites lie outside the bounding rectangle of the bound centered around
+    /// the point set, i.e. if the bound is smaller than the point set.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    strict: bool,
+    /// Suppresses the progress bar shown for large point sets.
+    #[arg(short, long)]
+    #[getset(get_copy = "pub")]
+    quiet: bool,
+    /// Writes the wall time of the deduplication, the diagramm construction, the cell
+    /// iteration and the clipping to stderr.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    timing: bool,
+    /// Decompresses stdin and compresses stdout with gzip.
+    /// Files with a `.gz` extension are always decompressed or compressed.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    gzip: bool,
+    /// Validates the point set files without computing the Voronoi diagramm or writing
+    /// any output and reports the number of unique and duplicate points to stdout.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    check: bool,
+    /// Writes the JSON Schema of the point set file to stdout instead of processing point sets.
+    /// This requires the `schema` feature.
+    #[arg(long)]
+    #[getset(get_copy = "pub")]
+    print_schema: bool,
+}
+
+/// The options that can be specified in a configuration file instead of
+/// on the command line. Unspecified options keep their command line value.
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct RunConfig {
+    /// The path to a JSON file containing the bound used for all point set files.
+    bound: Option<PathBuf>,
+    /// The margin of the convex hull used as bound if no bound has been specified.
+    hull_bound: Option<f64>,
+    /// The directory each cell is written to as its own file.
+    split_output: Option<PathBuf>,
+    /// The format of the result file.
+    format: Option<OutputFormat>,
+    /// Includes the length of the shared boundary in the adjacency output.
+    boundary_length: Option<bool>,
+    /// Includes the area of each cell in the JSON output.
+    area: Option<bool>,
+    /// Includes the index of each site in the output.
+    index: Option<bool>,
+    /// Includes the cell polygon before the bound is applied in the JSON output.
+    unbounded: Option<bool>,
+    /// Repeats the first vertex of each cell ring at its end in the JSON output.
+    close_rings: Option<bool>,
+    /// Orders the output cells like the first occurrences of their points in the input.
+    input_order: Option<bool>,
+    /// The key the output cells are sorted by.
+    sort_by: Option<SortKeyArgument>,
+    /// The number of Lloyd relaxation iterations applied to the sites.
+    lloyd: Option<usize>,
+    /// The displacement of the sites below which Lloyd relaxation stops.
+    lloyd_tolerance: Option<f64>,
+    /// The behaviour for points that do not lie within the bound centered around them.
+    site_placement: Option<SitePlacementArgument>,
+    /// The spacing of the grid the coordinates are snapped to before clipping.
+    snap_precision: Option<f64>,
+    /// The seed of the random number generator shuffling the points.
+    seed: Option<u64>,
+    /// The distance the bound is shrunk inwards by before clipping.
+    inset: Option<f64>,
+    /// The maximum length of the segments the cell edges are subdivided into.
+    densify: Option<f64>,
+    /// The tolerance the cell edges are simplified with.
+    simplify: Option<f64>,
+    /// The maximum coordinate difference of merged points.
+    merge_tolerance: Option<f64>,
+    /// The maximum number of unique sites of a point set.
+    max_sites: Option<usize>,
+    /// The number of threads the bound is applied to the cells with.
+    threads: Option<usize>,
+    /// The number of decimal places the output coordinates are rounded to.
+    decimals: Option<u32>,
+    /// Removes degenerate cells instead of only reporting them.
+    drop_degenerate: Option<bool>,
+    /// Wraps the JSON output in an object recording metadata about the generation.
+    with_metadata: Option<bool>,
+    /// Includes the processed bound of the input in the output object.
+    echo_bound: Option<bool>,
+    /// Writes the coordinates of the JSON output as objects instead of arrays.
+    coord_object: Option<bool>,
+    /// The planar coordinate reference system the input coordinates are projected to.
+    project: Option<String>,
+    /// Writes the Delaunay triangulation instead of the Voronoi cells.
+    delaunay: Option<bool>,
+    /// Writes the vertices of the Voronoi diagramm instead of the cells.
+    vertices: Option<bool>,
+    /// Reads several groups of points sharing one bound from each point set file.
+    groups: Option<bool>,
+    /// Writes statistics about the cell area to stderr.
+    stats: Option<bool>,
+    /// Warns if the union of all cells deviates from the union of the centered bounds.
+    verify: Option<bool>,
+    /// Indents the JSON and GeoJSON output for readability.
+    pretty: Option<bool>,
+    /// Aborts at the first point set file that cannot be processed.
+    fail_fast: Option<bool>,
+    /// Fails if sites lie outside the bounding rectangle of the bound.
+    strict: Option<bool>,
+    /// Suppresses the progress bar shown for large point sets.
+    quiet: Option<bool>,
+    /// Writes the wall time of the phases of the computation to stderr.
+    timing: Option<bool>,
+    /// Decompresses stdin and compresses stdout with gzip.
+    gzip: Option<bool>,
+}
+
+impl RunConfig {
+    /// Reads the configuration from the specified TOML file.
+    ///
+    /// # Parameters
+    ///
+    /// * `path` - the path to the configuration file
+    pub fn from_file<T: AsRef<Path>>(path: T) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(toml::from_str(&std::fs::read_to_string(path)?)?)
+    }
+}
+
+/// The supported output formats.
+#[derive(ValueEnum, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum OutputFormat {
+    /// An array of objects containing the site and the cell polygon.
+    Json,
+    /// A GeoJSON feature collection of cell polygons carrying their site as property.
+    Geojson,
+    /// A TopoJSON topology of cell polygons sharing the edges between adjacent cells.
+    Topojson,
+    /// Newline-delimited WKT cell polygons each followed by a tab and the WKT site point.
+    Wkt,
+    /// A table with one row per cell containing the site, the number of vertices and the area.
+    Csv,
+    /// An aligned text table with one row per cell containing the site, the number of
+    /// vertices and the area for reading on the terminal.
+    Table,
+    /// An SVG image of the cell polygons and their sites for visualization.
+    Svg,
+    /// A KML document with one placemark per cell for Google Earth, which requires
+    /// geographic coordinates.
+    Kml,
+    /// A CSV edge list with one row `i,j` per pair of neighboring cells with `i < j`.
+    Adjacency,
+    /// An ESRI shapefile with one polygon record per cell, which is written to the
+    /// `.shp`, `.shx` and `.dbf` files named after the output path.
+    /// This requires the `shapefile` feature.
+    Shapefile,
+}
+
+impl OutputFormat {
+    /// Returns the file extension of the output format.
+    fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Json => "json",
+            OutputFormat::Geojson => "geojson",
+            OutputFormat::Topojson => "topojson",
+            OutputFormat::Wkt => "wkt",
+            OutputFormat::Csv | OutputFormat::Adjacency => "csv",
+            OutputFormat::Table => "txt",
+            OutputFormat::Svg => "svg",
+            OutputFormat::Kml => "kml",
+            OutputFormat::Shapefile => "shp",
+        }
+    }
+}
+
+/// The behaviour for sites that do not lie within the bound centered around them
+/// as command line value. See [`SitePlacement`].
+#[derive(ValueEnum, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SitePlacementArgument {
+    /// An error is returned.
+    Error,
+    /// The points are removed before the Voronoi diagramm is computed.
+    Drop,
+    /// The points are kept, which results in an error if their cell
+    /// does not intersect the bound.
+    Keep,
+    /// The points are kept and their cells are output without any polygon
+    /// if they do not intersect the bound.
+    KeepEmpty,
+}
+
+impl From<SitePlacementArgument> for SitePlacement {
+    fn from(site_placement: SitePlacementArgument) -> Self {
+        match site_placement {
+            SitePlacementArgument::Error => SitePlacement::Error,
+            SitePlacementArgument::Drop => SitePlacement::Drop,
+            SitePlacementArgument::Keep => SitePlacement::Keep,
+            SitePlacementArgument::KeepEmpty => SitePlacement::KeepEmpty,
+        }
+    }
+}
+
+/// The key the cells are sorted by after the bound has been applied
+/// as command line value. See [`SortKey`].
+#[derive(ValueEnum, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortKeyArgument {
+    /// The cells are sorted by descending area, so that the largest cell comes first.
+    Area,
+    /// The cells are sorted by ascending area, so that the smallest cell comes first.
+    AreaAscending,
+    /// The cells are sorted by ascending x-coordinate of their point.
+    SiteX,
+    /// The cells are sorted by ascending y-coordinate of their point.
+    SiteY,
+}
+
+impl From<SortKeyArgument> for SortKey {
+    fn from(sort_key: SortKeyArgument) -> Self {
+        match sort_key {
+            SortKeyArgument::Area => SortKey::Area,
+            SortKeyArgument::AreaAscending => SortKey::AreaAscending,
+            SortKeyArgument::SiteX => SortKey::SiteX,
+            SortKeyArgument::SiteY => SortKey::SiteY,
+        }
+    }
 }
 
 impl CommandLineArguments {
-    /// Returns the output path that has been specified.
+    /// Returns the behaviour for points that do not lie within the bound centered around them.
+    pub fn site_placement(&self) -> SitePlacement {
+        self.site_placement.into()
+    }
+
+    /// Returns the key the output cells are sorted by if any.
+    pub fn sort_by(&self) -> Option<SortKey> {
+        self.sort_by.map(SortKey::from)
+    }
+
+    /// Replaces all options that have not been specified on the command line
+    /// with the options specified in the configuration.
+    ///
+    /// # Parameters
+    ///
+    /// * `config` - the configuration loaded from a file
+    /// * `matches` - the parsed command line the arguments have been created from
+    pub fn merge(&mut self, config: RunConfig, matches: &ArgMatches) {
+        let from_command_line =
+            |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+        merge_option(&mut self.bound, config.bound.map(Some), from_command_line("bound"));
+        merge_option(
+            &mut self.hull_bound,
+            config.hull_bound.map(Some),
+            from_command_line("hull_bound"),
+        );
+        merge_option(
+            &mut self.split_output,
+            config.split_output.map(Some),
+            from_command_line("split_output"),
+        );
+        merge_option(&mut self.format, config.format, from_command_line("format"));
+        merge_option(
+            &mut self.boundary_length,
+            config.boundary_length,
+            from_command_line("boundary_length"),
+        );
+        merge_option(&mut self.area, config.area, from_command_line("area"));
+        merge_option(&mut self.index, config.index, from_command_line("index"));
+        merge_option(&mut self.unbounded, config.unbounded, from_command_line("unbounded"));
+        merge_option(&mut self.close_rings, config.close_rings, from_command_line("close_rings"));
+        merge_option(&mut self.input_order, config.input_order, from_command_line("input_order"));
+        merge_option(&mut self.sort_by, config.sort_by.map(Some), from_command_line("sort_by"));
+        merge_option(&mut self.lloyd, config.lloyd, from_command_line("lloyd"));
+        merge_option(
+            &mut self.lloyd_tolerance,
+            config.lloyd_tolerance.map(Some),
+            from_command_line("lloyd_tolerance"),
+        );
+        merge_option(
+            &mut self.site_placement,
+            config.site_placement,
+            from_command_line("site_placement"),
+        );
+        merge_option(
+            &mut self.snap_precision,
+            config.snap_precision.map(Some),
+            from_command_line("snap_precision"),
+        );
+        merge_option(&mut self.seed, config.seed.map(Some), from_command_line("seed"));
+        merge_option(&mut self.inset, config.inset.map(Some), from_command_line("inset"));
+        merge_option(&mut self.densify, config.densify.map(Some), from_command_line("densify"));
+        merge_option(&mut self.simplify, config.simplify.map(Some), from_command_line("simplify"));
+        merge_option(
+            &mut self.merge_tolerance,
+            config.merge_tolerance.map(Some),
+            from_command_line("merge_tolerance"),
+        );
+        merge_option(&mut self.threads, config.threads.map(Some), from_command_line("threads"));
+        merge_option(
+            &mut self.max_sites,
+            config.max_sites.map(Some),
+            from_command_line("max_sites"),
+        );
+        merge_option(&mut self.decimals, config.decimals.map(Some), from_command_line("decimals"));
+        merge_option(
+            &mut self.drop_degenerate,
+            config.drop_degenerate,
+            from_command_line("drop_degenerate"),
+        );
+        merge_option(
+            &mut self.with_metadata,
+            config.with_metadata,
+            from_command_line("with_metadata"),
+        );
+        merge_option(&mut self.echo_bound, config.echo_bound, from_command_line("echo_bound"));
+        merge_option(
+            &mut self.coord_object,
+            config.coord_object,
+            from_command_line("coord_object"),
+        );
+        merge_option(&mut self.project, config.project.map(Some), from_command_line("project"));
+        merge_option(&mut self.delaunay, config.delaunay, from_command_line("delaunay"));
+        merge_option(&mut self.vertices, config.vertices, from_command_line("vertices"));
+        merge_option(&mut self.groups, config.groups, from_command_line("groups"));
+        merge_option(&mut self.stats, config.stats, from_command_line("stats"));
+        merge_option(&mut self.verify, config.verify, from_command_line("verify"));
+        merge_option(&mut self.pretty, config.pretty, from_command_line("pretty"));
+        merge_option(&mut self.fail_fast, config.fail_fast, from_command_line("fail_fast"));
+        merge_option(&mut self.strict, config.strict, from_command_line("strict"));
+        merge_option(&mut self.quiet, config.quiet, from_command_line("quiet"));
+        merge_option(&mut self.timing, config.timing, from_command_line("timing"));
+        merge_option(&mut self.gzip, config.gzip, from_command_line("gzip"));
+    }
+
+    /// Returns the maximum number of Lloyd relaxation iterations if a Lloyd tolerance
+    /// has been specified, which is the number of iterations set with `--lloyd` or
+    /// [`DEFAULT_MAX_LLOYD_ITERATIONS`] if no iterations have been set.
+    pub fn max_lloyd_iterations(&self) -> usize {
+        match self.lloyd {
+            0 => DEFAULT_MAX_LLOYD_ITERATIONS,
+            lloyd => lloyd,
+        }
+    }
+
+    /// Returns `true` if multiple point set files should be processed.
+    pub fn is_batch(&self) -> bool {
+        self.point_set_files.len() > 1
+    }
+
+    /// Returns `true` if the point set should be read from stdin.
+    ///
+    /// # Parameters
+    ///
+    /// * `point_set_file` - the point set file to process
+    pub fn reads_stdin<T: AsRef<Path>>(&self, point_set_file: T) -> bool {
+        point_set_file.as_ref() == Path::new(STANDARD_STREAM_PATH)
+    }
+
+    /// Returns `true` if the result should be written to stdout.
+    /// This is the case if explicitly specified or if no output path
+    /// has been set while reading from stdin.
+    ///
+    /// # Parameters
+    ///
+    /// * `point_set_file` - the point set file to process
+    pub fn writes_stdout<T: AsRef<Path>>(&self, point_set_file: T) -> bool {
+        match &self.output_path {
+            Some(output_path) => output_path == Path::new(STANDARD_STREAM_PATH),
+            None => self.reads_stdin(point_set_file),
+        }
+    }
+
+    /// Returns `true` if the point set should be decompressed with gzip.
+    /// This is the case if the point set file has a `.gz` extension
+    /// or if stdin is read and gzip has been explicitly requested.
+    ///
+    /// # Parameters
+    ///
+    /// * `point_set_file` - the point set file to process
+    pub fn reads_gzip<T: AsRef<Path>>(&self, point_set_file: T) -> bool {
+        if self.reads_stdin(&point_set_file) {
+            self.gzip
+        } else {
+            has_gzip_extension(point_set_file)
+        }
+    }
+
+    /// Returns `true` if the result should be compressed with gzip.
+    /// This is the case if the output file has a `.gz` extension
+    /// or if stdout is written and gzip has been explicitly requested.
+    ///
+    /// # Parameters
+    ///
+    /// * `point_set_file` - the point set file to process
+    pub fn writes_gzip<T: AsRef<Path>>(&self, point_set_file: T) -> bool {
+        if self.writes_stdout(&point_set_file) {
+            self.gzip
+        } else {
+            has_gzip_extension(self.output_path(point_set_file))
+        }
+    }
+
+    /// Returns the output path for the point set file.
     /// If none has been set the default output path is returned.
-    pub fn output_path(&self) -> PathBuf {
-        self.output_path
+    /// If multiple point set files are processed, the output file is named like the
+    /// default output file, e.g. `field_a.voronoi.json` for `field_a.json.gz`, and placed
+    /// in the specified output directory or the directory the point set file resides in.
+    ///
+    /// # Parameters
+    ///
+    /// * `point_set_file` - the point set file to process
+    pub fn output_path<T: AsRef<Path>>(&self, point_set_file: T) -> PathBuf {
+        let point_set_file = point_set_file.as_ref();
+        if self.is_batch() {
+            let directory = self
+                .output_path
+                .clone()
+                .or_else(|| point_set_file.parent().map(Path::to_path_buf))
+                .unwrap_or_default();
+            directory.join(default_output_file_name(point_set_file, self.output_extension()))
+        } else {
+            self.output_path
+                .as_ref()
+                .map(|output_file| output_file.to_path_buf())
+                .unwrap_or_else(|| self.default_ouptut_path(point_set_file))
+        }
+    }
+
+    /// Returns the path of the file the cell with the specified site index is written to
+    /// if each cell should be written to its own file.
+    /// The file is named `cell_<index>` with the extension of the output format and placed
+    /// in the split output directory. If multiple point set files are processed, the file
+    /// name is prefixed with the stem of the point set file, e.g. `field_a_cell_0.json`.
+    ///
+    /// # Parameters
+    ///
+    /// * `point_set_file` - the point set file to process
+    /// * `index` - the index of the site of the cell
+    pub fn split_output_path<T: AsRef<Path>>(
+        &self,
+        point_set_file: T,
+        index: usize,
+    ) -> Option<PathBuf> {
+        let file_name = format!("cell_{}.{}", index, self.output_extension());
+        let file_name = if self.is_batch() {
+            let stem = point_set_stem(point_set_file.as_ref()).unwrap_or_default();
+            format!("{}_{}", stem, file_name)
+        } else {
+            file_name
+        };
+        self.split_output
             .as_ref()
-            .map(|output_file| output_file.to_path_buf())
-            .unwrap_or_else(|| self.default_ouptut_path())
+            .map(|directory| directory.join(file_name))
     }
 
-    /// Returns the default output path.
-    fn default_ouptut_path(&self) -> PathBuf {
-        self.point_set_file
+    /// Returns the default output path, which is named after the stem of the point set file
+    /// with the extension of the output format, e.g. `field_a.voronoi.json` for `field_a.json`
+    /// or `field_a.json.gz`, and placed in the directory the point set file resides in.
+    /// If the point set file has no stem, the output file is named `geo_bound_voronoi.json`.
+    ///
+    /// # Parameters
+    ///
+    /// * `point_set_file` - the point set file to process
+    fn default_ouptut_path(&self, point_set_file: &Path) -> PathBuf {
+        let file_name = default_output_file_name(point_set_file, self.output_extension());
+        point_set_file
             .parent()
-            .map(|parent| parent.to_path_buf().join(DEFAULT_OUTPUT_FILE_NAME))
-            .unwrap_or(DEFAULT_OUTPUT_FILE_NAME.into())
+            .map(|parent| parent.to_path_buf().join(&file_name))
+            .unwrap_or(file_name.into())
+    }
+
+    /// Returns the extension of the output file, which is `json` for the Delaunay triangles
+    /// and the Voronoi vertices and the extension of the output format otherwise.
+    fn output_extension(&self) -> &'static str {
+        if self.delaunay || self.vertices {
+            OutputFormat::Json.extension()
+        } else {
+            self.format.extension()
+        }
     }
 }
 
+/// Replaces the value with the configured value if the value has not been
+/// specified on the command line and a configured value exists.
+///
+/// # Parameters
+///
+/// * `value` - the value parsed from the command line
+/// * `configured_value` - the value specified in the configuration file
+/// * `from_command_line` - if the value has been specified on the command line
+fn merge_option<T>(value: &mut T, configured_value: Option<T>, from_command_line: bool) {
+    if let (false, Some(configured_value)) = (from_command_line, configured_value) {
+        *value = configured_value;
+    }
+}
+
+/// Returns the stem of the point set file without a `.gz` extension,
+/// e.g. `field_a` for `field_a.json` or `field_a.json.gz`,
+/// or [`None`] if the point set file has no stem.
+///
+/// # Parameters
+///
+/// * `point_set_file` - the point set file to process
+fn point_set_stem(point_set_file: &Path) -> Option<String> {
+    let uncompressed_file = if has_gzip_extension(point_set_file) {
+        point_set_file.with_extension("")
+    } else {
+        point_set_file.to_path_buf()
+    };
+    uncompressed_file
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+}
+
+/// Returns the name of the output file derived from the stem of the point set file,
+/// e.g. `field_a.voronoi.json` for `field_a.json` or `field_a.json.gz`, or
+/// `geo_bound_voronoi.json` if the point set file has no stem.
+///
+/// # Parameters
+///
+/// * `point_set_file` - the point set file to process
+/// * `extension` - the extension of the output file
+fn default_output_file_name(point_set_file: &Path, extension: &str) -> String {
+    point_set_stem(point_set_file)
+        .map(|stem| format!("{}.{}.{}", stem, DEFAULT_OUTPUT_FILE_SUFFIX, extension))
+        .unwrap_or_else(|| format!("{}.{}", DEFAULT_OUTPUT_FILE_STEM, extension))
+}
+
+/// Returns `true` if the path has a `.gz` extension.
+///
+/// # Parameters
+///
+/// * `path` - the path to check
+pub fn has_gzip_extension<T: AsRef<Path>>(path: T) -> bool {
+    path.as_ref()
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case(GZIP_EXTENSION))
+}
+
 #[cfg(test)]
 mod tests {
+    use clap::{CommandFactory, FromArgMatches};
+
     use super::*;
 
+    /// Returns command line arguments with the specified paths and default options.
+    fn arguments(point_set_file: &str, output_path: Option<PathBuf>) -> CommandLineArguments {
+        CommandLineArguments {
+            point_set_files: vec![point_set_file.into()],
+            output_path,
+            split_output: None,
+            config: None,
+            bound: None,
+            hull_bound: None,
+            format: OutputFormat::Json,
+            boundary_length: false,
+            area: false,
+            index: false,
+            unbounded: false,
+            close_rings: false,
+            input_order: false,
+            sort_by: None,
+            lloyd: 0,
+            lloyd_tolerance: None,
+            site_placement: SitePlacementArgument::Keep,
+            snap_precision: None,
+            seed: None,
+            inset: None,
+            densify: None,
+            simplify: None,
+            merge_tolerance: None,
+            max_sites: None,
+            threads: None,
+            decimals: None,
+            drop_degenerate: false,
+            with_metadata: false,
+            echo_bound: false,
+            coord_object: false,
+            project: None,
+            delaunay: false,
+            vertices: false,
+            groups: false,
+            stats: false,
+            verify: false,
+            pretty: false,
+            fail_fast: false,
+            strict: false,
+            quiet: false,
+            timing: false,
+            gzip: false,
+            check: false,
+            print_schema: false,
+        }
+    }
+
+    #[test]
+    fn test_point_set_file_documented_structure() {
+        // The exact structure documented for the point set file.
+        let json = r#"{
+            "points": [[0.0, 1.0], [1.0, 1.0]],
+            "bound": [[-0.5, -0.5], [0.0, 0.0], [1.0, 0.5], [-0.5, -0.5]],
+            "holes": [[[0.0, 0.1], [0.1, 0.1], [0.1, 0.2], [0.0, 0.1]]],
+            "labels": ["first", "second"]
+        }"#;
+        let point_set: geo_bounded_voronoi::BoundedPointSet = serde_json::from_str(json).unwrap();
+        assert_eq!(point_set.point_set().len(), 2);
+        assert!(matches!(
+            point_set.bound(),
+            geo_bounded_voronoi::BoundKind::Polygon(ring) if ring.len() == 4
+        ));
+        assert_eq!(point_set.holes().len(), 1);
+        assert_eq!(point_set.labels().len(), 2);
+    }
+
+    #[test]
+    fn test_parse_print_schema() {
+        let cl_args =
+            CommandLineArguments::try_parse_from(["geo-bounded-voronoi", "--print-schema"])
+                .unwrap();
+        assert!(cl_args.print_schema());
+        assert!(cl_args.point_set_files().is_empty());
+        assert!(CommandLineArguments::try_parse_from(["geo-bounded-voronoi"]).is_err());
+    }
+
+    /// Parses the command line and merges it with the TOML configuration.
+    fn merged_arguments(command_line: &[&str], config: &str) -> CommandLineArguments {
+        let matches = CommandLineArguments::command()
+            .try_get_matches_from(command_line)
+            .unwrap();
+        let mut cl_args = CommandLineArguments::from_arg_matches(&matches).unwrap();
+        cl_args.merge(toml::from_str(config).unwrap(), &matches);
+        cl_args
+    }
+
+    #[test]
+    fn test_merge_config() {
+        let config = r#"
+            format = "geojson"
+            lloyd = 3
+            site-placement = "keep-empty"
+            snap-precision = 0.5
+            stats = true
+        "#;
+        let cl_args = merged_arguments(&["geo-bounded-voronoi", "input.json"], config);
+        assert_eq!(cl_args.format(), OutputFormat::Geojson);
+        assert_eq!(cl_args.lloyd(), 3);
+        assert_eq!(cl_args.site_placement(), SitePlacement::KeepEmpty);
+        assert_eq!(cl_args.snap_precision(), Some(0.5));
+        assert!(cl_args.stats());
+        assert!(!cl_args.pretty());
+    }
+
+    #[test]
+    fn test_merge_config_command_line_precedence() {
+        let config = r#"
+            format = "geojson"
+            lloyd = 3
+            site-placement = "keep-empty"
+            snap-precision = 0.5
+        "#;
+        let cl_args = merged_arguments(
+            &[
+                "geo-bounded-voronoi",
+                "--format",
+                "json",
+                "--lloyd",
+                "0",
+                "--snap-precision",
+                "0.1",
+                "--pretty",
+                "input.json",
+            ],
+            config,
+        );
+        // The command line values take precedence even if they match the defaults.
+        assert_eq!(cl_args.format(), OutputFormat::Json);
+        assert_eq!(cl_args.lloyd(), 0);
+        assert_eq!(cl_args.snap_precision(), Some(0.1));
+        assert!(cl_args.pretty());
+        assert_eq!(cl_args.site_placement(), SitePlacement::KeepEmpty);
+    }
+
+    #[test]
+    fn test_runconfig_unknown_option() {
+        assert!(toml::from_str::<RunConfig>("lloyds = 3").is_err());
+        assert_eq!(toml::from_str::<RunConfig>("").unwrap(), RunConfig::default());
+    }
+
     #[test]
     fn test_default_output_path_valid() {
-        let args = CommandLineArguments {
-            point_set_file: "/test/path/point_set.json".into(),
-            output_path: None,
-        };
+        let args = arguments("/test/path/point_set.json", None);
+        assert_eq!(
+            args.default_ouptut_path(&args.point_set_files[0]),
+            PathBuf::from("/test/path/point_set.voronoi.json")
+        );
+    }
+
+    #[test]
+    fn test_default_output_path_stem() {
+        for (point_set_file, output_file) in [
+            ("field_a.json", "field_a.voronoi.json"),
+            ("/data/field_b.json", "/data/field_b.voronoi.json"),
+            ("/data/field_c", "/data/field_c.voronoi.json"),
+            ("/data/field.d.json", "/data/field.d.voronoi.json"),
+            ("/data/field_e.json.gz", "/data/field_e.voronoi.json"),
+        ] {
+            let args = arguments(point_set_file, None);
+            assert_eq!(
+                args.default_ouptut_path(&args.point_set_files[0]),
+                PathBuf::from(output_file)
+            );
+        }
+    }
+
+    #[test]
+    fn test_default_output_path_stemless() {
+        let args = arguments("/", None);
         assert_eq!(
-            args.default_ouptut_path(),
-            PathBuf::from("/test/path").join(DEFAULT_OUTPUT_FILE_NAME)
+            args.default_ouptut_path(&args.point_set_files[0]),
+            PathBuf::from("geo_bound_voronoi.json")
+        );
+        let args = arguments("/test/path/..", None);
+        assert_eq!(
+            args.default_ouptut_path(&args.point_set_files[0]),
+            PathBuf::from("/test/path").join("geo_bound_voronoi.json")
         );
     }
 
     #[test]
     fn test_default_output_path_invalid() {
-        let args = CommandLineArguments {
-            point_set_file: "".into(),
-            output_path: None,
-        };
-        assert_eq!(args.default_ouptut_path(), PathBuf::from(DEFAULT_OUTPUT_FILE_NAME));
+        let args = arguments("", None);
+        assert_eq!(
+            args.default_ouptut_path(&args.point_set_files[0]),
+            PathBuf::from("geo_bound_voronoi.json")
+        );
     }
 
     #[test]
     fn test_output_path_set() {
         let output_path = PathBuf::from("test/output/path/file.json");
-        let args = CommandLineArguments {
-            point_set_file: "/test/path/point_set.json".into(),
-            output_path: Some(output_path),
-        };
-        assert_eq!(args.output_path(), args.output_path.clone().unwrap());
+        let args = arguments("/test/path/point_set.json", Some(output_path));
+        assert_eq!(args.output_path(&args.point_set_files[0]), args.output_path.clone().unwrap());
     }
 
     #[test]
     fn test_output_path_unset_valid() {
-        let args = CommandLineArguments {
-            point_set_file: "/test/path/point_set.json".into(),
-            output_path: None,
-        };
-        assert_eq!(args.output_path(), PathBuf::from("/test/path").join(DEFAULT_OUTPUT_FILE_NAME));
+        let args = arguments("/test/path/point_set.json", None);
+        assert_eq!(
+            args.output_path(&args.point_set_files[0]),
+            PathBuf::from("/test/path/point_set.voronoi.json")
+        );
+    }
+
+    #[test]
+    fn test_output_path_unset_format() {
+        let mut args = arguments("/test/path/point_set.json", None);
+        args.format = OutputFormat::Csv;
+        assert_eq!(
+            args.output_path(&args.point_set_files[0]),
+            PathBuf::from("/test/path/point_set.voronoi.csv")
+        );
+        args.delaunay = true;
+        assert_eq!(
+            args.output_path(&args.point_set_files[0]),
+            PathBuf::from("/test/path/point_set.voronoi.json")
+        );
+        let mut args = arguments("", None);
+        args.format = OutputFormat::Svg;
+        assert_eq!(
+            args.output_path(&args.point_set_files[0]),
+            PathBuf::from("geo_bound_voronoi.svg")
+        );
     }
 
     #[test]
     fn test_output_path_unset_invalid() {
-        let args = CommandLineArguments {
-            point_set_file: "".into(),
-            output_path: None,
-        };
-        assert_eq!(args.output_path(), PathBuf::from(DEFAULT_OUTPUT_FILE_NAME));
+        let args = arguments("", None);
+        assert_eq!(
+            args.output_path(&args.point_set_files[0]),
+            PathBuf::from("geo_bound_voronoi.json")
+        );
+    }
+
+    #[test]
+    fn test_reads_stdin() {
+        assert!(arguments("-", None).reads_stdin("-"));
+        assert!(
+            !arguments("/test/path/point_set.json", None).reads_stdin("/test/path/point_set.json")
+        );
+    }
+
+    #[test]
+    fn test_writes_stdout() {
+        assert!(arguments("-", None).writes_stdout("-"));
+        assert!(arguments("-", Some("-".into())).writes_stdout("-"));
+        assert!(arguments("/test/path/point_set.json", Some("-".into()))
+            .writes_stdout("/test/path/point_set.json"));
+        assert!(!arguments("-", Some("test/output/path/file.json".into())).writes_stdout("-"));
+        assert!(!arguments("/test/path/point_set.json", None)
+            .writes_stdout("/test/path/point_set.json"));
+    }
+
+    #[test]
+    fn test_reads_gzip() {
+        assert!(arguments("/test/path/point_set.json.gz", None)
+            .reads_gzip("/test/path/point_set.json.gz"));
+        assert!(arguments("/test/path/point_set.GZ", None).reads_gzip("/test/path/point_set.GZ"));
+        assert!(
+            !arguments("/test/path/point_set.json", None).reads_gzip("/test/path/point_set.json")
+        );
+        assert!(!arguments("-", None).reads_gzip("-"));
+        let mut args = arguments("-", None);
+        args.gzip = true;
+        assert!(args.reads_gzip("-"));
+    }
+
+    #[test]
+    fn test_writes_gzip() {
+        assert!(arguments("/test/path/point_set.json", Some("output.json.gz".into()))
+            .writes_gzip("/test/path/point_set.json"));
+        assert!(!arguments("/test/path/point_set.json.gz", None)
+            .writes_gzip("/test/path/point_set.json.gz"));
+        assert!(!arguments("-", None).writes_gzip("-"));
+        let mut args = arguments("-", None);
+        args.gzip = true;
+        assert!(args.writes_gzip("-"));
+        // The explicit flag does not compress output files without the extension.
+        let mut args = arguments("/test/path/point_set.json", Some("output.json".into()));
+        args.gzip = true;
+        assert!(!args.writes_gzip("/test/path/point_set.json"));
+    }
+
+    #[test]
+    fn test_max_lloyd_iterations() {
+        let args = CommandLineArguments::try_parse_from([
+            "geo-bounded-voronoi",
+            "--lloyd-tolerance",
+            "1e-6",
+            "input.json",
+        ])
+        .unwrap();
+        assert_eq!(args.max_lloyd_iterations(), DEFAULT_MAX_LLOYD_ITERATIONS);
+        let args = CommandLineArguments::try_parse_from([
+            "geo-bounded-voronoi",
+            "--lloyd",
+            "20",
+            "--lloyd-tolerance",
+            "1e-6",
+            "input.json",
+        ])
+        .unwrap();
+        assert_eq!(args.max_lloyd_iterations(), 20);
+    }
+
+    #[test]
+    fn test_parse_pretty() {
+        let args =
+            CommandLineArguments::try_parse_from(["geo-bounded-voronoi", "--pretty", "input.json"])
+                .unwrap();
+        assert!(args.pretty());
+        assert_eq!(args.point_set_files, vec![PathBuf::from("input.json")]);
+        let args =
+            CommandLineArguments::try_parse_from(["geo-bounded-voronoi", "input.json"]).unwrap();
+        assert!(!args.pretty());
+    }
+
+    #[test]
+    fn test_parse_multiple_point_set_files() {
+        let args = CommandLineArguments::try_parse_from([
+            "geo-bounded-voronoi",
+            "--fail-fast",
+            "first.json",
+            "data/second.json",
+        ])
+        .unwrap();
+        assert!(args.is_batch());
+        assert!(args.fail_fast());
+        assert_eq!(
+            args.point_set_files(),
+            &vec![
+                PathBuf::from("first.json"),
+                PathBuf::from("data/second.json")
+            ]
+        );
+        assert!(CommandLineArguments::try_parse_from(["geo-bounded-voronoi"]).is_err());
+    }
+
+    #[test]
+    fn test_output_path_batch() {
+        let mut args = arguments("/test/path/first.json", None);
+        args.point_set_files.push("/other/second.json".into());
+        assert_eq!(
+            args.output_path("/test/path/first.json"),
+            PathBuf::from("/test/path/first.voronoi.json")
+        );
+        assert_eq!(
+            args.output_path("/other/second.json"),
+            PathBuf::from("/other/second.voronoi.json")
+        );
+        assert_eq!(
+            args.output_path("/other/third.json.gz"),
+            PathBuf::from("/other/third.voronoi.json")
+        );
+        args.output_path = Some("/output".into());
+        assert_eq!(
+            args.output_path("/other/second.json"),
+            PathBuf::from("/output/second.voronoi.json")
+        );
+        assert_eq!(
+            args.output_path("/other/third.json.gz"),
+            PathBuf::from("/output/third.voronoi.json")
+        );
+    }
+
+    #[test]
+    fn test_split_output_path() {
+        let mut args = arguments("/test/path/first.json", None);
+        assert_eq!(args.split_output_path("/test/path/first.json", 3), None);
+        args.split_output = Some("/cells".into());
+        assert_eq!(
+            args.split_output_path("/test/path/first.json", 3),
+            Some(PathBuf::from("/cells/cell_3.json"))
+        );
+        args.format = OutputFormat::Geojson;
+        args.point_set_files.push("/other/second.json".into());
+        assert_eq!(
+            args.split_output_path("/other/second.json", 0),
+            Some(PathBuf::from("/cells/second_cell_0.geojson"))
+        );
     }
 }
@@ -0,0 +1,453 @@
+//! This module provides a builder for configuring and computing the bounded voronoi diagramm.
+
+use std::borrow::Borrow;
+
+use getset::Getters;
+
+use crate::{
+    error::VoronoiError,
+    input::BoundedPointSet,
+    voronoi::{
+        compute_voronoi_with_options, BoundedVoronoiCell, SitePlacement, SortKey, VoronoiOptions,
+    },
+};
+
+/// The entry point for computing bounded Voronoi diagramms with a builder.
+///
+/// ```
+/// use geo_bounded_voronoi::{BoundedPointSet, BoundedVoronoi, SitePlacement};
+///
+/// let point_set = BoundedPointSet::with_bbox(
+///     vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]],
+///     [-1.0, -1.0, 1.0, 1.0],
+/// );
+/// let cells = BoundedVoronoi::builder()
+///     .lloyd(5)
+///     .close_rings(true)
+///     .site_placement(SitePlacement::Drop)
+///     .build(point_set)
+///     .unwrap();
+/// assert_eq!(cells.len(), 3);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BoundedVoronoi;
+
+impl BoundedVoronoi {
+    /// Returns a builder with the default [`VoronoiOptions`].
+    pub fn builder() -> BoundedVoronoiBuilder {
+        BoundedVoronoiBuilder::default()
+    }
+}
+
+/// A builder configuring the computation of the bounded Voronoi diagramm.
+/// Each setter consumes the builder and returns it with the according option set.
+#[derive(Clone, Debug, Default, Getters, PartialEq)]
+pub struct BoundedVoronoiBuilder {
+    /// The options the Voronoi diagramm is computed with.
+    #[getset(get = "pub")]
+    options: VoronoiOptions,
+}
+
+impl BoundedVoronoiBuilder {
+    /// Sets if the area of each cell should be included in the output.
+    /// See [`VoronoiOptions::include_area`].
+    ///
+    /// # Parameters
+    ///
+    /// * `area` - if the area of each cell should be included in the output
+    pub fn area(mut self, area: bool) -> Self {
+        self.options = self.options.include_area(area);
+        self
+    }
+
+    /// Sets if the index of each site should be included in the output.
+    /// See [`VoronoiOptions::include_index`].
+    ///
+    /// # Parameters
+    ///
+    /// * `index` - if the index of each site should be included in the output
+    pub fn index(mut self, index: bool) -> Self {
+        self.options = self.options.include_index(index);
+        self
+    }
+
+    /// Sets if the cell polygons before the bound is applied should be included in the output.
+    /// See [`VoronoiOptions::include_unbounded`].
+    ///
+    /// # Parameters
+    ///
+    /// * `unbounded` - if the unbounded cell polygon should be included in the output
+    pub fn unbounded(mut self, unbounded: bool) -> Self {
+        self.options = self.options.include_unbounded(unbounded);
+        self
+    }
+
+    /// Sets the number of Lloyd relaxation iterations.
+    /// See [`VoronoiOptions::lloyd_iterations`].
+    ///
+    /// # Parameters
+    ///
+    /// * `iterations` - the number of Lloyd relaxation iterations
+    pub fn lloyd(mut self, iterations: usize) -> Self {
+        self.options = self.options.lloyd_iterations(iterations);
+        self
+    }
+
+    /// Sets Lloyd relaxation to run until convergence.
+    /// See [`VoronoiOptions::lloyd_until`].
+    ///
+    /// # Parameters
+    ///
+    /// * `tolerance` - the maximum displacement of a site below which the relaxation stops
+    /// * `max_iterations` - the maximum number of Lloyd relaxation iterations
+    pub fn lloyd_until(mut self, tolerance: f64, max_iterations: usize) -> Self {
+        self.options = self.options.lloyd_until(tolerance, max_iterations);
+        self
+    }
+
+    /// Sets the density weighting the centroids of Lloyd relaxation.
+    /// See [`VoronoiOptions::lloyd_weighted`].
+    ///
+    /// # Parameters
+    ///
+    /// * `density` - the density at a location
+    pub fn lloyd_weighted<F: Fn([f64; 2]) -> f64 + Send + Sync + 'static>(
+        mut self,
+        density: F,
+    ) -> Self {
+        self.options = self.options.lloyd_weighted(density);
+        self
+    }
+
+    /// Sets the behaviour for sites that do not lie within the bound centered around them.
+    /// See [`VoronoiOptions::site_placement`].
+    ///
+    /// # Parameters
+    ///
+    /// * `site_placement` - the behaviour for sites outside of the bound
+    pub fn site_placement(mut self, site_placement: SitePlacement) -> Self {
+        self.options = self.options.site_placement(site_placement);
+        self
+    }
+
+    /// Sets the precision of the grid the coordinates are snapped to before clipping.
+    /// See [`VoronoiOptions::snap_precision`].
+    ///
+    /// # Parameters
+    ///
+    /// * `snap_precision` - the spacing of the grid the coordinates are snapped to
+    pub fn snap_precision(mut self, snap_precision: f64) -> Self {
+        self.options = self.options.snap_precision(snap_precision);
+        self
+    }
+
+    /// Sets the distance the bound is shrunk inwards by before clipping.
+    /// See [`VoronoiOptions::inset`].
+    ///
+    /// # Parameters
+    ///
+    /// * `inset` - the distance the bound is shrunk inwards by
+    pub fn inset(mut self, inset: f64) -> Self {
+        self.options = self.options.inset(inset);
+        self
+    }
+
+    /// Sets if the wall time of the phases of the computation should be written to stderr.
+    /// See [`VoronoiOptions::show_timing`].
+    ///
+    /// # Parameters
+    ///
+    /// * `show_timing` - if the wall time of the phases should be written to stderr
+    pub fn show_timing(mut self, show_timing: bool) -> Self {
+        self.options = self.options.show_timing(show_timing);
+        self
+    }
+
+    /// Sets the maximum number of unique sites.
+    /// See [`VoronoiOptions::max_sites`].
+    ///
+    /// # Parameters
+    ///
+    /// * `max_sites` - the maximum number of unique sites
+    pub fn max_sites(mut self, max_sites: usize) -> Self {
+        self.options = self.options.max_sites(max_sites);
+        self
+    }
+
+    /// Sets the maximum coordinate difference of sites that are merged.
+    /// See [`VoronoiOptions::merge_tolerance`].
+    ///
+    /// # Parameters
+    ///
+    /// * `merge_tolerance` - the maximum coordinate difference of merged sites
+    pub fn merge_tolerance(mut self, merge_tolerance: f64) -> Self {
+        self.options = self.options.merge_tolerance(merge_tolerance);
+        self
+    }
+
+    /// Sets if the first vertex of each cell ring should be repeated at its end.
+    /// See [`VoronoiOptions::close_rings`].
+    ///
+    /// # Parameters
+    ///
+    /// * `close_rings` - if the rings of the cells should be closed
+    pub fn close_rings(mut self, close_rings: bool) -> Self {
+        self.options = self.options.close_rings(close_rings);
+        self
+    }
+
+    /// Sets if the cells should be ordered like the first occurrences of their input points.
+    /// See [`VoronoiOptions::input_order`].
+    ///
+    /// # Parameters
+    ///
+    /// * `input_order` - if the cells should be ordered like the input points
+    pub fn input_order(mut self, input_order: bool) -> Self {
+        self.options = self.options.input_order(input_order);
+        self
+    }
+
+    /// Sets the key the cells are sorted by after the bound has been applied.
+    /// See [`VoronoiOptions::sort_by`].
+    ///
+    /// # Parameters
+    ///
+    /// * `sort_key` - the key the cells are sorted by
+    pub fn sort_by(mut self, sort_key: SortKey) -> Self {
+        self.options = self.options.sort_by(sort_key);
+        self
+    }
+
+    /// Sets the margin the convex hull of the points is expanded by, which is used
+    /// as bound instead of the bound of the point set.
+    /// See [`VoronoiOptions::hull_bound`].
+    ///
+    /// # Parameters
+    ///
+    /// * `margin` - the distance the hull is expanded by
+    pub fn hull_bound(mut self, margin: f64) -> Self {
+        self.options = self.options.hull_bound(margin);
+        self
+    }
+
+    /// Sets if a progress bar should be shown for large point sets.
+    /// See [`VoronoiOptions::show_progress`].
+    ///
+    /// # Parameters
+    ///
+    /// * `show_progress` - if a progress bar should be shown for large point sets
+    pub fn show_progress(mut self, show_progress: bool) -> Self {
+        self.options = self.options.show_progress(show_progress);
+        self
+    }
+
+    /// Sets the number of threads the bound is applied to the cells with.
+    /// See [`VoronoiOptions::threads`].
+    ///
+    /// # Parameters
+    ///
+    /// * `threads` - the number of threads
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.options = self.options.threads(threads);
+        self
+    }
+
+    /// Computes the polygon-bound Voronoi diagramm of the input point set
+    /// with the configured options.
+    /// This is equivalent to calling [`compute_voronoi_with_options`].
+    ///
+    /// # Parameters
+    ///
+    /// * `bounded_point_set` - the input point set and bounding geometry
+    pub fn build<T: Borrow<BoundedPointSet>>(
+        &self,
+        bounded_point_set: T,
+    ) -> Result<Vec<BoundedVoronoiCell>, VoronoiError> {
+        compute_voronoi_with_options(bounded_point_set, self.options.clone())
+    }
+}
+
+impl From<BoundedVoronoiBuilder> for VoronoiOptions {
+    fn from(builder: BoundedVoronoiBuilder) -> Self {
+        builder.options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a slightly perturbed 3 by 3 grid of points, which is not specified
+    /// in sorted order, with a square bound.
+    fn grid_bounded_point_set() -> BoundedPointSet {
+        BoundedPointSet::with_bbox(
+            vec![
+                [2.0, 2.0],
+                [0.0, 0.0],
+                [1.0, 0.0],
+                [2.0, 0.0],
+                [0.0, 1.0],
+                [1.0, 1.5],
+                [2.0, 1.0],
+                [0.0, 2.0],
+                [1.0, 2.0],
+            ],
+            [-1.0, -1.0, 1.0, 1.0],
+        )
+    }
+
+    #[test]
+    fn test_builder_default() {
+        let bps = grid_bounded_point_set();
+        let builder = BoundedVoronoi::builder();
+        assert_eq!(builder.options(), &VoronoiOptions::default());
+        assert_eq!(
+            serde_json::to_value(builder.build(&bps).unwrap()).unwrap(),
+            serde_json::to_value(crate::voronoi::compute_voronoi(&bps).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_builder_area() {
+        let cells = BoundedVoronoi::builder()
+            .area(true)
+            .build(grid_bounded_point_set())
+            .unwrap();
+        assert!(serde_json::to_value(&cells[0]).unwrap()["area"].is_f64());
+    }
+
+    #[test]
+    fn test_builder_unbounded() {
+        let cells = BoundedVoronoi::builder()
+            .unbounded(true)
+            .build(grid_bounded_point_set())
+            .unwrap();
+        assert!(cells.iter().all(|cell| cell.unbounded_cell().is_some()));
+    }
+
+    #[test]
+    fn test_builder_lloyd() {
+        let cells = BoundedVoronoi::builder()
+            .lloyd(5)
+            .build(grid_bounded_point_set())
+            .unwrap();
+        assert!(cells
+            .iter()
+            .any(|cell| cell.original_site() != Some(cell.site())));
+    }
+
+    #[test]
+    fn test_builder_lloyd_until() {
+        let builder = BoundedVoronoi::builder().lloyd_until(1e-3, 50);
+        assert_eq!(builder.options(), &VoronoiOptions::default().lloyd_until(1e-3, 50));
+        let cells = builder.build(grid_bounded_point_set()).unwrap();
+        assert!(cells
+            .iter()
+            .any(|cell| cell.original_site() != Some(cell.site())));
+    }
+
+    #[test]
+    fn test_builder_lloyd_weighted() {
+        let cells = BoundedVoronoi::builder()
+            .lloyd(3)
+            .lloyd_weighted(|location| if location[1] > 1.0 { 10.0 } else { 1.0 })
+            .build(grid_bounded_point_set())
+            .unwrap();
+        let uniform_cells = BoundedVoronoi::builder()
+            .lloyd_until(0.0, 3)
+            .build(grid_bounded_point_set())
+            .unwrap();
+        let mean_y = |cells: &[BoundedVoronoiCell]| {
+            cells.iter().map(|cell| cell.site()[1]).sum::<f64>() / cells.len() as f64
+        };
+        assert!(mean_y(&cells) > mean_y(&uniform_cells));
+    }
+
+    #[test]
+    fn test_builder_site_placement() {
+        let bps = BoundedPointSet::new(
+            vec![[0.0, 0.0], [10.0, 0.0], [0.0, 10.0]],
+            vec![
+                [0.0, 0.0],
+                [2.0, 0.0],
+                [2.0, 0.5],
+                [0.5, 0.5],
+                [0.5, 2.0],
+                [0.0, 2.0],
+                [0.0, 0.0],
+            ],
+        );
+        assert_eq!(
+            BoundedVoronoi::builder()
+                .site_placement(SitePlacement::Drop)
+                .build(&bps)
+                .unwrap_err(),
+            VoronoiError::NoSiteWithinBound
+        );
+        let cells = BoundedVoronoi::builder()
+            .site_placement(SitePlacement::KeepEmpty)
+            .build(&bps)
+            .unwrap();
+        assert!(cells.iter().all(|cell| cell.cell().is_empty()));
+    }
+
+    #[test]
+    fn test_builder_snap_precision() {
+        let cells = BoundedVoronoi::builder()
+            .snap_precision(0.25)
+            .build(grid_bounded_point_set())
+            .unwrap();
+        assert!(cells
+            .iter()
+            .flat_map(|cell| cell.cell())
+            .all(|vertex| vertex[0] % 0.25 == 0.0 && vertex[1] % 0.25 == 0.0));
+    }
+
+    #[test]
+    fn test_builder_close_rings() {
+        let cells = BoundedVoronoi::builder()
+            .close_rings(true)
+            .build(grid_bounded_point_set())
+            .unwrap();
+        assert!(cells
+            .iter()
+            .all(|cell| cell.cell().first() == cell.cell().last()));
+    }
+
+    #[test]
+    fn test_builder_input_order() {
+        let cells = BoundedVoronoi::builder()
+            .input_order(true)
+            .build(grid_bounded_point_set())
+            .unwrap();
+        assert_eq!(cells[0].site(), [2.0, 2.0]);
+        let cells = BoundedVoronoi::builder()
+            .build(grid_bounded_point_set())
+            .unwrap();
+        assert_eq!(cells[0].site(), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_builder_sort_by() {
+        let builder = BoundedVoronoi::builder().sort_by(SortKey::Area);
+        assert_eq!(VoronoiOptions::from(builder), VoronoiOptions::default().sort_by(SortKey::Area));
+    }
+
+    #[test]
+    fn test_builder_hull_bound() {
+        let builder = BoundedVoronoi::builder().hull_bound(0.5);
+        assert_eq!(VoronoiOptions::from(builder), VoronoiOptions::default().hull_bound(0.5));
+    }
+
+    #[test]
+    fn test_builder_show_progress() {
+        let builder = BoundedVoronoi::builder().show_progress(true);
+        assert_eq!(VoronoiOptions::from(builder), VoronoiOptions::default().show_progress(true));
+    }
+
+    #[test]
+    fn test_builder_threads() {
+        let builder = BoundedVoronoi::builder().threads(2);
+        assert_eq!(VoronoiOptions::from(builder), VoronoiOptions::default().threads(2));
+    }
+}
@@ -0,0 +1,158 @@
+//! This module provides a Voronoi diagramm that can be edited after it has been computed.
+
+use std::borrow::Borrow;
+
+use getset::Getters;
+
+use crate::{
+    error::VoronoiError,
+    input::{BoundedPointSet, Point2D},
+    voronoi::{compute_voronoi_with_options, BoundedVoronoiCell, VoronoiOptions},
+};
+
+/// A bounded Voronoi diagramm that keeps its point set and options together with
+/// the computed cells, so that sites can be inserted interactively.
+///
+/// ```
+/// use geo_bounded_voronoi::{BoundedPointSet, VoronoiDiagram, VoronoiOptions};
+///
+/// let point_set = BoundedPointSet::with_bbox(vec![[0.0, 0.0], [1.0, 0.0]], [-1.0, -1.0, 1.0, 1.0]);
+/// let mut diagram = VoronoiDiagram::new(point_set, VoronoiOptions::default()).unwrap();
+/// let index = diagram.insert_site([0.5, 1.0]).unwrap();
+/// assert_eq!(index, Some(1));
+/// assert_eq!(diagram.cells().len(), 3);
+/// ```
+#[derive(Clone, Debug, Getters)]
+pub struct VoronoiDiagram {
+    /// The input point set including all inserted sites.
+    #[getset(get = "pub")]
+    bounded_point_set: BoundedPointSet,
+    /// The options the Voronoi diagramm is computed with.
+    #[getset(get = "pub")]
+    options: VoronoiOptions,
+    /// The bounded Voronoi cells in the order of [`compute_voronoi_with_options`].
+    #[getset(get = "pub")]
+    cells: Vec<BoundedVoronoiCell>,
+}
+
+impl VoronoiDiagram {
+    /// Computes the bounded Voronoi diagramm of the input point set with the specified options.
+    ///
+    /// # Parameters
+    ///
+    /// * `bounded_point_set` - the input point set and bounding geometry
+    /// * `options` - the options controlling the computation
+    pub fn new<T: Borrow<VoronoiOptions>>(
+        bounded_point_set: BoundedPointSet,
+        options: T,
+    ) -> Result<Self, VoronoiError> {
+        let options = options.borrow().clone();
+        let cells = compute_voronoi_with_options(&bounded_point_set, &options)?;
+        Ok(Self {
+            bounded_point_set,
+            options,
+            cells,
+        })
+    }
+
+    /// Inserts the site into the diagramm and updates the cells, so that they equal
+    /// the cells computed from the point set with the site appended.
+    /// Returns the position of the cell of the inserted site in [`VoronoiDiagram::cells`]
+    /// or [`None`] if the site has no cell of its own, e.g. because it is invalid,
+    /// already part of the point set, merged with another site or dropped.
+    /// If the cells cannot be computed, the diagramm is left unchanged.
+    ///
+    /// `voronoice` does not support inserting sites into an existing triangulation,
+    /// so the whole diagramm is currently recomputed. Only the cells around the new
+    /// site actually change, so this can be replaced by a local update without
+    /// changing the signature.
+    ///
+    /// # Parameters
+    ///
+    /// * `site` - the site to insert
+    pub fn insert_site(&mut self, site: [f64; 2]) -> Result<Option<usize>, VoronoiError> {
+        let is_new_site = Point2D::new(site[0], site[1])
+            .is_some_and(|point| !self.bounded_point_set.point_set().contains(&point));
+        let mut bounded_point_set = self.bounded_point_set.clone();
+        bounded_point_set.push_point(site);
+        self.cells = compute_voronoi_with_options(&bounded_point_set, &self.options)?;
+        self.bounded_point_set = bounded_point_set;
+        Ok(is_new_site
+            .then(|| {
+                self.cells
+                    .iter()
+                    .position(|cell| cell.original_site().unwrap_or(cell.site()) == site)
+            })
+            .flatten())
+    }
+
+    /// Consumes the diagramm and returns its cells.
+    pub fn into_cells(self) -> Vec<BoundedVoronoiCell> {
+        self.cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns a point set with an irregular layout in the unit square.
+    fn bounded_point_set() -> BoundedPointSet {
+        BoundedPointSet::new(
+            vec![[0.1, 0.1], [0.8, 0.2], [0.5, 0.9], [0.2, 0.6], [0.45, 0.4]],
+            vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+        )
+    }
+
+    #[test]
+    fn test_voronoidiagram_insert_site() {
+        let options = VoronoiOptions::default().include_index(true);
+        let mut diagram = VoronoiDiagram::new(bounded_point_set(), &options).unwrap();
+        assert!(diagram.insert_site([0.7, 0.6]).unwrap().is_some());
+        let index = diagram.insert_site([0.3, 0.3]).unwrap();
+        let recomputed = compute_voronoi_with_options(
+            BoundedPointSet::new(
+                vec![
+                    [0.1, 0.1],
+                    [0.8, 0.2],
+                    [0.5, 0.9],
+                    [0.2, 0.6],
+                    [0.45, 0.4],
+                    [0.7, 0.6],
+                    [0.3, 0.3],
+                ],
+                vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+            ),
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(diagram.cells()).unwrap(),
+            serde_json::to_value(&recomputed).unwrap()
+        );
+        assert_eq!(diagram.cells()[index.unwrap()].site(), [0.3, 0.3]);
+        assert_eq!(diagram.bounded_point_set().point_set().len(), 7);
+    }
+
+    #[test]
+    fn test_voronoidiagram_insert_existing_site() {
+        let mut diagram =
+            VoronoiDiagram::new(bounded_point_set(), VoronoiOptions::default()).unwrap();
+        assert_eq!(diagram.insert_site([0.8, 0.2]).unwrap(), None);
+        assert_eq!(diagram.insert_site([f64::NAN, 0.2]).unwrap(), None);
+        assert_eq!(diagram.cells().len(), 5);
+        // A failed computation leaves the diagramm unchanged.
+        let mut diagram =
+            VoronoiDiagram::new(bounded_point_set(), VoronoiOptions::default().max_sites(5))
+                .unwrap();
+        assert_eq!(
+            diagram.insert_site([0.7, 0.6]).unwrap_err(),
+            VoronoiError::TooManySites {
+                sites: 6,
+                max_sites: 5
+            }
+        );
+        assert_eq!(diagram.cells().len(), 5);
+        assert_eq!(diagram.bounded_point_set().point_set().len(), 5);
+    }
+}
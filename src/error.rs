@@ -0,0 +1,317 @@
+//! This module defines the errors that can occur during the computation
+//! of the bounded voronoi diagramm.
+
+use std::fmt::Display;
+
+/// An error that occurred during the computation of the bounded Voronoi diagramm.
+#[derive(Clone, Debug, PartialEq)]
+pub enum VoronoiError {
+    /// Less than 3 points have been specified for the bounding polygon.
+    TooFewBoundPoints,
+    /// Less than 3 points have been specified for a hole of the bounding polygon.
+    TooFewHolePoints,
+    /// The point set does not contain any valid point.
+    EmptyPointSet,
+    /// The point set contains more unique sites than allowed.
+    TooManySites {
+        /// The number of unique sites.
+        sites: usize,
+        /// The maximum number of sites.
+        max_sites: usize,
+    },
+    /// A polygon is invalid, e.g. its bounding rectangle cannot be calculated.
+    InvalidPolygon,
+    /// Both a bounding polygon and a bounding rectangle have been specified.
+    ConflictingBounds,
+    /// The bounding rectangle is empty or contains a non-finite coordinate.
+    InvalidBoundingBox,
+    /// The bound consists of several disjoint regions, but a single polygon is required.
+    MultiPolygonBound,
+    /// The circular bound has a non-finite center, a radius that is not a positive,
+    /// finite number or less than 3 segments.
+    InvalidCircle,
+    /// The rings of the bound collapse or intersect after shrinking it inwards.
+    InvalidInset {
+        /// The inset distance.
+        distance: f64,
+    },
+    /// A ring of the bounding polygon intersects itself.
+    SelfIntersection {
+        /// The index of the hole or [`None`] for the exterior ring.
+        hole: Option<usize>,
+        /// The indices of the intersecting segments, where segment `i`
+        /// connects the points `i` and `i + 1`.
+        segments: [usize; 2],
+    },
+    /// The bound centered around the site does not intersect the cell of the site.
+    NoIntersection {
+        /// The site of the cell.
+        site: [f64; 2],
+    },
+    /// The site does not lie within the bound centered around it.
+    SiteOutsideBound {
+        /// The offending site.
+        site: [f64; 2],
+    },
+    /// No site lies within the bound centered around it.
+    NoSiteWithinBound,
+    /// No Voronoi diagramm could be built for the point set.
+    BuilderFailed,
+    /// The number of weights does not match the number of points.
+    WeightCountMismatch {
+        /// The number of weights.
+        weights: usize,
+        /// The number of points.
+        points: usize,
+    },
+    /// A weight is not a positive, finite number.
+    InvalidWeight {
+        /// The offending weight.
+        weight: f64,
+    },
+    /// The resolution of an approximation is 0.
+    InvalidResolution,
+    /// The number of radii does not match the number of points.
+    RadiusCountMismatch {
+        /// The number of radii.
+        radii: usize,
+        /// The number of points.
+        points: usize,
+    },
+    /// A radius is not a non-negative, finite number.
+    InvalidRadius {
+        /// The offending radius.
+        radius: f64,
+    },
+    /// An integer coordinate cannot be represented exactly as floating point number.
+    IntegerOutOfRange {
+        /// The offending integer.
+        value: i64,
+    },
+    /// The projection definition is invalid.
+    InvalidProjection {
+        /// The offending projection definition.
+        definition: String,
+    },
+    /// A coordinate cannot be transformed by the projection.
+    ProjectionFailed {
+        /// The offending coordinate.
+        coordinate: [f64; 2],
+    },
+    /// The number of labels does not match the number of points.
+    LabelCountMismatch {
+        /// The number of labels.
+        labels: usize,
+        /// The number of points.
+        points: usize,
+    },
+    /// The sites should be shuffled with a seed and ordered like the input at once.
+    ConflictingSiteOrder,
+    /// The convex hull of the point set has no area, e.g. because all points are
+    /// collinear, and cannot be used as bound.
+    DegenerateHull,
+    /// The number of fixed flags does not match the number of points.
+    FixedCountMismatch {
+        /// The number of fixed flags.
+        fixed: usize,
+        /// The number of points.
+        points: usize,
+    },
+    /// The thread pool for applying the bound to the cells could not be built.
+    ThreadPoolFailed,
+    /// The extent of the point set exceeds the bounding rectangle of the bound.
+    SitesOutsideBound {
+        /// The number of sites outside the bounding rectangle.
+        sites: usize,
+    },
+}
+
+impl Display for VoronoiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VoronoiError::TooFewBoundPoints => {
+                write!(f, "At least 3 points are needed to specify a bounding polygon.")
+            },
+            VoronoiError::TooFewHolePoints => write!(
+                f,
+                "At least 3 points are needed to specify a hole of the bounding polygon."
+            ),
+            VoronoiError::EmptyPointSet => {
+                write!(f, "The point set must contain at least one valid point.")
+            },
+            VoronoiError::TooManySites { sites, max_sites } => write!(
+                f,
+                "The point set contains {} unique sites, but at most {} are allowed.",
+                sites, max_sites
+            ),
+            VoronoiError::InvalidPolygon => {
+                write!(f, "Invalid polygon. Cannot calculate bounding rectangle.")
+            },
+            VoronoiError::ConflictingBounds => write!(
+                f,
+                "Either a bounding polygon or a bounding rectangle must be specified, but not both."
+            ),
+            VoronoiError::InvalidBoundingBox => write!(
+                f,
+                "The bounding rectangle must consist of finite coordinates with the minimum \
+                 below the maximum."
+            ),
+            VoronoiError::MultiPolygonBound => write!(
+                f,
+                "The bound consists of several regions, but a single polygon is required."
+            ),
+            VoronoiError::InvalidInset { distance } => write!(
+                f,
+                "The rings of the bound collapse or intersect when insetting it by {}.",
+                distance
+            ),
+            VoronoiError::InvalidCircle => write!(
+                f,
+                "The circular bound must have a finite center, a positive, finite radius \
+                 and at least 3 segments."
+            ),
+            VoronoiError::SelfIntersection { hole, segments } => {
+                match hole {
+                    Some(hole) => write!(f, "Hole {} of the bounding polygon", hole)?,
+                    None => write!(f, "The bounding polygon")?,
+                }
+                write!(
+                    f,
+                    " intersects itself at the segments {} and {}.",
+                    segments[0], segments[1]
+                )
+            },
+            VoronoiError::NoIntersection { site } => write!(
+                f,
+                "No intersection could be found between the bound and the voronoi cell of site [{}, {}].",
+                site[0], site[1]
+            ),
+            VoronoiError::SiteOutsideBound { site } => write!(
+                f,
+                "The site [{}, {}] does not lie within the bound centered around it.",
+                site[0], site[1]
+            ),
+            VoronoiError::NoSiteWithinBound => {
+                write!(f, "No site lies within the bound centered around it.")
+            },
+            VoronoiError::BuilderFailed => {
+                write!(f, "No Voronoi diagramm could be built for the specified point set.")
+            },
+            VoronoiError::WeightCountMismatch { weights, points } => write!(
+                f,
+                "The number of weights ({}) does not match the number of points ({}).",
+                weights, points
+            ),
+            VoronoiError::InvalidWeight { weight } => {
+                write!(f, "The weight {} is not a positive, finite number.", weight)
+            },
+            VoronoiError::InvalidResolution => {
+                write!(f, "The resolution must be at least 1.")
+            },
+            VoronoiError::RadiusCountMismatch { radii, points } => write!(
+                f,
+                "The number of radii ({}) does not match the number of points ({}).",
+                radii, points
+            ),
+            VoronoiError::InvalidRadius { radius } => {
+                write!(f, "The radius {} is not a non-negative, finite number.", radius)
+            },
+            VoronoiError::IntegerOutOfRange { value } => write!(
+                f,
+                "The integer coordinate {} lies beyond ±2^53 and cannot be represented exactly.",
+                value
+            ),
+            VoronoiError::InvalidProjection { definition } => {
+                write!(f, "The projection \"{}\" is invalid.", definition)
+            },
+            VoronoiError::ProjectionFailed { coordinate } => write!(
+                f,
+                "The coordinate [{}, {}] cannot be projected.",
+                coordinate[0], coordinate[1]
+            ),
+            VoronoiError::LabelCountMismatch { labels, points } => write!(
+                f,
+                "The number of labels ({}) does not match the number of points ({}).",
+                labels, points
+            ),
+            VoronoiError::ConflictingSiteOrder => write!(
+                f,
+                "The sites can either be shuffled with a seed or ordered like the input, \
+                 but not both."
+            ),
+            VoronoiError::DegenerateHull => write!(
+                f,
+                "The convex hull of the points has no area and cannot be used as bound."
+            ),
+            VoronoiError::FixedCountMismatch { fixed, points } => write!(
+                f,
+                "The number of fixed flags ({}) does not match the number of points ({}).",
+                fixed, points
+            ),
+            VoronoiError::ThreadPoolFailed => {
+                write!(f, "The thread pool for applying the bound could not be built.")
+            },
+            VoronoiError::SitesOutsideBound { sites } => write!(
+                f,
+                "{} sites lie outside the bounding rectangle of the bound centered around the \
+                 point set.",
+                sites
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VoronoiError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_voronoierror_display_self_intersection() {
+        assert_eq!(
+            VoronoiError::SelfIntersection {
+                hole: None,
+                segments: [0, 2]
+            }
+            .to_string(),
+            "The bounding polygon intersects itself at the segments 0 and 2."
+        );
+        assert_eq!(
+            VoronoiError::SelfIntersection {
+                hole: Some(1),
+                segments: [3, 5]
+            }
+            .to_string(),
+            "Hole 1 of the bounding polygon intersects itself at the segments 3 and 5."
+        );
+    }
+
+    #[test]
+    fn test_voronoierror_display_site() {
+        assert_eq!(
+            VoronoiError::SiteOutsideBound { site: [1.5, -2.0] }.to_string(),
+            "The site [1.5, -2] does not lie within the bound centered around it."
+        );
+        assert_eq!(
+            VoronoiError::NoIntersection { site: [0.0, 3.25] }.to_string(),
+            "No intersection could be found between the bound and the voronoi cell of site [0, 3.25]."
+        );
+    }
+
+    #[test]
+    fn test_voronoierror_display_weight() {
+        assert_eq!(
+            VoronoiError::InvalidWeight { weight: -1.5 }.to_string(),
+            "The weight -1.5 is not a positive, finite number."
+        );
+        assert_eq!(
+            VoronoiError::WeightCountMismatch {
+                weights: 2,
+                points: 3
+            }
+            .to_string(),
+            "The number of weights (2) does not match the number of points (3)."
+        );
+    }
+}
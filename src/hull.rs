@@ -0,0 +1,374 @@
+//! This module computes the lower convex hull of points in three dimensions.
+//!
+//! Lifting the sites of a power diagramm onto the paraboloid `z = x² + y² - r²`
+//! turns the faces of the lower convex hull into the triangles of the regular
+//! triangulation, which is dual to the power diagramm. The hull is built with the
+//! QuickHull algorithm, which repeatedly adds the point farthest outside of a face
+//! and replaces all faces visible from it by a fan of faces connecting the point to
+//! the horizon.
+
+use std::collections::{HashMap, HashSet};
+
+/// The distance of a point from a face relative to the extent of the points
+/// below which the point is treated as lying on the face.
+const HULL_EPSILON: f64 = 1e-12;
+
+/// A point in three dimensions.
+type Vector = [f64; 3];
+
+/// Returns the difference of two vectors.
+///
+/// # Parameters
+///
+/// * `a` - the vector to subtract from
+/// * `b` - the vector to subtract
+fn sub(a: Vector, b: Vector) -> Vector {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+/// Returns the dot product of two vectors.
+///
+/// # Parameters
+///
+/// * `a` - the first vector
+/// * `b` - the second vector
+fn dot(a: Vector, b: Vector) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Returns the cross product of two vectors.
+///
+/// # Parameters
+///
+/// * `a` - the first vector
+/// * `b` - the second vector
+fn cross(a: Vector, b: Vector) -> Vector {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+/// A triangular face of the convex hull.
+struct HullFace {
+    /// The indices of the vertices, which are ordered counter-clockwise
+    /// if seen from outside of the hull.
+    vertices: [usize; 3],
+    /// The indices of the faces adjacent across the edges starting at the vertices.
+    neighbors: [usize; 3],
+    /// The outward unit normal of the face.
+    normal: Vector,
+    /// The distance of the plane of the face from the origin along the normal.
+    offset: f64,
+    /// The indices of the points lying outside of the face.
+    outside: Vec<usize>,
+    /// If the face is still part of the hull.
+    alive: bool,
+}
+
+impl HullFace {
+    /// Creates a new face without neighbors and outside points.
+    ///
+    /// # Parameters
+    ///
+    /// * `vertices` - the indices of the vertices ordered counter-clockwise from outside
+    /// * `points` - the points the indices refer to
+    fn new(vertices: [usize; 3], points: &[Vector]) -> Self {
+        let [a, b, c] = vertices.map(|vertex| points[vertex]);
+        let normal = cross(sub(b, a), sub(c, a));
+        let length = dot(normal, normal).sqrt();
+        let normal = normal.map(|value| value / length);
+        Self {
+            vertices,
+            neighbors: [usize::MAX; 3],
+            normal,
+            offset: dot(normal, a),
+            outside: Vec::new(),
+            alive: true,
+        }
+    }
+
+    /// Returns the signed distance of the point from the plane of the face,
+    /// which is positive outside of the hull.
+    ///
+    /// # Parameters
+    ///
+    /// * `point` - the point to measure the distance of
+    fn distance(&self, point: Vector) -> f64 {
+        dot(self.normal, point) - self.offset
+    }
+}
+
+/// Returns the index of the point with the largest value of the key or [`None`]
+/// if there are no points.
+///
+/// # Parameters
+///
+/// * `points` - the points to compare
+/// * `key` - the key of a point
+fn max_point_by<F: Fn(Vector) -> f64>(points: &[Vector], key: F) -> Option<usize> {
+    (0..points.len()).max_by(|&a, &b| key(points[a]).total_cmp(&key(points[b])))
+}
+
+/// Assigns each point to the first face it lies outside of.
+/// Points that do not lie outside of any face lie within the hull and are discarded.
+///
+/// # Parameters
+///
+/// * `candidates` - the indices of the points to assign
+/// * `faces` - the faces of the hull
+/// * `face_indices` - the indices of the faces the points can be assigned to
+/// * `points` - the points the indices refer to
+/// * `epsilon` - the distance below which a point is treated as lying on a face
+fn assign_outside_points(
+    candidates: &[usize],
+    faces: &mut [HullFace],
+    face_indices: &[usize],
+    points: &[Vector],
+    epsilon: f64,
+) {
+    for &candidate in candidates {
+        if let Some(&face) = face_indices
+            .iter()
+            .find(|&&face| faces[face].distance(points[candidate]) > epsilon)
+        {
+            faces[face].outside.push(candidate);
+        }
+    }
+}
+
+/// Returns the faces of the tetrahedron spanned by four points that are not coplanar
+/// or [`None`] if all points lie within a plane.
+///
+/// # Parameters
+///
+/// * `points` - the points to span the tetrahedron with
+/// * `epsilon` - the distance below which points are treated as coplanar
+fn initial_tetrahedron(points: &[Vector], epsilon: f64) -> Option<Vec<HullFace>> {
+    let first = max_point_by(points, |point| -point[0])?;
+    let second = max_point_by(points, |point| {
+        let difference = sub(point, points[first]);
+        dot(difference, difference)
+    })?;
+    let direction = sub(points[second], points[first]);
+    let direction_length = dot(direction, direction).sqrt();
+    if direction_length <= epsilon {
+        return None;
+    }
+    let line_distance = |point: Vector| {
+        let normal = cross(sub(point, points[first]), direction);
+        dot(normal, normal).sqrt() / direction_length
+    };
+    let third = max_point_by(points, line_distance)?;
+    if line_distance(points[third]) <= epsilon {
+        return None;
+    }
+    let base = HullFace::new([first, second, third], points);
+    let fourth = max_point_by(points, |point| base.distance(point).abs())?;
+    if base.distance(points[fourth]).abs() <= epsilon {
+        return None;
+    }
+
+    // The base is oriented, so that the fourth point lies below it.
+    let [a, b, c] = if base.distance(points[fourth]) > 0.0 {
+        [first, third, second]
+    } else {
+        [first, second, third]
+    };
+    let mut faces: Vec<HullFace> = [[a, b, c], [b, a, fourth], [c, b, fourth], [a, c, fourth]]
+        .into_iter()
+        .map(|vertices| HullFace::new(vertices, points))
+        .collect();
+    let mut edges = HashMap::new();
+    for (face_index, face) in faces.iter().enumerate() {
+        for slot in 0..3 {
+            edges.insert((face.vertices[slot], face.vertices[(slot + 1) % 3]), face_index);
+        }
+    }
+    for face in faces.iter_mut() {
+        for slot in 0..3 {
+            face.neighbors[slot] = edges[&(face.vertices[(slot + 1) % 3], face.vertices[slot])];
+        }
+    }
+    Some(faces)
+}
+
+/// Returns the indices of the points adjacent to each point along the edges of the
+/// lower convex hull, i.e. the faces whose outward normals do not point upwards,
+/// or [`None`] if the points do not span a volume, e.g. because there are fewer than
+/// four points or all points lie within a plane.
+/// Points that are no vertices of the lower hull have no adjacent points.
+/// The adjacent points of each point are sorted and unique.
+///
+/// # Parameters
+///
+/// * `points` - the points to compute the lower convex hull of
+pub(crate) fn lower_hull_neighbors(points: &[Vector]) -> Option<Vec<Vec<usize>>> {
+    let scale = points
+        .iter()
+        .flatten()
+        .fold(0.0f64, |scale, value| scale.max(value.abs()));
+    let epsilon = HULL_EPSILON * scale;
+    let mut faces = initial_tetrahedron(points, epsilon)?;
+    let initial_faces: Vec<usize> = (0..faces.len()).collect();
+    let candidates: Vec<usize> = (0..points.len())
+        .filter(|point| !faces.iter().any(|face| face.vertices.contains(point)))
+        .collect();
+    assign_outside_points(&candidates, &mut faces, &initial_faces, points, epsilon);
+
+    let mut pending = initial_faces;
+    while let Some(start) = pending.pop() {
+        if !faces[start].alive || faces[start].outside.is_empty() {
+            continue;
+        }
+        let apex = *faces[start]
+            .outside
+            .iter()
+            .max_by(|&&a, &&b| {
+                faces[start]
+                    .distance(points[a])
+                    .total_cmp(&faces[start].distance(points[b]))
+            })
+            .expect("The outside points are not empty.");
+
+        // Collects the faces visible from the apex and the edges of the horizon
+        // between the visible faces and the remaining faces.
+        let mut visible = HashSet::from([start]);
+        let mut stack = vec![start];
+        let mut horizon = Vec::new();
+        while let Some(face) = stack.pop() {
+            for slot in 0..3 {
+                let neighbor = faces[face].neighbors[slot];
+                if visible.contains(&neighbor) {
+                    continue;
+                }
+                if faces[neighbor].distance(points[apex]) > epsilon {
+                    visible.insert(neighbor);
+                    stack.push(neighbor);
+                } else {
+                    horizon.push((
+                        faces[face].vertices[slot],
+                        faces[face].vertices[(slot + 1) % 3],
+                        neighbor,
+                    ));
+                }
+            }
+        }
+
+        // Connects the apex to each horizon edge, keeping the orientation of the edge.
+        let mut new_faces = Vec::with_capacity(horizon.len());
+        let mut faces_by_start = HashMap::new();
+        let mut faces_by_end = HashMap::new();
+        for &(start_vertex, end_vertex, neighbor) in &horizon {
+            let face_index = faces.len();
+            let mut face = HullFace::new([start_vertex, end_vertex, apex], points);
+            face.neighbors[0] = neighbor;
+            let neighbor_face = &mut faces[neighbor];
+            for slot in 0..3 {
+                if neighbor_face.vertices[slot] == end_vertex
+                    && neighbor_face.vertices[(slot + 1) % 3] == start_vertex
+                {
+                    neighbor_face.neighbors[slot] = face_index;
+                }
+            }
+            faces.push(face);
+            faces_by_start.insert(start_vertex, face_index);
+            faces_by_end.insert(end_vertex, face_index);
+            new_faces.push(face_index);
+        }
+        for &face_index in &new_faces {
+            let [start_vertex, end_vertex, _] = faces[face_index].vertices;
+            faces[face_index].neighbors[1] = *faces_by_start.get(&end_vertex)?;
+            faces[face_index].neighbors[2] = *faces_by_end.get(&start_vertex)?;
+        }
+
+        // Reassigns the points outside of the removed faces to the new faces.
+        let mut candidates = Vec::new();
+        for &face in &visible {
+            faces[face].alive = false;
+            candidates.append(&mut faces[face].outside);
+        }
+        candidates.retain(|&candidate| candidate != apex);
+        assign_outside_points(&candidates, &mut faces, &new_faces, points, epsilon);
+        pending.extend(new_faces);
+    }
+
+    let mut neighbors = vec![Vec::new(); points.len()];
+    // Vertical faces are included, as additional neighbors do not change the cells.
+    for face in faces
+        .iter()
+        .filter(|face| face.alive && face.normal[2] <= HULL_EPSILON)
+    {
+        for slot in 0..3 {
+            let (a, b) = (face.vertices[slot], face.vertices[(slot + 1) % 3]);
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+    }
+    for point_neighbors in neighbors.iter_mut() {
+        point_neighbors.sort_unstable();
+        point_neighbors.dedup();
+    }
+    Some(neighbors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Lifts the points onto the paraboloid `z = x² + y²`.
+    ///
+    /// # Parameters
+    ///
+    /// * `points` - the points to lift
+    fn lift(points: &[[f64; 2]]) -> Vec<Vector> {
+        points.iter().map(|&[x, y]| [x, y, x * x + y * y]).collect()
+    }
+
+    #[test]
+    fn test_lower_hull_neighbors_delaunay() {
+        // The centre is adjacent to all corners, which are adjacent to the
+        // neighboring corners along the convex hull.
+        let points = lift(&[
+            [0.0, 0.0],
+            [2.0, 0.1],
+            [-0.1, 2.0],
+            [-2.0, -0.1],
+            [0.1, -2.0],
+        ]);
+        assert_eq!(
+            lower_hull_neighbors(&points).unwrap(),
+            vec![
+                vec![1, 2, 3, 4],
+                vec![0, 2, 4],
+                vec![0, 1, 3],
+                vec![0, 2, 4],
+                vec![0, 1, 3]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lower_hull_neighbors_hidden_point() {
+        // The centre is lifted above the lower hull of the corners.
+        let mut points = lift(&[
+            [0.0, 0.0],
+            [2.0, 0.1],
+            [-0.1, 2.0],
+            [-2.0, -0.1],
+            [0.1, -2.0],
+        ]);
+        points[0][2] = 10.0;
+        let neighbors = lower_hull_neighbors(&points).unwrap();
+        assert!(neighbors[0].is_empty());
+        assert!(neighbors[1..].iter().all(|neighbors| neighbors.len() >= 2));
+    }
+
+    #[test]
+    fn test_lower_hull_neighbors_degenerate() {
+        assert!(lower_hull_neighbors(&lift(&[[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]])).is_none());
+        assert!(lower_hull_neighbors(&lift(&[[0.0, 0.0], [1.0, 0.0], [2.0, 0.0], [3.0, 0.0]]))
+            .is_none());
+    }
+}
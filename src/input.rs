@@ -1,42 +1,681 @@
 //! This module handles parsing of input data.
 
 use core::f64;
-use std::{borrow::Borrow, collections::HashSet};
+use std::{
+    borrow::Borrow,
+    collections::{HashMap, HashSet},
+};
 
-use geo::{BoundingRect, LineString, Polygon};
+use geo::{
+    line_intersection::{line_intersection, LineIntersection},
+    orient::Direction,
+    Area, BooleanOps, BoundingRect, ConvexHull, EuclideanLength, Intersects, LineString,
+    MultiPoint, MultiPolygon, Orient, Polygon,
+};
 use getset::{CopyGetters, Getters};
 use serde::{Deserialize, Serialize};
 
+use crate::error::VoronoiError;
+
+/// The largest integer magnitude up to which all integers can be
+/// represented exactly as floating point number.
+const MAX_EXACT_INTEGER: u64 = 1 << f64::MANTISSA_DIGITS;
+/// The default number of segments a circular bound is approximated with.
+pub const DEFAULT_CIRCLE_SEGMENTS: usize = 64;
+/// The number of vertices of the regular polygon each point is replaced with
+/// when expanding the convex hull by a margin.
+const HULL_MARGIN_SEGMENTS: usize = 32;
+
+/// The arbitrary properties of an input point, which are passed on to its cell.
+pub type PointProperties = serde_json::Map<String, serde_json::Value>;
+
 /// A set of 2d points bound by a specified polygon.
 #[derive(Getters, Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct BoundedPointSet {
     /// The set of 2d points.
-    point_set: Vec<[f64; 2]>,
-    /// The bounding polygon.
+    #[serde(rename = "points", alias = "point_set")]
+    point_set: Vec<InputPoint>,
+    /// The bounding polygon or the bounding regions.
+    #[getset(get = "pub")]
+    #[serde(default)]
+    bound: BoundKind,
+    /// The axis-aligned bounding rectangle as `[min_x, min_y, max_x, max_y]`,
+    /// which can be specified instead of the bounding polygon.
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bbox: Option<[f64; 4]>,
+    /// The holes / interiors of the bounding polygon.
+    #[getset(get = "pub")]
+    #[serde(default)]
+    holes: Vec<Vec<[f64; 2]>>,
+    /// The labels of the 2d points in the same order as the points.
     #[getset(get = "pub")]
-    bound: Vec<[f64; 2]>,
+    #[serde(default)]
+    labels: Vec<String>,
+    /// The weights of the 2d points in the same order as the points.
+    #[getset(get = "pub")]
+    #[serde(default)]
+    weights: Vec<f64>,
+    /// The flags marking the 2d points that are not moved by Lloyd relaxation
+    /// in the same order as the points.
+    #[getset(get = "pub")]
+    #[serde(default)]
+    fixed: Vec<bool>,
 }
 
 impl BoundedPointSet {
-    /// Returns the bounding polygon or an error if less than 3 points have been specified.
-    pub fn bounding_polygon(&self) -> Result<Polygon, &'static str> {
-        let bound_points: Vec<(f64, f64)> = self
-            .bound()
+    /// Creates a new bounded point set without validating the input.
+    ///
+    /// # Parameters
+    ///
+    /// * `point_set` - the set of 2d points
+    /// * `bound` - the points of the bounding polygon
+    pub fn new(point_set: Vec<[f64; 2]>, bound: Vec<[f64; 2]>) -> Self {
+        Self {
+            point_set: point_set.into_iter().map(InputPoint::from).collect(),
+            bound: BoundKind::Polygon(bound),
+            bbox: None,
+            holes: Vec::new(),
+            labels: Vec::new(),
+            weights: Vec::new(),
+            fixed: Vec::new(),
+        }
+    }
+
+    /// Creates a new bounded point set from iterators over the coordinates of the
+    /// points and of the bounding polygon without validating the input.
+    /// Invalid points are kept, so that labels and weights can be specified in the
+    /// same order, but are filtered out during the computation.
+    ///
+    /// # Parameters
+    ///
+    /// * `points` - the coordinates of the 2d points
+    /// * `bound` - the coordinates of the bounding polygon
+    pub fn from_points_and_bound<P, B>(points: P, bound: B) -> Self
+    where
+        P: IntoIterator<Item = [f64; 2]>,
+        B: IntoIterator<Item = [f64; 2]>,
+    {
+        Self::new(points.into_iter().collect(), bound.into_iter().collect())
+    }
+
+    /// Creates a new bounded point set with an axis-aligned bounding rectangle
+    /// instead of a bounding polygon without validating the input.
+    ///
+    /// # Parameters
+    ///
+    /// * `point_set` - the set of 2d points
+    /// * `bbox` - the bounding rectangle as `[min_x, min_y, max_x, max_y]`
+    pub fn with_bbox(point_set: Vec<[f64; 2]>, bbox: [f64; 4]) -> Self {
+        Self {
+            bbox: Some(bbox),
+            ..Self::new(point_set, Vec::new())
+        }
+    }
+
+    /// Creates a new bounded point set with several disjoint bounding regions
+    /// without validating the input.
+    ///
+    /// # Parameters
+    ///
+    /// * `point_set` - the set of 2d points
+    /// * `bound` - the exterior rings of the bounding regions
+    pub fn with_multi_polygon(point_set: Vec<[f64; 2]>, bound: Vec<Vec<[f64; 2]>>) -> Self {
+        Self {
+            bound: BoundKind::MultiPolygon(bound),
+            ..Self::new(point_set, Vec::new())
+        }
+    }
+
+    /// Creates a new bounded point set with a circular bound without validating the input.
+    ///
+    /// # Parameters
+    ///
+    /// * `point_set` - the set of 2d points
+    /// * `circle` - the circular bound
+    pub fn with_circle(point_set: Vec<[f64; 2]>, circle: CircleBound) -> Self {
+        Self {
+            bound: BoundKind::Circle { circle },
+            ..Self::new(point_set, Vec::new())
+        }
+    }
+
+    /// Consumes the bounded point set and returns it with the specified holes
+    /// as interiors of the bounding polygon.
+    ///
+    /// # Parameters
+    ///
+    /// * `holes` - the rings of the holes in the bounding polygon
+    pub fn with_holes(self, holes: Vec<Vec<[f64; 2]>>) -> Self {
+        Self { holes, ..self }
+    }
+
+    /// Consumes the bounded point set and returns it with the specified bound, which
+    /// replaces both a previously specified bounding geometry and bounding rectangle.
+    /// The holes are kept.
+    ///
+    /// # Parameters
+    ///
+    /// * `bound` - the bounding polygon, regions or circle
+    pub fn with_bound(self, bound: BoundKind) -> Self {
+        Self {
+            bound,
+            bbox: None,
+            ..self
+        }
+    }
+
+    /// Returns `true` if a bounding geometry or a bounding rectangle has been specified.
+    pub fn has_bound(&self) -> bool {
+        self.bbox.is_some() || !self.bound.is_empty()
+    }
+
+    /// Consumes the bounded point set and returns it with the specified labels.
+    ///
+    /// # Parameters
+    ///
+    /// * `labels` - the labels of the 2d points in the same order as the points
+    pub fn with_labels(self, labels: Vec<String>) -> Self {
+        Self { labels, ..self }
+    }
+
+    /// Appends the point to the point set.
+    /// If labels, weights or fixed flags have been specified, the point is labelled with
+    /// an empty label, weighted with 1 and not fixed, so that their number still matches
+    /// the number of points.
+    ///
+    /// # Parameters
+    ///
+    /// * `point` - the point to append
+    pub fn push_point<T: Into<InputPoint>>(&mut self, point: T) {
+        self.point_set.push(point.into());
+        if !self.labels.is_empty() {
+            self.labels.push(String::new());
+        }
+        if !self.weights.is_empty() {
+            self.weights.push(1.0);
+        }
+        if !self.fixed.is_empty() {
+            self.fixed.push(false);
+        }
+    }
+
+    /// Consumes the bounded point set and returns it with the specified weights.
+    ///
+    /// # Parameters
+    ///
+    /// * `weights` - the weights of the 2d points in the same order as the points
+    pub fn with_weights(self, weights: Vec<f64>) -> Self {
+        Self { weights, ..self }
+    }
+
+    /// Consumes the bounded point set and returns it with the specified fixed flags.
+    /// Fixed points are not moved by Lloyd relaxation, but still shape the cells of
+    /// the other points.
+    ///
+    /// # Parameters
+    ///
+    /// * `fixed` - the flags marking the fixed 2d points in the same order as the points
+    pub fn with_fixed(self, fixed: Vec<bool>) -> Self {
+        Self { fixed, ..self }
+    }
+
+    /// Tries to create a new bounded point set.
+    /// Fails if less than 3 bound points or no valid point have been specified.
+    ///
+    /// # Parameters
+    ///
+    /// * `point_set` - the set of 2d points
+    /// * `bound` - the points of the bounding polygon
+    pub fn try_new(point_set: Vec<[f64; 2]>, bound: Vec<[f64; 2]>) -> Result<Self, VoronoiError> {
+        if bound.len() < 3 {
+            return Err(VoronoiError::TooFewBoundPoints);
+        }
+        let bounded_point_set = Self::new(point_set, bound);
+        if bounded_point_set.point_set().is_empty() {
+            Err(VoronoiError::EmptyPointSet)
+        } else {
+            Ok(bounded_point_set)
+        }
+    }
+
+    /// Returns the bounding polygon or an error if less than 3 points have been specified
+    /// for the exterior or any of the holes or if any of the rings intersects itself.
+    /// If a bounding rectangle has been specified, it is used as exterior instead and
+    /// an error is returned if a bounding polygon has been specified as well
+    /// or if the rectangle is empty.
+    /// An error is also returned if the bound consists of several regions.
+    /// A circular bound is approximated by a regular polygon as described for
+    /// [`CircleBound::to_ring`].
+    /// The exterior is oriented counter-clockwise and the holes clockwise
+    /// regardless of the winding of the input rings.
+    pub fn bounding_polygon(&self) -> Result<Polygon, VoronoiError> {
+        let exterior = match (self.bbox(), self.bound()) {
+            (Some(_), bound) if !bound.is_empty() => return Err(VoronoiError::ConflictingBounds),
+            (Some(bbox), _) => bbox_to_ring(bbox)?,
+            (None, BoundKind::Polygon(ring)) => ring.clone(),
+            (None, BoundKind::Circle { circle }) => circle.to_ring()?,
+            (None, BoundKind::MultiPolygon(rings)) => match rings.as_slice() {
+                [] => Vec::new(),
+                [ring] => ring.clone(),
+                _ => return Err(VoronoiError::MultiPolygonBound),
+            },
+        };
+        if exterior.len() < 3 {
+            return Err(VoronoiError::TooFewBoundPoints);
+        }
+        let exterior = validate_ring(exterior, None)?;
+        let interiors = self.validated_holes()?;
+        Ok(Polygon::new(exterior, interiors).orient(Direction::Default))
+    }
+
+    /// Returns the bounding regions with the same validation as
+    /// [`BoundedPointSet::bounding_polygon`].
+    /// The holes are subtracted from all regions they overlap.
+    pub fn bounding_multi_polygon(&self) -> Result<MultiPolygon, VoronoiError> {
+        match (self.bbox(), self.bound()) {
+            (None, BoundKind::MultiPolygon(rings)) if rings.len() > 1 => {
+                if rings.iter().any(|ring| ring.len() < 3) {
+                    return Err(VoronoiError::TooFewBoundPoints);
+                }
+                let regions = rings
+                    .iter()
+                    .map(|ring| {
+                        validate_ring(ring, None).map(|ring| Polygon::new(ring, Vec::new()))
+                    })
+                    .collect::<Result<Vec<Polygon>, VoronoiError>>()?;
+                let holes = self
+                    .validated_holes()?
+                    .into_iter()
+                    .map(|hole| Polygon::new(hole, Vec::new()))
+                    .collect();
+                Ok(MultiPolygon::new(regions)
+                    .difference(&MultiPolygon::new(holes))
+                    .orient(Direction::Default))
+            },
+            _ => self
+                .bounding_polygon()
+                .map(|polygon| MultiPolygon::new(vec![polygon])),
+        }
+    }
+
+    /// Returns the holes as closed rings or an error if less than 3 points
+    /// have been specified for any of them or if any of them intersects itself.
+    fn validated_holes(&self) -> Result<Vec<LineString>, VoronoiError> {
+        if self.holes().iter().any(|hole| hole.len() < 3) {
+            return Err(VoronoiError::TooFewHolePoints);
+        }
+        self.holes()
+            .iter()
+            .enumerate()
+            .map(|(hole, ring)| validate_ring(ring, Some(hole)))
+            .collect()
+    }
+
+    /// Returns the bounded point set with all coordinates transformed by the specified
+    /// function, which fails on the first coordinate that cannot be transformed.
+    /// Non-finite points are kept unchanged, so that the labels and weights still match.
+    /// A bounding rectangle or circle is converted to a bounding polygon first, as the
+    /// transformed rectangle is generally not axis-aligned and the transformed circle
+    /// generally not circular anymore.
+    ///
+    /// # Parameters
+    ///
+    /// * `transform` - the transformation applied to each coordinate
+    pub fn try_map_coords<F: Fn([f64; 2]) -> Result<[f64; 2], VoronoiError>>(
+        &self,
+        transform: F,
+    ) -> Result<Self, VoronoiError> {
+        let map_ring = |ring: &Vec<[f64; 2]>| -> Result<Vec<[f64; 2]>, VoronoiError> {
+            ring.iter().map(|&point| transform(point)).collect()
+        };
+        let bound = match (self.bbox(), self.bound()) {
+            (Some(_), bound) if !bound.is_empty() => return Err(VoronoiError::ConflictingBounds),
+            (Some(bbox), _) => BoundKind::Polygon(map_ring(&bbox_to_ring(bbox)?)?),
+            (None, BoundKind::Polygon(ring)) => BoundKind::Polygon(map_ring(ring)?),
+            (None, BoundKind::Circle { circle }) => {
+                BoundKind::Polygon(map_ring(&circle.to_ring()?)?)
+            },
+            (None, BoundKind::MultiPolygon(rings)) => {
+                BoundKind::MultiPolygon(rings.iter().map(map_ring).collect::<Result<_, _>>()?)
+            },
+        };
+        Ok(Self {
+            point_set: self
+                .point_set
+                .iter()
+                .map(|point| {
+                    let coordinates = point.coordinates();
+                    if coordinates.iter().all(|coordinate| coordinate.is_finite()) {
+                        Ok(point.with_coordinates(transform(coordinates)?))
+                    } else {
+                        Ok(point.clone())
+                    }
+                })
+                .collect::<Result<_, _>>()?,
+            bound,
+            bbox: None,
+            holes: self
+                .holes()
+                .iter()
+                .map(map_ring)
+                .collect::<Result<_, _>>()?,
+            ..self.clone()
+        })
+    }
+
+    /// Returns the unique, filtered 2d points that lie outside the bounding rectangle of the
+    /// bound if it is centered around the bounding rectangle of the point set.
+    /// As the bound is centered around each site, sites outside of it indicate that the
+    /// bound is smaller than the extent of the point set, so that the cells do not cover
+    /// the space between the outer sites.
+    /// The extents are compared first, so that the sites are only checked one by one
+    /// if the point set does not fit into the bound.
+    pub fn sites_outside_bound(&self) -> Result<Vec<Point2D>, VoronoiError> {
+        let bound = Bounds::from_multi_polygon(self.bounding_multi_polygon()?)
+            .ok_or(VoronoiError::InvalidPolygon)?;
+        let point_set = self.point_set();
+        let Some(extent) = Bounds::from_point_set(&point_set) else {
+            return Ok(Vec::new());
+        };
+        if extent.diff_x() <= bound.diff_x() && extent.diff_y() <= bound.diff_y() {
+            return Ok(Vec::new());
+        }
+        let (half_width, half_height) = (bound.diff_x() / 2.0, bound.diff_y() / 2.0);
+        let mut outside: Vec<Point2D> = point_set
             .into_iter()
-            .map(|point| (point[0], point[1]))
+            .filter(|point| {
+                (point.x() - extent.centre_x()).abs() > half_width
+                    || (point.y() - extent.centre_y()).abs() > half_height
+            })
             .collect();
-        if bound_points.len() < 3 {
-            Err("At least 3 points are needed to specify a bounding polygon.")
+        outside.sort();
+        Ok(outside)
+    }
+
+    /// Returns the convex hull of the unique, filtered 2d points expanded by the margin
+    /// or [`None`] if the hull has no area, e.g. because all points are collinear and
+    /// no margin has been specified.
+    /// The hull is expanded by replacing each point with a regular polygon around the
+    /// circle of the margin, so that all locations within the margin of a point lie
+    /// within the hull. Non-positive or non-finite margins are ignored.
+    ///
+    /// # Parameters
+    ///
+    /// * `margin` - the distance the hull is expanded by
+    pub fn convex_hull(&self, margin: f64) -> Option<Polygon> {
+        let offsets: Vec<[f64; 2]> = if margin.is_finite() && margin > 0.0 {
+            let segments = HULL_MARGIN_SEGMENTS as f64;
+            let radius = margin / (f64::consts::PI / segments).cos();
+            (0..HULL_MARGIN_SEGMENTS)
+                .map(|segment| {
+                    let angle = 2.0 * f64::consts::PI * segment as f64 / segments;
+                    [radius * angle.cos(), radius * angle.sin()]
+                })
+                .collect()
         } else {
-            Ok(Polygon::new(LineString::from(bound_points), Vec::new()))
+            vec![[0.0, 0.0]]
+        };
+        let points: MultiPoint = self
+            .point_set()
+            .into_iter()
+            .flat_map(|point| {
+                offsets.iter().map(move |offset| {
+                    geo::Point::new(point.x() + offset[0], point.y() + offset[1])
+                })
+            })
+            .collect();
+        let hull = points.convex_hull();
+        (hull.unsigned_area() > 0.0).then_some(hull)
+    }
+
+    /// Returns the smallest bounds containing both the unique, filtered 2d points and
+    /// the bound as specified, e.g. to set up the extent of a plot. The bound is not
+    /// centered around the points.
+    /// If the bound is invalid or has not been specified, only the points are taken
+    /// into account and vice versa. Returns [`None`] if neither the points nor the
+    /// bound have any extent.
+    pub fn combined_bounds(&self) -> Option<Bounds> {
+        let point_set_bounds = Bounds::from_point_set(self.point_set());
+        let bound_bounds = self
+            .bounding_multi_polygon()
+            .ok()
+            .and_then(Bounds::from_multi_polygon);
+        match (point_set_bounds, bound_bounds) {
+            (Some(point_set_bounds), Some(bound_bounds)) => {
+                Some(point_set_bounds.union(bound_bounds))
+            },
+            (point_set_bounds, bound_bounds) => point_set_bounds.or(bound_bounds),
         }
     }
 
+    /// The coordinates of all input points including invalid and duplicate ones
+    /// in the order of their occurrence.
+    fn coordinates(&self) -> impl Iterator<Item = [f64; 2]> + '_ {
+        self.point_set.iter().map(InputPoint::coordinates)
+    }
+
     /// The set of unique, filtered 2d points.
     pub fn point_set(&self) -> HashSet<Point2D> {
-        (&self.point_set)
-            .into_iter()
+        self.coordinates()
+            .filter_map(|point| Point2D::new(point[0], point[1]))
+            .collect()
+    }
+
+    /// The set of unique, filtered 2d points together with a report
+    /// of the duplicate points that have been removed.
+    pub fn point_set_with_report(&self) -> (HashSet<Point2D>, DeduplicationReport) {
+        let mut point_set = HashSet::new();
+        let mut dropped = Vec::new();
+        for point in self
+            .coordinates()
             .filter_map(|point| Point2D::new(point[0], point[1]))
+        {
+            if !point_set.insert(point) {
+                dropped.push([point.x(), point.y()]);
+            }
+        }
+        let report = DeduplicationReport {
+            kept: point_set.len(),
+            dropped,
+        };
+        (point_set, report)
+    }
+
+    /// The set of unique, filtered 2d points together with the points that have
+    /// been rejected, because they contain a NaN or infinite coordinate, in the order
+    /// of their occurrence.
+    /// Subnormal coordinates are valid and therefore never rejected.
+    pub fn point_set_with_rejects(&self) -> (HashSet<Point2D>, Vec<RejectedPoint>) {
+        let mut point_set = HashSet::new();
+        let mut rejects = Vec::new();
+        for (index, coordinates) in self.coordinates().enumerate() {
+            match Point2D::new(coordinates[0], coordinates[1]) {
+                Some(point) => {
+                    point_set.insert(point);
+                },
+                None => rejects.push(RejectedPoint {
+                    index,
+                    coordinates,
+                    reason: RejectReason::of(coordinates),
+                }),
+            }
+        }
+        (point_set, rejects)
+    }
+
+    /// The set of unique, filtered 2d points with near-duplicate points merged.
+    /// Points whose coordinates both differ by at most the tolerance from a point
+    /// occurring earlier are merged into that point, which keeps its coordinates.
+    /// The points are hashed on a grid with the spacing of the tolerance, so only
+    /// the points in the adjacent grid squares need to be compared.
+    /// A tolerance that is not a positive, finite number merges only exact duplicates.
+    ///
+    /// # Parameters
+    ///
+    /// * `tolerance` - the maximum coordinate difference of merged points
+    pub fn merged_point_set(&self, tolerance: f64) -> HashSet<Point2D> {
+        if !tolerance.is_finite() || tolerance <= 0.0 {
+            return self.point_set();
+        }
+        let grid_square = |point: Point2D| {
+            ((point.x() / tolerance).floor() as i64, (point.y() / tolerance).floor() as i64)
+        };
+        let mut grid: HashMap<(i64, i64), Vec<Point2D>> = HashMap::new();
+        let mut point_set = HashSet::new();
+        for point in self.ordered_point_set() {
+            let (x, y) = grid_square(point);
+            let is_near_duplicate = (x.saturating_sub(1)..=x.saturating_add(1))
+                .flat_map(|x| (y.saturating_sub(1)..=y.saturating_add(1)).map(move |y| (x, y)))
+                .filter_map(|square| grid.get(&square))
+                .flatten()
+                .any(|merged_point| {
+                    (merged_point.x() - point.x()).abs() <= tolerance
+                        && (merged_point.y() - point.y()).abs() <= tolerance
+                });
+            if !is_near_duplicate {
+                grid.entry((x, y)).or_default().push(point);
+                point_set.insert(point);
+            }
+        }
+        point_set
+    }
+
+    /// The unique, filtered 2d points sorted in ascending order by their
+    /// x- and then y-coordinate.
+    pub fn sorted_point_set(&self) -> Vec<Point2D> {
+        let mut point_set: Vec<Point2D> = self.point_set().into_iter().collect();
+        point_set.sort();
+        point_set
+    }
+
+    /// The unique, filtered 2d points in the order of their first occurrence.
+    pub fn ordered_point_set(&self) -> Vec<Point2D> {
+        let mut unique_points = HashSet::new();
+        self.coordinates()
+            .filter_map(|point| Point2D::new(point[0], point[1]))
+            .filter(|&point| unique_points.insert(point))
+            .collect()
+    }
+
+    /// Validates the bounded point set without computing the Voronoi diagramm and
+    /// returns a report of the duplicate points on success.
+    /// Fails if the bound is invalid as described for [`BoundedPointSet::bounding_multi_polygon`],
+    /// if the point set does not contain any valid point or if the number of
+    /// labels, weights or fixed flags does not match the number of points.
+    pub fn validate(&self) -> Result<DeduplicationReport, VoronoiError> {
+        self.bounding_multi_polygon()?;
+        let (_, report) = self.point_set_with_report();
+        if report.kept() == 0 {
+            return Err(VoronoiError::EmptyPointSet);
+        }
+        self.point_labels()?;
+        self.point_weights()?;
+        self.fixed_points()?;
+        Ok(report)
+    }
+
+    /// Returns the labels of the unique, filtered 2d points.
+    /// If multiple input points with different labels are the same point,
+    /// the label of the first occurrence is used.
+    /// Fails if labels have been specified, but their number does not match
+    /// the number of input points.
+    pub fn point_labels(&self) -> Result<HashMap<Point2D, String>, VoronoiError> {
+        if self.labels.is_empty() {
+            return Ok(HashMap::new());
+        }
+        if self.labels.len() != self.point_set.len() {
+            return Err(VoronoiError::LabelCountMismatch {
+                labels: self.labels.len(),
+                points: self.point_set.len(),
+            });
+        }
+        let mut point_labels = HashMap::new();
+        for (point, label) in self.coordinates().zip(self.labels.iter()) {
+            if let Some(point) = Point2D::new(point[0], point[1]) {
+                point_labels.entry(point).or_insert_with(|| label.clone());
+            }
+        }
+        Ok(point_labels)
+    }
+
+    /// Returns the weights of the unique, filtered 2d points.
+    /// If no weights have been specified, all points are weighted with 1.
+    /// If multiple input points with different weights are the same point,
+    /// the weight of the first occurrence is used.
+    /// Fails if weights have been specified, but their number does not match
+    /// the number of input points or if any weight is not a positive, finite number.
+    pub fn point_weights(&self) -> Result<HashMap<Point2D, f64>, VoronoiError> {
+        if self.weights.is_empty() {
+            return Ok(self
+                .point_set()
+                .into_iter()
+                .map(|point| (point, 1.0))
+                .collect());
+        }
+        if self.weights.len() != self.point_set.len() {
+            return Err(VoronoiError::WeightCountMismatch {
+                weights: self.weights.len(),
+                points: self.point_set.len(),
+            });
+        }
+        let mut point_weights = HashMap::new();
+        for (point, &weight) in self.coordinates().zip(self.weights.iter()) {
+            if !weight.is_finite() || weight <= 0.0 {
+                return Err(VoronoiError::InvalidWeight { weight });
+            }
+            if let Some(point) = Point2D::new(point[0], point[1]) {
+                point_weights.entry(point).or_insert(weight);
+            }
+        }
+        Ok(point_weights)
+    }
+
+    /// Returns the unique, filtered 2d points that have been marked as fixed.
+    /// If multiple input points with different flags are the same point,
+    /// the flag of the first occurrence is used.
+    /// Fails if fixed flags have been specified, but their number does not match
+    /// the number of input points.
+    pub fn fixed_points(&self) -> Result<HashSet<Point2D>, VoronoiError> {
+        if self.fixed.is_empty() {
+            return Ok(HashSet::new());
+        }
+        if self.fixed.len() != self.point_set.len() {
+            return Err(VoronoiError::FixedCountMismatch {
+                fixed: self.fixed.len(),
+                points: self.point_set.len(),
+            });
+        }
+        let mut point_fixed = HashMap::new();
+        for (point, &fixed) in self.coordinates().zip(self.fixed.iter()) {
+            if let Some(point) = Point2D::new(point[0], point[1]) {
+                point_fixed.entry(point).or_insert(fixed);
+            }
+        }
+        Ok(point_fixed
+            .into_iter()
+            .filter_map(|(point, fixed)| fixed.then_some(point))
+            .collect())
+    }
+
+    /// Returns the properties of the unique, filtered 2d points that have been
+    /// specified as objects with properties.
+    /// If multiple input points are the same point, the properties of the first
+    /// occurrence are used, even if it has not been specified with properties.
+    pub fn point_properties(&self) -> HashMap<Point2D, PointProperties> {
+        let mut point_properties = HashMap::new();
+        for point in &self.point_set {
+            let coordinates = point.coordinates();
+            if let Some(site) = Point2D::new(coordinates[0], coordinates[1]) {
+                point_properties
+                    .entry(site)
+                    .or_insert_with(|| point.properties().cloned());
+            }
+        }
+        point_properties
+            .into_iter()
+            .filter_map(|(site, properties)| properties.map(|properties| (site, properties)))
             .collect()
     }
 
@@ -49,8 +688,353 @@ impl BoundedPointSet {
     }
 }
 
+/// Several point sets, which share one bound and are tessellated separately,
+/// e.g. `{"bound": [...], "groups": [[...], [...]]}`.
+#[derive(Getters, Clone, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MultiGroupInput {
+    /// The sets of 2d points of the groups.
+    #[getset(get = "pub")]
+    groups: Vec<Vec<InputPoint>>,
+    /// The bounding polygon or the bounding regions shared by all groups.
+    #[getset(get = "pub")]
+    #[serde(default)]
+    bound: BoundKind,
+    /// The axis-aligned bounding rectangle as `[min_x, min_y, max_x, max_y]`,
+    /// which can be specified instead of the bounding polygon.
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bbox: Option<[f64; 4]>,
+    /// The holes / interiors of the bounding polygon.
+    #[getset(get = "pub")]
+    #[serde(default)]
+    holes: Vec<Vec<[f64; 2]>>,
+}
+
+impl MultiGroupInput {
+    /// Creates a new multi group input without validating the input.
+    ///
+    /// # Parameters
+    ///
+    /// * `groups` - the sets of 2d points of the groups
+    /// * `bound` - the points of the bounding polygon
+    pub fn new(groups: Vec<Vec<[f64; 2]>>, bound: Vec<[f64; 2]>) -> Self {
+        Self {
+            groups: groups
+                .into_iter()
+                .map(|group| group.into_iter().map(InputPoint::from).collect())
+                .collect(),
+            bound: BoundKind::Polygon(bound),
+            bbox: None,
+            holes: Vec::new(),
+        }
+    }
+
+    /// Consumes the input and returns it with the specified bound, which
+    /// replaces both a previously specified bounding geometry and bounding rectangle.
+    /// The holes are kept.
+    ///
+    /// # Parameters
+    ///
+    /// * `bound` - the bounding polygon, regions or circle
+    pub fn with_bound(self, bound: BoundKind) -> Self {
+        Self {
+            bound,
+            bbox: None,
+            ..self
+        }
+    }
+
+    /// Returns `true` if a bounding geometry or a bounding rectangle has been specified.
+    pub fn has_bound(&self) -> bool {
+        self.bbox.is_some() || !self.bound.is_empty()
+    }
+
+    /// Returns one bounded point set per group, which shares the bound of the input.
+    pub fn point_sets(&self) -> Vec<BoundedPointSet> {
+        self.groups
+            .iter()
+            .map(|group| BoundedPointSet {
+                point_set: group.clone(),
+                bound: self.bound.clone(),
+                bbox: self.bbox,
+                holes: self.holes.clone(),
+                labels: Vec::new(),
+                weights: Vec::new(),
+                fixed: Vec::new(),
+            })
+            .collect()
+    }
+}
+
+/// A point of the input point set, either specified by its coordinates only or
+/// as object carrying arbitrary properties, e.g. `{"coord": [x, y], "properties": {...}}`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum InputPoint {
+    /// The coordinates `[x, y]` of the point.
+    Coordinates([f64; 2]),
+    /// The coordinates of the point together with its properties.
+    WithProperties {
+        /// The coordinates `[x, y]` of the point.
+        coord: [f64; 2],
+        /// The properties, which are passed on to the cell of the point.
+        #[serde(default)]
+        properties: PointProperties,
+    },
+}
+
+impl InputPoint {
+    /// Returns the coordinates of the point.
+    pub fn coordinates(&self) -> [f64; 2] {
+        match self {
+            InputPoint::Coordinates(coordinates) => *coordinates,
+            InputPoint::WithProperties { coord, .. } => *coord,
+        }
+    }
+
+    /// Returns the properties of the point if it has been specified with properties.
+    pub fn properties(&self) -> Option<&PointProperties> {
+        match self {
+            InputPoint::Coordinates(_) => None,
+            InputPoint::WithProperties { properties, .. } => Some(properties),
+        }
+    }
+
+    /// Returns the point with the specified coordinates and the properties of this point.
+    ///
+    /// # Parameters
+    ///
+    /// * `coordinates` - the new coordinates of the point
+    fn with_coordinates(&self, coordinates: [f64; 2]) -> Self {
+        match self {
+            InputPoint::Coordinates(_) => InputPoint::Coordinates(coordinates),
+            InputPoint::WithProperties { properties, .. } => InputPoint::WithProperties {
+                coord: coordinates,
+                properties: properties.clone(),
+            },
+        }
+    }
+}
+
+impl From<[f64; 2]> for InputPoint {
+    fn from(coordinates: [f64; 2]) -> Self {
+        InputPoint::Coordinates(coordinates)
+    }
+}
+
+/// The bound of a point set, either a single polygon, a circle or several disjoint regions.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum BoundKind {
+    /// The exterior ring of a single bounding polygon.
+    Polygon(Vec<[f64; 2]>),
+    /// The exterior rings of several disjoint bounding regions.
+    MultiPolygon(Vec<Vec<[f64; 2]>>),
+    /// A circle, which is approximated by a regular polygon.
+    Circle {
+        /// The circular bound.
+        circle: CircleBound,
+    },
+}
+
+impl BoundKind {
+    /// Returns `true` if no bound has been specified.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            BoundKind::Polygon(ring) => ring.is_empty(),
+            BoundKind::MultiPolygon(rings) => rings.is_empty(),
+            BoundKind::Circle { .. } => false,
+        }
+    }
+}
+
+impl Default for BoundKind {
+    fn default() -> Self {
+        BoundKind::Polygon(Vec::new())
+    }
+}
+
+/// A circular bound, which is approximated by a regular polygon with the specified number
+/// of segments, so that more segments trade a larger number of vertices for precision.
+#[derive(Clone, Copy, CopyGetters, Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CircleBound {
+    /// The center of the circle.
+    #[getset(get_copy = "pub")]
+    center: [f64; 2],
+    /// The radius of the circle.
+    #[getset(get_copy = "pub")]
+    radius: f64,
+    /// The number of segments of the polygon approximating the circle.
+    #[getset(get_copy = "pub")]
+    #[serde(default = "default_circle_segments")]
+    segments: usize,
+}
+
+impl CircleBound {
+    /// Creates a new circular bound approximated with [`DEFAULT_CIRCLE_SEGMENTS`] segments
+    /// without validating the input.
+    ///
+    /// # Parameters
+    ///
+    /// * `center` - the center of the circle
+    /// * `radius` - the radius of the circle
+    pub fn new(center: [f64; 2], radius: f64) -> Self {
+        Self {
+            center,
+            radius,
+            segments: DEFAULT_CIRCLE_SEGMENTS,
+        }
+    }
+
+    /// Consumes the circular bound and returns it with the specified number of segments.
+    ///
+    /// # Parameters
+    ///
+    /// * `segments` - the number of segments of the polygon approximating the circle
+    pub fn with_segments(self, segments: usize) -> Self {
+        Self { segments, ..self }
+    }
+
+    /// Returns the closed, counter-clockwise ring of the regular polygon approximating
+    /// the circle. Its vertices lie on the circle starting at the angle 0, so the polygon
+    /// is inscribed and its area of `segments / 2 * radius^2 * sin(2 * pi / segments)`
+    /// underestimates the area of the circle, e.g. by about 0.16 % for the default of
+    /// 64 segments.
+    /// Fails if the center is not finite, the radius is not a positive, finite number
+    /// or less than 3 segments have been specified.
+    pub fn to_ring(&self) -> Result<Vec<[f64; 2]>, VoronoiError> {
+        if self.center.iter().any(|coordinate| !coordinate.is_finite())
+            || !self.radius.is_finite()
+            || self.radius <= 0.0
+            || self.segments < 3
+        {
+            return Err(VoronoiError::InvalidCircle);
+        }
+        let [center_x, center_y] = self.center;
+        Ok((0..=self.segments)
+            .map(|segment| {
+                // Repeats the exact first vertex to close the ring.
+                let angle =
+                    (segment % self.segments) as f64 * 2.0 * f64::consts::PI / self.segments as f64;
+                [
+                    center_x + self.radius * angle.cos(),
+                    center_y + self.radius * angle.sin(),
+                ]
+            })
+            .collect())
+    }
+}
+
+/// Returns the default number of segments of a circular bound.
+fn default_circle_segments() -> usize {
+    DEFAULT_CIRCLE_SEGMENTS
+}
+
+/// Helper function to convert a ring of two dimensional arrays to a [`LineString`].
+fn ring_to_line_string<T: Borrow<Vec<[f64; 2]>>>(ring: T) -> LineString {
+    LineString::from(
+        ring.borrow()
+            .iter()
+            .map(|point| (point[0], point[1]))
+            .collect::<Vec<(f64, f64)>>(),
+    )
+}
+
+/// Converts a ring to a closed [`LineString`] without consecutive duplicate points,
+/// i.e. without segments of zero length, which can confuse boolean operations.
+/// Returns an error if the ring intersects itself, where the reported segments refer
+/// to the input ring, or if less than 3 distinct points remain.
+///
+/// # Parameters
+///
+/// * `ring` - the ring to validate
+/// * `hole` - the index of the hole or [`None`] for an exterior ring
+fn validate_ring<T: Borrow<Vec<[f64; 2]>>>(
+    ring: T,
+    hole: Option<usize>,
+) -> Result<LineString, VoronoiError> {
+    let mut line_string = ring_to_line_string(ring);
+    line_string.close();
+    if let Some(segments) = find_self_intersection(&line_string) {
+        return Err(VoronoiError::SelfIntersection { hole, segments });
+    }
+    // The first and the last point of the closed ring are never consecutive
+    // duplicates unless all points are the same.
+    line_string.0.dedup();
+    if line_string.0.len() < 4 {
+        return Err(match hole {
+            Some(_) => VoronoiError::TooFewHolePoints,
+            None => VoronoiError::TooFewBoundPoints,
+        });
+    }
+    Ok(line_string)
+}
+
+/// Converts a bounding rectangle to a closed ring.
+/// Fails if any coordinate is not finite or the rectangle is empty.
+///
+/// # Parameters
+///
+/// * `bbox` - the bounding rectangle as `[min_x, min_y, max_x, max_y]`
+fn bbox_to_ring(bbox: &[f64; 4]) -> Result<Vec<[f64; 2]>, VoronoiError> {
+    let [min_x, min_y, max_x, max_y] = *bbox;
+    if bbox.iter().any(|coordinate| !coordinate.is_finite()) || min_x >= max_x || min_y >= max_y {
+        return Err(VoronoiError::InvalidBoundingBox);
+    }
+    Ok(vec![
+        [min_x, min_y],
+        [max_x, min_y],
+        [max_x, max_y],
+        [min_x, max_y],
+        [min_x, min_y],
+    ])
+}
+
+/// Returns the indices of the first pair of segments of the closed ring that
+/// intersect each other, where segment `i` connects the points `i` and `i + 1`.
+/// Segments of zero length are ignored, so that repeated points are not
+/// reported as intersection. Neighbouring segments are only reported if they
+/// overlap collinearly, i.e. if the ring folds back onto itself.
+///
+/// # Parameters
+///
+/// * `ring` - the closed ring to check
+pub(crate) fn find_self_intersection<T: Borrow<LineString>>(ring: T) -> Option<[usize; 2]> {
+    let segments: Vec<(usize, geo::Line)> = ring
+        .borrow()
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.euclidean_length() > 0.0)
+        .collect();
+    let number_of_segments = segments.len();
+    for i in 0..number_of_segments {
+        for j in (i + 1)..number_of_segments {
+            let (index_a, segment_a) = segments[i];
+            let (index_b, segment_b) = segments[j];
+            // Neighbouring segments always share an end point, including the first
+            // and the last segment of the closed ring.
+            let intersect = if j == i + 1 || (i == 0 && j == number_of_segments - 1) {
+                matches!(
+                    line_intersection(segment_a, segment_b),
+                    Some(LineIntersection::Collinear { .. })
+                )
+            } else {
+                segment_a.intersects(&segment_b)
+            };
+            if intersect {
+                return Some([index_a, index_b]);
+            }
+        }
+    }
+    None
+}
+
 /// A 2-dimensional point.
-#[derive(Clone, Copy, CopyGetters, Debug, PartialEq, PartialOrd)]
+#[derive(Clone, Copy, CopyGetters, Debug, PartialEq)]
 pub struct Point2D {
     /// The x-coordinate.
     #[getset(get_copy = "pub")]
@@ -63,31 +1047,84 @@ pub struct Point2D {
 impl Point2D {
     /// Tries to create a new 2 dimensional point from
     /// the specified coordinates.
-    /// Fails if one of the specified coordinates is not normal.
+    /// Fails if one of the specified coordinates is NaN or infinite.
+    /// Subnormal coordinates are kept and a negative zero is converted to zero.
     ///
     /// # Parameters
     ///
     /// * `x` - the x-coordinate
     /// * `y` - the y-coordinate
     pub fn new(x: f64, y: f64) -> Option<Self> {
-        if !x.is_finite() || x.is_subnormal() || !y.is_finite() || y.is_subnormal() {
+        if !x.is_finite() || !y.is_finite() {
             None
         } else {
-            Some(Point2D { x, y })
+            // Adding zero turns a negative zero into zero, so that equal
+            // coordinates always share the same bit pattern.
+            Some(Point2D {
+                x: x + 0.0,
+                y: y + 0.0,
+            })
+        }
+    }
+
+    /// Creates the valid points from the coordinates in the same order,
+    /// skipping coordinates that are NaN or infinite as described for [`Point2D::new`].
+    ///
+    /// # Parameters
+    ///
+    /// * `coordinates` - the coordinates of the points
+    pub fn from_coordinates<I: IntoIterator<Item = [f64; 2]>>(coordinates: I) -> Vec<Self> {
+        coordinates
+            .into_iter()
+            .filter_map(|[x, y]| Point2D::new(x, y))
+            .collect()
+    }
+
+    /// Tries to create a new 2 dimensional point from the specified
+    /// integer coordinates, which are converted to floating point exactly.
+    /// Fails if one of the coordinates lies beyond ±2^53, as not every
+    /// integer of that magnitude can be represented exactly.
+    ///
+    /// # Parameters
+    ///
+    /// * `x` - the x-coordinate
+    /// * `y` - the y-coordinate
+    pub fn from_integer(x: i64, y: i64) -> Result<Self, VoronoiError> {
+        for value in [x, y] {
+            if value.unsigned_abs() > MAX_EXACT_INTEGER {
+                return Err(VoronoiError::IntegerOutOfRange { value });
+            }
         }
+        Ok(Point2D {
+            x: x as f64,
+            y: y as f64,
+        })
     }
 }
 
-// Eq and Ord can be implemented as all non-normal
-// coordinates have been filtered out during creation.
+// Eq and Ord can be implemented as NaN and infinite coordinates
+// have been filtered out during creation, so that all remaining
+// coordinates including subnormal ones are totally ordered.
 impl Eq for Point2D {}
 
 impl Ord for Point2D {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
+        self.x
+            .partial_cmp(&other.x)
+            .unwrap()
+            .then_with(|| self.y.partial_cmp(&other.y).unwrap())
+    }
+}
+
+impl PartialOrd for Point2D {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
+// Hashing the bits is consistent with Eq as a negative zero has been
+// converted to zero during creation, which is the only case of equal
+// finite coordinates with different bit patterns.
 impl std::hash::Hash for Point2D {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.x.to_bits().hash(state);
@@ -104,6 +1141,56 @@ impl From<Point2D> for voronoice::Point {
     }
 }
 
+/// A report of the duplicate points removed from a point set.
+#[derive(Clone, CopyGetters, Debug, Getters, PartialEq)]
+pub struct DeduplicationReport {
+    /// The number of unique points that have been kept.
+    #[getset(get_copy = "pub")]
+    kept: usize,
+    /// The coordinates of the duplicate points that have been removed
+    /// in the order of their occurrence.
+    #[getset(get = "pub")]
+    dropped: Vec<[f64; 2]>,
+}
+
+/// The reason a point has been rejected from the point set.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RejectReason {
+    /// At least one coordinate is NaN.
+    NaN,
+    /// At least one coordinate is infinite and none is NaN.
+    Infinite,
+}
+
+impl RejectReason {
+    /// Returns the reason the invalid point is rejected.
+    ///
+    /// # Parameters
+    ///
+    /// * `coordinates` - the coordinates of the invalid point
+    fn of(coordinates: [f64; 2]) -> Self {
+        if coordinates.iter().any(|coordinate| coordinate.is_nan()) {
+            RejectReason::NaN
+        } else {
+            RejectReason::Infinite
+        }
+    }
+}
+
+/// A point that has been rejected from the point set.
+#[derive(Clone, Copy, CopyGetters, Debug, PartialEq)]
+pub struct RejectedPoint {
+    /// The index of the point in the input point set.
+    #[getset(get_copy = "pub")]
+    index: usize,
+    /// The raw coordinates of the point.
+    #[getset(get_copy = "pub")]
+    coordinates: [f64; 2],
+    /// The reason the point has been rejected.
+    #[getset(get_copy = "pub")]
+    reason: RejectReason,
+}
+
 /// The bounds / bounding rectangle of a polygon or point set.
 #[derive(Clone, Copy, CopyGetters, Debug, PartialEq, PartialOrd)]
 pub struct Bounds {
@@ -140,6 +1227,24 @@ impl Bounds {
         })
     }
 
+    /// Returns the bounds of a [`MultiPolygon`] if applicable.
+    ///
+    /// # Parameters
+    ///
+    /// * `multi_polygon` - the multi-polygon to get the bounds for
+    pub fn from_multi_polygon<T: Borrow<MultiPolygon>>(multi_polygon: T) -> Option<Self> {
+        multi_polygon.borrow().bounding_rect().map(|bound| {
+            let (min_x, min_y) = bound.min().x_y();
+            let (max_x, max_y) = bound.max().x_y();
+            Self {
+                min_x,
+                max_x,
+                min_y,
+                max_y,
+            }
+        })
+    }
+
     /// Returns the bounds of a [`Point2D`] set if applicable.
     ///
     /// # Parameters
@@ -178,6 +1283,36 @@ impl Bounds {
         }
     }
 
+    /// Returns the smallest bounds containing both bounds.
+    ///
+    /// # Parameters
+    ///
+    /// * `other` - the bounds to combine with
+    pub fn union<T: Borrow<Bounds>>(&self, other: T) -> Self {
+        let other: &Bounds = other.borrow();
+        Self {
+            min_x: self.min_x.min(other.min_x),
+            max_x: self.max_x.max(other.max_x),
+            min_y: self.min_y.min(other.min_y),
+            max_y: self.max_y.max(other.max_y),
+        }
+    }
+
+    /// Returns the bounds enlarged by the specified margins on each side.
+    ///
+    /// # Parameters
+    ///
+    /// * `margin_x` - the margin added to the left and right
+    /// * `margin_y` - the margin added to the bottom and top
+    pub fn expand(&self, margin_x: f64, margin_y: f64) -> Self {
+        Self {
+            min_x: self.min_x - margin_x,
+            max_x: self.max_x + margin_x,
+            min_y: self.min_y - margin_y,
+            max_y: self.max_y + margin_y,
+        }
+    }
+
     /// Returns the width of the bounding rectangle.
     pub fn diff_x(&self) -> f64 {
         self.max_x - self.min_x
@@ -201,11 +1336,187 @@ impl Bounds {
 
 #[cfg(test)]
 mod tests {
-    use approx::assert_ulps_eq;
-    use geo::CoordsIter;
+    use approx::{assert_abs_diff_eq, assert_ulps_eq};
+    use geo::{Contains, CoordsIter, Winding};
 
     use super::*;
 
+    #[test]
+    fn test_boundedpointset_deserialize_points() {
+        let json = r#"{"points": [[0.0, 1.0], [1.0, 1.0]], "bound": [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]}"#;
+        let bps: BoundedPointSet = serde_json::from_str(json).unwrap();
+        assert_eq!(bps.coordinates().collect::<Vec<_>>(), vec![[0.0, 1.0], [1.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_multigroupinput_deserialize() {
+        let json = r#"{
+            "bound": [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+            "holes": [[[0.4, 0.4], [0.6, 0.4], [0.6, 0.6], [0.4, 0.6]]],
+            "groups": [[[0.2, 0.2], [0.8, 0.8]], [[0.1, 0.9], {"coord": [0.9, 0.1]}, [0.5, 0.1]]]
+        }"#;
+        let input: MultiGroupInput = serde_json::from_str(json).unwrap();
+        let point_sets = input.point_sets();
+        assert_eq!(point_sets.len(), 2);
+        assert_eq!(point_sets[0].coordinates().collect::<Vec<_>>(), vec![[0.2, 0.2], [0.8, 0.8]]);
+        assert_eq!(
+            point_sets[1].coordinates().collect::<Vec<_>>(),
+            vec![[0.1, 0.9], [0.9, 0.1], [0.5, 0.1]]
+        );
+        for point_set in &point_sets {
+            assert_eq!(point_set.bound(), input.bound());
+            assert_eq!(point_set.holes(), input.holes());
+        }
+    }
+
+    #[test]
+    fn test_boundedpointset_deserialize_point_set() {
+        let json = r#"{"point_set": [[0.0, 1.0], [1.0, 1.0]], "bound": [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]}"#;
+        let bps: BoundedPointSet = serde_json::from_str(json).unwrap();
+        assert_eq!(bps.coordinates().collect::<Vec<_>>(), vec![[0.0, 1.0], [1.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_boundedpointset_deserialize_points_with_properties() {
+        let json = r#"{"points": [[0.0, 1.0], {"coord": [1.0, 1.0], "properties": {"name": "a", "value": 2.5}}, {"coord": [2, 1]}]}"#;
+        let bps: BoundedPointSet = serde_json::from_str(json).unwrap();
+        assert_eq!(bps.coordinates().collect::<Vec<_>>(), vec![[0.0, 1.0], [1.0, 1.0], [2.0, 1.0]]);
+        assert_eq!(bps.point_set[0].properties(), None);
+        let properties = bps.point_set[1].properties().unwrap();
+        assert_eq!(properties["name"], "a");
+        assert_eq!(properties["value"], 2.5);
+        assert!(bps.point_set[2].properties().unwrap().is_empty());
+        let serialized = serde_json::to_value(&bps).unwrap();
+        assert_eq!(serialized["points"][0], serde_json::json!([0.0, 1.0]));
+        assert_eq!(serialized["points"][1]["properties"]["name"], "a");
+    }
+
+    #[test]
+    fn test_boundedpointset_deserialize_points_invalid() {
+        let json = r#"{"points": [{"properties": {"name": "a"}}]}"#;
+        assert!(serde_json::from_str::<BoundedPointSet>(json).is_err());
+    }
+
+    #[test]
+    fn test_boundedpointset_merged_point_set() {
+        let point_set = vec![
+            [1.0, 1.0],
+            [1.0 + 1e-12, 1.0],
+            // The point lies in another grid square, but still within the tolerance.
+            [1.0, 1.0 - 1e-12],
+            [1.0, 1.0 + 1e-6],
+            [5.0, 5.0],
+        ];
+        let bps = BoundedPointSet::new(point_set, vec![]);
+        assert_eq!(bps.point_set().len(), 5);
+        let merged_point_set = bps.merged_point_set(1e-9);
+        assert_eq!(merged_point_set.len(), 3);
+        // The first occurrence keeps its coordinates.
+        assert!(merged_point_set.contains(&Point2D::new(1.0, 1.0).unwrap()));
+        assert!(merged_point_set.contains(&Point2D::new(1.0, 1.0 + 1e-6).unwrap()));
+        assert!(merged_point_set.contains(&Point2D::new(5.0, 5.0).unwrap()));
+        assert_eq!(bps.merged_point_set(1e-3).len(), 2);
+        assert_eq!(bps.merged_point_set(0.0).len(), 5);
+        assert_eq!(bps.merged_point_set(f64::NAN).len(), 5);
+    }
+
+    #[test]
+    fn test_boundedpointset_point_properties() {
+        let json = r#"{"points": [
+            {"coord": [0.0, 0.0], "properties": {"name": "first"}},
+            {"coord": [0.0, 0.0], "properties": {"name": "second"}},
+            [1.0, 1.0],
+            {"coord": [1.0, 1.0], "properties": {"name": "ignored"}},
+            {"coord": [2.0, 2.0], "properties": {"name": "last"}}
+        ]}"#;
+        let bps: BoundedPointSet = serde_json::from_str(json).unwrap();
+        let point_properties = bps.point_properties();
+        // The first occurrence of a duplicate point determines the properties,
+        // even if it has been specified without properties.
+        assert_eq!(point_properties.len(), 2);
+        assert_eq!(point_properties[&Point2D::new(0.0, 0.0).unwrap()]["name"], "first");
+        assert_eq!(point_properties[&Point2D::new(2.0, 2.0).unwrap()]["name"], "last");
+    }
+
+    #[test]
+    fn test_boundedpointset_serialize_points() {
+        let bps = BoundedPointSet::new(vec![[0.0, 1.0]], vec![]);
+        let serialized = serde_json::to_value(&bps).unwrap();
+        assert!(serialized.get("points").is_some());
+        assert!(serialized.get("point_set").is_none());
+    }
+
+    #[test]
+    fn test_boundedpointset_new() {
+        let point_set = vec![[0.0, 0.0], [1.0, 1.0]];
+        let bound = vec![[-1.0, -1.0], [2.0, -1.0], [2.0, 2.0], [-1.0, -1.0]];
+        let bps = BoundedPointSet::new(point_set.clone(), bound.clone());
+        assert_eq!(bps.coordinates().collect::<Vec<_>>(), point_set);
+        assert_eq!(bps.bound(), &BoundKind::Polygon(bound));
+    }
+
+    #[test]
+    fn test_boundedpointset_from_points_and_bound() {
+        let coordinates = [(0.0, 0.0), (f64::NAN, 1.0), (1.0, 1.0)];
+        let bps = BoundedPointSet::from_points_and_bound(
+            coordinates.iter().map(|&(x, y)| [x, y]),
+            [[-1.0, -1.0], [2.0, -1.0], [2.0, 2.0]],
+        );
+        assert_eq!(bps.point_set.len(), 3);
+        assert_eq!(
+            bps.sorted_point_set(),
+            vec![
+                Point2D::new(0.0, 0.0).unwrap(),
+                Point2D::new(1.0, 1.0).unwrap()
+            ]
+        );
+        assert_eq!(bps.bound(), &BoundKind::Polygon(vec![[-1.0, -1.0], [2.0, -1.0], [2.0, 2.0]]));
+    }
+
+    #[test]
+    fn test_boundedpointset_with_bound() {
+        let bound = vec![[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0]];
+        let bps = BoundedPointSet::with_bbox(vec![[0.0, 0.0]], [-2.0, -2.0, 2.0, 2.0])
+            .with_holes(vec![vec![[0.0, 0.0], [0.1, 0.0], [0.1, 0.1]]]);
+        assert!(bps.has_bound());
+        let bps = bps.with_bound(BoundKind::Polygon(bound.clone()));
+        assert_eq!(bps.bound(), &BoundKind::Polygon(bound));
+        assert_eq!(bps.bbox(), &None);
+        assert_eq!(bps.holes().len(), 1);
+        assert!(bps.bounding_polygon().is_ok());
+        let bps: BoundedPointSet = serde_json::from_str(r#"{"points": [[0.0, 0.0]]}"#).unwrap();
+        assert!(!bps.has_bound());
+    }
+
+    #[test]
+    fn test_boundedpointset_try_new_valid() {
+        let point_set = vec![[0.0, 0.0], [1.0, 1.0]];
+        let bound = vec![[-1.0, -1.0], [2.0, -1.0], [2.0, 2.0], [-1.0, -1.0]];
+        let bps = BoundedPointSet::try_new(point_set.clone(), bound.clone()).unwrap();
+        assert_eq!(bps.coordinates().collect::<Vec<_>>(), point_set);
+        assert_eq!(bps.bound(), &BoundKind::Polygon(bound));
+    }
+
+    #[test]
+    fn test_boundedpointset_try_new_too_few_bound_points() {
+        let point_set = vec![[0.0, 0.0], [1.0, 1.0]];
+        let bound = vec![[-1.0, -1.0], [2.0, -1.0]];
+        assert_eq!(
+            BoundedPointSet::try_new(point_set, bound).unwrap_err(),
+            VoronoiError::TooFewBoundPoints
+        );
+    }
+
+    #[test]
+    fn test_boundedpointset_try_new_no_valid_points() {
+        let point_set = vec![[f64::NAN, 0.0], [1.0, f64::INFINITY]];
+        let bound = vec![[-1.0, -1.0], [2.0, -1.0], [2.0, 2.0], [-1.0, -1.0]];
+        assert_eq!(
+            BoundedPointSet::try_new(point_set, bound).unwrap_err(),
+            VoronoiError::EmptyPointSet
+        );
+    }
+
     #[test]
     fn test_boundedpointset_bounding_polygon_valid() {
         let poly_points = vec![
@@ -218,29 +1529,212 @@ mod tests {
         ];
         let bps = BoundedPointSet {
             point_set: vec![],
-            bound: poly_points.clone(),
+            bound: BoundKind::Polygon(poly_points.clone()),
+            holes: vec![],
+            labels: vec![],
+            weights: vec![],
+            fixed: vec![],
+            bbox: None,
         };
         let bounding_poly = bps.bounding_polygon().unwrap();
 
         assert!(bounding_poly.interiors().is_empty());
         assert_eq!(bounding_poly.exterior().coords_iter().count(), poly_points.len());
-        for (i, c) in bounding_poly.exterior_coords_iter().enumerate() {
-            assert_eq!(c.x, poly_points[i][0]);
-            assert_eq!(c.y, poly_points[i][1]);
+        // The clockwise exterior is reversed.
+        for (c, point) in bounding_poly
+            .exterior_coords_iter()
+            .zip(poly_points.iter().rev())
+        {
+            assert_eq!(c.x, point[0]);
+            assert_eq!(c.y, point[1]);
         }
     }
 
+    #[test]
+    fn test_boundedpointset_bounding_polygon_orientation() {
+        let counter_clockwise = vec![[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0], [0.0, 0.0]];
+        let clockwise: Vec<[f64; 2]> = counter_clockwise.iter().rev().copied().collect();
+        let hole = vec![[0.5, 0.5], [1.0, 0.5], [1.0, 1.0], [0.5, 0.5]];
+        let expected = BoundedPointSet::new(vec![], counter_clockwise)
+            .with_holes(vec![hole.clone()])
+            .bounding_polygon()
+            .unwrap();
+        let reversed = BoundedPointSet::new(vec![], clockwise)
+            .with_holes(vec![hole])
+            .bounding_polygon()
+            .unwrap();
+        assert_eq!(expected, reversed);
+        assert!(expected.exterior().is_ccw());
+        assert!(expected.interiors()[0].is_cw());
+    }
+
     #[test]
     fn test_boundedpointset_bounding_polygon_invalid() {
         // To few points for a proper polygon.
         let poly_points = vec![[0.0, 0.0], [1.0, 1.0]];
         let bps = BoundedPointSet {
             point_set: vec![],
-            bound: poly_points,
+            bound: BoundKind::Polygon(poly_points),
+            holes: vec![],
+            labels: vec![],
+            weights: vec![],
+            fixed: vec![],
+            bbox: None,
         };
         assert!(bps.bounding_polygon().is_err())
     }
 
+    #[test]
+    fn test_boundedpointset_bounding_polygon_holes_valid() {
+        let poly_points = vec![
+            [-2.0, -2.0],
+            [2.0, -2.0],
+            [2.0, 2.0],
+            [-2.0, 2.0],
+            [-2.0, -2.0],
+        ];
+        let hole_points = vec![[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, -1.0]];
+        let bps =
+            BoundedPointSet::new(vec![], poly_points.clone()).with_holes(vec![hole_points.clone()]);
+        let bounding_poly = bps.bounding_polygon().unwrap();
+
+        assert_eq!(bounding_poly.exterior().coords_iter().count(), poly_points.len());
+        assert_eq!(bounding_poly.interiors().len(), 1);
+        // The counter-clockwise hole is reversed.
+        for (c, point) in bounding_poly.interiors()[0]
+            .coords_iter()
+            .zip(hole_points.iter().rev())
+        {
+            assert_eq!(c.x, point[0]);
+            assert_eq!(c.y, point[1]);
+        }
+    }
+
+    #[test]
+    fn test_boundedpointset_bounding_polygon_holes_invalid() {
+        // To few points for a proper hole.
+        let poly_points = vec![
+            [-2.0, -2.0],
+            [2.0, -2.0],
+            [2.0, 2.0],
+            [-2.0, 2.0],
+            [-2.0, -2.0],
+        ];
+        let bps = BoundedPointSet::new(vec![], poly_points)
+            .with_holes(vec![vec![[0.0, 0.0], [1.0, 1.0]]]);
+        assert_eq!(bps.bounding_polygon().unwrap_err(), VoronoiError::TooFewHolePoints);
+    }
+
+    #[test]
+    fn test_boundedpointset_bounding_polygon_bowtie() {
+        let poly_points = vec![[0.0, 0.0], [2.0, 2.0], [2.0, 0.0], [0.0, 2.0], [0.0, 0.0]];
+        let bps = BoundedPointSet::new(vec![], poly_points);
+        assert_eq!(
+            bps.bounding_polygon().unwrap_err(),
+            VoronoiError::SelfIntersection {
+                hole: None,
+                segments: [0, 2]
+            }
+        );
+    }
+
+    #[test]
+    fn test_boundedpointset_bounding_polygon_bowtie_hole() {
+        let poly_points = vec![
+            [-4.0, -4.0],
+            [4.0, -4.0],
+            [4.0, 4.0],
+            [-4.0, 4.0],
+            [-4.0, -4.0],
+        ];
+        let hole_points = vec![[0.0, 0.0], [2.0, 2.0], [2.0, 0.0], [0.0, 2.0]];
+        let bps = BoundedPointSet::new(vec![], poly_points).with_holes(vec![hole_points]);
+        assert_eq!(
+            bps.bounding_polygon().unwrap_err(),
+            VoronoiError::SelfIntersection {
+                hole: Some(0),
+                segments: [0, 2]
+            }
+        );
+    }
+
+    #[test]
+    fn test_boundedpointset_bounding_polygon_repeated_points() {
+        let poly_points = vec![
+            [0.0, 0.0],
+            [0.0, 0.0],
+            [2.0, 0.0],
+            [2.0, 2.0],
+            [2.0, 2.0],
+            [2.0, 2.0],
+            [0.0, 2.0],
+            [0.0, 0.0],
+            [0.0, 0.0],
+        ];
+        let hole_points = vec![[0.5, 0.5], [1.0, 0.5], [1.0, 0.5], [1.0, 1.0]];
+        let bounding_poly = BoundedPointSet::new(vec![], poly_points)
+            .with_holes(vec![hole_points])
+            .bounding_polygon()
+            .unwrap();
+        let expected = Polygon::new(
+            LineString::from(vec![(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0), (0.0, 0.0)]),
+            vec![LineString::from(vec![
+                (0.5, 0.5),
+                (1.0, 1.0),
+                (1.0, 0.5),
+                (0.5, 0.5),
+            ])],
+        );
+        assert_eq!(bounding_poly, expected);
+        assert!(bounding_poly
+            .exterior()
+            .lines()
+            .chain(bounding_poly.interiors()[0].lines())
+            .all(|line| line.start != line.end));
+    }
+
+    #[test]
+    fn test_boundedpointset_bounding_polygon_repeated_points_too_few() {
+        let poly_points = vec![[0.0, 0.0], [1.0, 1.0], [1.0, 1.0], [0.0, 0.0]];
+        assert_eq!(
+            BoundedPointSet::new(vec![], poly_points)
+                .bounding_polygon()
+                .unwrap_err(),
+            VoronoiError::TooFewBoundPoints
+        );
+    }
+
+    #[test]
+    fn test_find_self_intersection_repeated_points() {
+        let ring = ring_to_line_string(vec![
+            [0.0, 0.0],
+            [2.0, 0.0],
+            [2.0, 0.0],
+            [2.0, 2.0],
+            [0.0, 2.0],
+            [0.0, 0.0],
+        ]);
+        assert!(find_self_intersection(ring).is_none());
+    }
+
+    #[test]
+    fn test_find_self_intersection_collinear_overlap() {
+        // The second segment folds back onto the first one, which only
+        // touches the other segments in their shared end points.
+        let ring = ring_to_line_string(vec![[0.0, 0.0], [2.0, 0.0], [1.0, 0.0], [0.0, 0.0]]);
+        assert_eq!(find_self_intersection(ring), Some([0, 1]));
+        // Collinear neighbouring segments that continue each other do not overlap.
+        let ring = ring_to_line_string(vec![
+            [0.0, 0.0],
+            [1.0, 0.0],
+            [2.0, 0.0],
+            [2.0, 2.0],
+            [0.0, 2.0],
+            [0.0, 0.0],
+        ]);
+        assert!(find_self_intersection(ring).is_none());
+    }
+
     #[test]
     fn test_boundedpointset_point_set() {
         // 4 unique values.
@@ -256,8 +1750,16 @@ mod tests {
             .map(|p| Point2D::new(p[0], p[1]).unwrap())
             .collect();
         let bps = BoundedPointSet {
-            point_set: point_set_duplicates,
-            bound: vec![],
+            point_set: point_set_duplicates
+                .into_iter()
+                .map(InputPoint::from)
+                .collect(),
+            bound: BoundKind::default(),
+            holes: vec![],
+            labels: vec![],
+            weights: vec![],
+            fixed: vec![],
+            bbox: None,
         };
         let point_set_unique = bps.point_set();
         assert_eq!(point_set_unique.len(), 4);
@@ -266,6 +1768,375 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_boundedpointset_sorted_point_set() {
+        let point_set = vec![
+            [1.0, 1.0],
+            [0.0, 2.0],
+            [1.0, -1.0],
+            [f64::NAN, 0.0],
+            [0.0, 2.0],
+        ];
+        let bps = BoundedPointSet::new(point_set, vec![]);
+        let expected: Vec<Point2D> = vec![[0.0, 2.0], [1.0, -1.0], [1.0, 1.0]]
+            .into_iter()
+            .map(|p| Point2D::new(p[0], p[1]).unwrap())
+            .collect();
+        assert_eq!(bps.sorted_point_set(), expected);
+    }
+
+    #[test]
+    fn test_boundedpointset_ordered_point_set() {
+        let bps = BoundedPointSet::new(
+            vec![
+                [2.0, 0.0],
+                [0.0, 1.0],
+                [f64::NAN, 0.0],
+                [2.0, 0.0],
+                [0.0, 0.0],
+            ],
+            vec![],
+        );
+        assert_eq!(
+            bps.ordered_point_set(),
+            vec![
+                Point2D::new(2.0, 0.0).unwrap(),
+                Point2D::new(0.0, 1.0).unwrap(),
+                Point2D::new(0.0, 0.0).unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_boundedpointset_point_labels() {
+        let point_set = vec![[0.0, 0.0], [1.0, 1.0], [f64::NAN, 1.0], [0.0, 0.0]];
+        let labels = vec!["a", "b", "c", "d"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let bps = BoundedPointSet::new(point_set, vec![]).with_labels(labels);
+        let point_labels = bps.point_labels().unwrap();
+        assert_eq!(point_labels.len(), 2);
+        // The first occurrence of a duplicate point determines the label.
+        assert_eq!(point_labels[&Point2D::new(0.0, 0.0).unwrap()], "a");
+        assert_eq!(point_labels[&Point2D::new(1.0, 1.0).unwrap()], "b");
+    }
+
+    #[test]
+    fn test_boundedpointset_push_point() {
+        let mut bps = BoundedPointSet::new(vec![[0.0, 0.0]], vec![])
+            .with_labels(vec!["first".into()])
+            .with_weights(vec![2.0]);
+        bps.push_point([1.0, 1.0]);
+        assert_eq!(bps.ordered_point_set().len(), 2);
+        assert_eq!(bps.labels(), &vec!["first".to_string(), String::new()]);
+        assert_eq!(bps.weights(), &vec![2.0, 1.0]);
+        assert!(bps.point_labels().is_ok());
+    }
+
+    #[test]
+    fn test_boundedpointset_point_labels_empty() {
+        let bps = BoundedPointSet::new(vec![[0.0, 0.0], [1.0, 1.0]], vec![]);
+        assert!(bps.point_labels().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_boundedpointset_point_labels_mismatch() {
+        let bps = BoundedPointSet::new(vec![[0.0, 0.0], [1.0, 1.0]], vec![])
+            .with_labels(vec!["a".to_string()]);
+        assert_eq!(
+            bps.point_labels().unwrap_err(),
+            VoronoiError::LabelCountMismatch {
+                labels: 1,
+                points: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_boundedpointset_bounding_polygon_bbox() {
+        let json = r#"{"points": [[0.0, 0.0]], "bbox": [-1.0, -2.0, 3.0, 4.0]}"#;
+        let bps: BoundedPointSet = serde_json::from_str(json).unwrap();
+        let ring = BoundedPointSet::new(
+            vec![[0.0, 0.0]],
+            vec![
+                [-1.0, -2.0],
+                [3.0, -2.0],
+                [3.0, 4.0],
+                [-1.0, 4.0],
+                [-1.0, -2.0],
+            ],
+        );
+        assert_eq!(bps.bounding_polygon().unwrap(), ring.bounding_polygon().unwrap());
+    }
+
+    #[test]
+    fn test_boundedpointset_bounding_polygon_bbox_invalid() {
+        let bps = BoundedPointSet::with_bbox(vec![[0.0, 0.0]], [1.0, 0.0, -1.0, 2.0]);
+        assert_eq!(bps.bounding_polygon().unwrap_err(), VoronoiError::InvalidBoundingBox);
+        let json = r#"{
+            "points": [[0.0, 0.0]],
+            "bound": [[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0]],
+            "bbox": [-1.0, -1.0, 1.0, 1.0]
+        }"#;
+        let bps: BoundedPointSet = serde_json::from_str(json).unwrap();
+        assert_eq!(bps.bounding_polygon().unwrap_err(), VoronoiError::ConflictingBounds);
+    }
+
+    #[test]
+    fn test_boundedpointset_bounding_polygon_circle() {
+        let json = r#"{"points": [[0.0, 0.0]], "bound": {"circle": {"center": [1.0, 2.0], "radius": 3.0}}}"#;
+        let bps: BoundedPointSet = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            bps.bound(),
+            &BoundKind::Circle {
+                circle: CircleBound::new([1.0, 2.0], 3.0)
+            }
+        );
+        let polygon = bps.bounding_polygon().unwrap();
+        assert_eq!(polygon.exterior().0.len(), DEFAULT_CIRCLE_SEGMENTS + 1);
+        assert!(polygon.exterior().points().all(|point| ((point.x() - 1.0)
+            .hypot(point.y() - 2.0)
+            - 3.0)
+            .abs()
+            < 1e-12));
+        let segments = DEFAULT_CIRCLE_SEGMENTS as f64;
+        assert_abs_diff_eq!(
+            polygon.unsigned_area(),
+            segments / 2.0 * 9.0 * (2.0 * f64::consts::PI / segments).sin(),
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_boundedpointset_bounding_polygon_circle_segments() {
+        let json = r#"{"points": [[0.0, 0.0]], "bound": {"circle": {"center": [0.0, 0.0], "radius": 1.0, "segments": 4}}}"#;
+        let bps: BoundedPointSet = serde_json::from_str(json).unwrap();
+        let polygon = bps.bounding_polygon().unwrap();
+        assert_eq!(polygon.exterior().0.len(), 5);
+        assert_abs_diff_eq!(polygon.unsigned_area(), 2.0, epsilon = 1e-12);
+        assert_eq!(polygon.exterior().0.first(), polygon.exterior().0.last());
+    }
+
+    #[test]
+    fn test_boundedpointset_bounding_polygon_circle_invalid() {
+        for circle in [
+            CircleBound::new([0.0, 0.0], 0.0),
+            CircleBound::new([0.0, 0.0], f64::INFINITY),
+            CircleBound::new([f64::NAN, 0.0], 1.0),
+            CircleBound::new([0.0, 0.0], 1.0).with_segments(2),
+        ] {
+            let bps = BoundedPointSet::with_circle(vec![[0.0, 0.0]], circle);
+            assert_eq!(bps.bounding_polygon().unwrap_err(), VoronoiError::InvalidCircle);
+        }
+    }
+
+    #[test]
+    fn test_boundedpointset_bounding_multi_polygon() {
+        let json = r#"{
+            "points": [[1.0, 1.0], [4.5, 4.5]],
+            "bound": [
+                [[0.0, 0.0], [3.0, 0.0], [3.0, 3.0], [0.0, 3.0]],
+                [[4.0, 4.0], [5.0, 4.0], [5.0, 5.0], [4.0, 5.0]]
+            ],
+            "holes": [[[1.0, 1.0], [2.0, 1.0], [2.0, 2.0], [1.0, 2.0]]]
+        }"#;
+        let bps: BoundedPointSet = serde_json::from_str(json).unwrap();
+        assert!(matches!(bps.bound(), BoundKind::MultiPolygon(rings) if rings.len() == 2));
+        assert_eq!(bps.bounding_polygon().unwrap_err(), VoronoiError::MultiPolygonBound);
+        let multi_polygon = bps.bounding_multi_polygon().unwrap();
+        assert_eq!(multi_polygon.0.len(), 2);
+        assert_abs_diff_eq!(multi_polygon.unsigned_area(), 9.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_boundedpointset_bounding_multi_polygon_single_ring() {
+        let bps = BoundedPointSet::new(
+            vec![[0.0, 0.0]],
+            vec![[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, -1.0]],
+        );
+        assert_eq!(
+            bps.bounding_multi_polygon().unwrap(),
+            MultiPolygon::new(vec![bps.bounding_polygon().unwrap()])
+        );
+    }
+
+    #[test]
+    fn test_boundedpointset_point_set_with_report() {
+        let point_set = vec![
+            [1.0, 2.0],
+            [0.0, 0.0],
+            [1.0, 2.0],
+            [f64::NAN, 0.0],
+            [1.0, 2.0],
+        ];
+        let bps = BoundedPointSet::new(point_set, vec![]);
+        let (point_set, report) = bps.point_set_with_report();
+        assert_eq!(point_set, bps.point_set());
+        assert_eq!(report.kept(), 2);
+        // Invalid points are filtered, but not reported as duplicates.
+        assert_eq!(report.dropped(), &vec![[1.0, 2.0], [1.0, 2.0]]);
+    }
+
+    #[test]
+    fn test_boundedpointset_point_set_with_rejects() {
+        let subnormal = f64::MIN_POSITIVE / 2.0;
+        let point_set = vec![
+            [1.0, 2.0],
+            [f64::NAN, 0.0],
+            [0.0, f64::INFINITY],
+            [f64::NEG_INFINITY, f64::NAN],
+            [subnormal, 0.0],
+            [1.0, 2.0],
+        ];
+        let bps = BoundedPointSet::new(point_set, vec![]);
+        let (point_set, rejects) = bps.point_set_with_rejects();
+        assert_eq!(point_set, bps.point_set());
+        assert_eq!(point_set.len(), 2);
+        assert_eq!(
+            rejects
+                .iter()
+                .map(|reject| (reject.index(), reject.reason()))
+                .collect::<Vec<_>>(),
+            vec![
+                (1, RejectReason::NaN),
+                (2, RejectReason::Infinite),
+                (3, RejectReason::NaN)
+            ]
+        );
+        assert!(rejects[0].coordinates()[0].is_nan());
+        assert_eq!(rejects[1].coordinates(), [0.0, f64::INFINITY]);
+    }
+
+    #[test]
+    fn test_boundedpointset_point_set_with_rejects_subnormal() {
+        // Subnormal coordinates are valid and kept.
+        let subnormal = f64::MIN_POSITIVE / 2.0;
+        let bps = BoundedPointSet::new(vec![[subnormal, -subnormal]], vec![]);
+        let (point_set, rejects) = bps.point_set_with_rejects();
+        assert_eq!(point_set.len(), 1);
+        assert!(rejects.is_empty());
+    }
+
+    #[test]
+    fn test_boundedpointset_point_weights() {
+        let point_set = vec![[0.0, 0.0], [1.0, 1.0], [f64::NAN, 1.0], [0.0, 0.0]];
+        let bps = BoundedPointSet::new(point_set, vec![]).with_weights(vec![2.0, 0.5, 1.0, 3.0]);
+        let point_weights = bps.point_weights().unwrap();
+        assert_eq!(point_weights.len(), 2);
+        // The first occurrence of a duplicate point determines the weight.
+        assert_eq!(point_weights[&Point2D::new(0.0, 0.0).unwrap()], 2.0);
+        assert_eq!(point_weights[&Point2D::new(1.0, 1.0).unwrap()], 0.5);
+    }
+
+    #[test]
+    fn test_boundedpointset_point_weights_default() {
+        let bps = BoundedPointSet::new(vec![[0.0, 0.0], [1.0, 1.0]], vec![]);
+        let point_weights = bps.point_weights().unwrap();
+        assert_eq!(point_weights.len(), 2);
+        assert!(point_weights.values().all(|&weight| weight == 1.0));
+    }
+
+    #[test]
+    fn test_boundedpointset_point_weights_invalid() {
+        let bps =
+            BoundedPointSet::new(vec![[0.0, 0.0], [1.0, 1.0]], vec![]).with_weights(vec![1.0, 0.0]);
+        assert_eq!(bps.point_weights().unwrap_err(), VoronoiError::InvalidWeight { weight: 0.0 });
+        let bps =
+            BoundedPointSet::new(vec![[0.0, 0.0], [1.0, 1.0]], vec![]).with_weights(vec![1.0]);
+        assert_eq!(
+            bps.point_weights().unwrap_err(),
+            VoronoiError::WeightCountMismatch {
+                weights: 1,
+                points: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_boundedpointset_sites_outside_bound_contained() {
+        // The bound is placed elsewhere, but its extent covers the extent of the points.
+        let bps = BoundedPointSet::new(
+            vec![[10.0, 10.0], [11.0, 10.5], [12.0, 11.0]],
+            vec![[0.0, 0.0], [2.0, 0.0], [2.0, 1.0], [0.0, 1.0]],
+        );
+        assert!(bps.sites_outside_bound().unwrap().is_empty());
+        let bps = BoundedPointSet::with_bbox(vec![[0.0, 0.0], [1.0, 1.0]], [5.0, 5.0, 6.0, 6.0]);
+        assert!(bps.sites_outside_bound().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_boundedpointset_sites_outside_bound_spilling_over() {
+        let bps = BoundedPointSet::new(
+            vec![[0.0, 0.0], [1.0, 0.2], [2.0, 0.4], [3.0, 0.0], [4.0, 0.0]],
+            vec![[0.0, 0.0], [2.0, 0.0], [2.0, 1.0], [0.0, 1.0]],
+        );
+        // The bound is centered around [2.0, 0.2] and reaches from 1.0 to 3.0 along x.
+        assert_eq!(
+            bps.sites_outside_bound().unwrap(),
+            vec![
+                Point2D::new(0.0, 0.0).unwrap(),
+                Point2D::new(4.0, 0.0).unwrap()
+            ]
+        );
+        assert_eq!(
+            BoundedPointSet::new(vec![[0.0, 0.0]], Vec::new())
+                .sites_outside_bound()
+                .unwrap_err(),
+            VoronoiError::TooFewBoundPoints
+        );
+    }
+
+    #[test]
+    fn test_boundedpointset_convex_hull() {
+        let bps = BoundedPointSet::new(
+            vec![
+                [0.0, 0.0],
+                [2.0, 0.0],
+                [2.0, 1.0],
+                [0.0, 1.0],
+                [1.0, 0.5],
+                [f64::NAN, 5.0],
+            ],
+            vec![],
+        );
+        let hull = bps.convex_hull(0.0).unwrap();
+        assert_ulps_eq!(hull.unsigned_area(), 2.0);
+        // The expanded hull contains all locations within the margin of a point
+        // and approximates the rounded rectangle.
+        let hull = bps.convex_hull(0.5).unwrap();
+        assert!(hull.contains(&geo::Point::new(-0.5, 0.0)));
+        assert!(hull.contains(&geo::Point::new(2.35, 1.35)));
+        let rounded_area = 2.0 + 2.0 * (2.0 + 1.0) * 0.5 + f64::consts::PI * 0.25;
+        assert!(hull.unsigned_area() > rounded_area);
+        assert!(hull.unsigned_area() < rounded_area * 1.01);
+        let collinear = BoundedPointSet::new(vec![[0.0, 0.0], [1.0, 1.0], [2.0, 2.0]], vec![]);
+        assert_eq!(collinear.convex_hull(0.0), None);
+        assert!(collinear.convex_hull(0.1).is_some());
+    }
+
+    #[test]
+    fn test_boundedpointset_fixed_points() {
+        let bps =
+            BoundedPointSet::new(vec![[0.0, 0.0], [1.0, 1.0], [0.0, 0.0], [2.0, 2.0]], vec![])
+                .with_fixed(vec![true, false, false, true]);
+        assert_eq!(
+            bps.fixed_points().unwrap(),
+            HashSet::from([
+                Point2D::new(0.0, 0.0).unwrap(),
+                Point2D::new(2.0, 2.0).unwrap()
+            ])
+        );
+        let bps = BoundedPointSet::new(vec![[0.0, 0.0], [1.0, 1.0]], vec![]).with_fixed(vec![true]);
+        assert_eq!(
+            bps.fixed_points().unwrap_err(),
+            VoronoiError::FixedCountMismatch {
+                fixed: 1,
+                points: 2
+            }
+        );
+    }
+
     #[test]
     fn test_boundedpointset_voronoi_point_set() {
         // 4 unique values.
@@ -277,8 +2148,17 @@ mod tests {
             [1.0, 1.0],
         ];
         let bps = BoundedPointSet {
-            point_set: point_set_duplicates.clone(),
-            bound: vec![],
+            point_set: point_set_duplicates
+                .iter()
+                .copied()
+                .map(InputPoint::from)
+                .collect(),
+            bound: BoundKind::default(),
+            holes: vec![],
+            labels: vec![],
+            weights: vec![],
+            fixed: vec![],
+            bbox: None,
         };
         let point_set_voronoi = bps.voronoi_point_set();
         let point_set_voronoi_converted: Vec<[f64; 2]> =
@@ -290,6 +2170,17 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "schema")]
+    fn test_boundedpointset_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(BoundedPointSet)).unwrap();
+        let properties = schema["properties"].as_object().unwrap();
+        assert!(properties.contains_key("points"));
+        assert!(!properties.contains_key("point_set"));
+        assert!(properties.contains_key("bound"));
+        assert_eq!(schema["required"], serde_json::json!(["points"]));
+    }
+
     #[test]
     fn test_point2d_new_valid() {
         let x = -10.0;
@@ -304,15 +2195,122 @@ mod tests {
         assert!(Point2D::new(f64::INFINITY, -10.0).is_none());
         assert!(Point2D::new(f64::NEG_INFINITY, -10.0).is_none());
         assert!(Point2D::new(f64::NAN, -10.0).is_none());
-        assert!(Point2D::new(1.0e-308_f64, -10.0).is_none());
         assert!(Point2D::new(0.0, -10.0).is_some());
         assert!(Point2D::new(10.0, f64::INFINITY).is_none());
         assert!(Point2D::new(10.0, f64::NEG_INFINITY).is_none());
         assert!(Point2D::new(10.0, f64::NAN).is_none());
-        assert!(Point2D::new(10.0, 1.0e-308_f64).is_none());
         assert!(Point2D::new(10.0, 0.0).is_some());
     }
 
+    #[test]
+    fn test_boundedpointset_validate() {
+        let bps = BoundedPointSet::with_bbox(
+            vec![[0.0, 0.0], [1.0, 1.0], [0.0, 0.0], [f64::NAN, 0.0]],
+            [-1.0, -1.0, 1.0, 1.0],
+        );
+        let report = bps.validate().unwrap();
+        assert_eq!(report.kept(), 2);
+        assert_eq!(report.dropped(), &vec![[0.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_boundedpointset_validate_invalid() {
+        // The bound intersects itself.
+        let bps = BoundedPointSet::new(
+            vec![[0.0, 0.0]],
+            vec![[0.0, 0.0], [1.0, 1.0], [1.0, 0.0], [0.0, 1.0], [0.0, 0.0]],
+        );
+        assert!(matches!(
+            bps.validate().unwrap_err(),
+            VoronoiError::SelfIntersection { hole: None, .. }
+        ));
+        let bps = BoundedPointSet::with_bbox(vec![[f64::NAN, 0.0]], [-1.0, -1.0, 1.0, 1.0]);
+        assert_eq!(bps.validate().unwrap_err(), VoronoiError::EmptyPointSet);
+        let bps = BoundedPointSet::new(vec![[0.0, 0.0]], vec![[0.0, 0.0], [1.0, 1.0]]);
+        assert_eq!(bps.validate().unwrap_err(), VoronoiError::TooFewBoundPoints);
+    }
+
+    #[test]
+    fn test_point2d_new_subnormal() {
+        let subnormal = 1.0e-310_f64;
+        assert!(subnormal.is_subnormal());
+        let point = Point2D::new(subnormal, -subnormal).unwrap();
+        assert_eq!(point.x(), subnormal);
+        assert_eq!(point.y(), -subnormal);
+        assert!(point > Point2D::new(0.0, 0.0).unwrap());
+    }
+
+    #[test]
+    fn test_point2d_new_negative_zero() {
+        let point = Point2D::new(-0.0, -0.0).unwrap();
+        assert!(point.x().is_sign_positive());
+        assert!(point.y().is_sign_positive());
+        let point_set: HashSet<Point2D> = [Point2D::new(0.0, 0.0), Point2D::new(-0.0, -0.0)]
+            .into_iter()
+            .flatten()
+            .collect();
+        assert_eq!(point_set.len(), 1);
+    }
+
+    #[test]
+    fn test_boundedpointset_point_set_subnormal() {
+        let subnormal = 1.0e-310_f64;
+        let bps = BoundedPointSet::new(
+            vec![
+                [subnormal, 1.0],
+                [1.0, subnormal],
+                [subnormal, 1.0],
+                [0.0, 0.0],
+            ],
+            vec![],
+        );
+        let point_set = bps.point_set();
+        assert_eq!(point_set.len(), 3);
+        assert!(point_set.contains(&Point2D::new(subnormal, 1.0).unwrap()));
+        assert!(point_set.contains(&Point2D::new(1.0, subnormal).unwrap()));
+        let sorted_point_set = bps.sorted_point_set();
+        assert_eq!(sorted_point_set[0], Point2D::new(0.0, 0.0).unwrap());
+        assert_eq!(sorted_point_set[1], Point2D::new(subnormal, 1.0).unwrap());
+        assert_eq!(sorted_point_set[2], Point2D::new(1.0, subnormal).unwrap());
+    }
+
+    #[test]
+    fn test_point2d_from_coordinates() {
+        let coordinates = (0..4).map(|i| match i {
+            1 => [f64::INFINITY, 0.0],
+            2 => [0.0, f64::NAN],
+            _ => [i as f64, -(i as f64)],
+        });
+        assert_eq!(
+            Point2D::from_coordinates(coordinates),
+            vec![
+                Point2D::new(0.0, 0.0).unwrap(),
+                Point2D::new(3.0, -3.0).unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_point2d_from_integer() {
+        let limit: i64 = 1 << 53;
+        let point = Point2D::from_integer(limit, -limit).unwrap();
+        assert_eq!(point.x(), 9007199254740992.0);
+        assert_eq!(point.y(), -9007199254740992.0);
+        assert_eq!(Point2D::from_integer(0, 0).unwrap(), Point2D::new(0.0, 0.0).unwrap());
+        assert_eq!(
+            Point2D::from_integer(limit + 1, 0).unwrap_err(),
+            VoronoiError::IntegerOutOfRange { value: limit + 1 }
+        );
+        assert_eq!(
+            Point2D::from_integer(0, -limit - 1).unwrap_err(),
+            VoronoiError::IntegerOutOfRange { value: -limit - 1 }
+        );
+        assert_eq!(
+            Point2D::from_integer(i64::MIN, 0).unwrap_err(),
+            VoronoiError::IntegerOutOfRange { value: i64::MIN }
+        );
+    }
+
     #[test]
     fn test_bounds_from_polygon_valid() {
         let poly: Polygon = Polygon::new(
@@ -384,4 +2382,80 @@ mod tests {
         assert_ulps_eq!(bounds.centre_x(), 7.0);
         assert_ulps_eq!(bounds.centre_y(), 25.0);
     }
+
+    #[test]
+    fn test_bounds_union_expand() {
+        let point_set: HashSet<Point2D> = vec![(0.0, 0.0), (2.0, 1.0)]
+            .into_iter()
+            .map(|(x, y)| Point2D { x, y })
+            .collect();
+        let other_point_set: HashSet<Point2D> = vec![(-1.0, 0.5), (1.0, 4.0)]
+            .into_iter()
+            .map(|(x, y)| Point2D { x, y })
+            .collect();
+        let bounds = Bounds::from_point_set(point_set)
+            .unwrap()
+            .union(Bounds::from_point_set(other_point_set).unwrap())
+            .expand(1.0, 0.5);
+        assert_ulps_eq!(bounds.min_x(), -2.0);
+        assert_ulps_eq!(bounds.max_x(), 3.0);
+        assert_ulps_eq!(bounds.min_y(), -0.5);
+        assert_ulps_eq!(bounds.max_y(), 4.5);
+    }
+
+    #[test]
+    fn test_bounds_union_overlapping_disjoint() {
+        let bounds = |min_x, min_y, max_x, max_y| Bounds {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+        };
+        let overlapping = Bounds::union(&bounds(0.0, 0.0, 2.0, 2.0), bounds(1.0, -1.0, 3.0, 1.0));
+        assert_eq!(overlapping, bounds(0.0, -1.0, 3.0, 2.0));
+        let disjoint = Bounds::union(&bounds(0.0, 0.0, 1.0, 1.0), bounds(5.0, 6.0, 7.0, 8.0));
+        assert_eq!(disjoint, bounds(0.0, 0.0, 7.0, 8.0));
+        let contained = bounds(-1.0, -1.0, 4.0, 4.0);
+        assert_eq!(contained.union(bounds(0.0, 0.0, 1.0, 1.0)), contained);
+    }
+
+    #[test]
+    fn test_boundedpointset_combined_bounds() {
+        let bps = BoundedPointSet::with_bbox(vec![[0.0, 0.0], [10.0, 3.0]], [-1.0, -2.0, 1.0, 2.0]);
+        let bounds = bps.combined_bounds().unwrap();
+        assert_eq!(
+            [
+                bounds.min_x(),
+                bounds.min_y(),
+                bounds.max_x(),
+                bounds.max_y()
+            ],
+            [-1.0, -2.0, 10.0, 3.0]
+        );
+        let bps = BoundedPointSet::new(vec![[0.0, 0.0], [10.0, 3.0]], vec![]);
+        let bounds = bps.combined_bounds().unwrap();
+        assert_eq!(
+            [
+                bounds.min_x(),
+                bounds.min_y(),
+                bounds.max_x(),
+                bounds.max_y()
+            ],
+            [0.0, 0.0, 10.0, 3.0]
+        );
+        let bps = BoundedPointSet::with_bbox(vec![[f64::NAN, 0.0]], [-1.0, -2.0, 1.0, 2.0]);
+        let bounds = bps.combined_bounds().unwrap();
+        assert_eq!(
+            [
+                bounds.min_x(),
+                bounds.min_y(),
+                bounds.max_x(),
+                bounds.max_y()
+            ],
+            [-1.0, -2.0, 1.0, 2.0]
+        );
+        assert!(BoundedPointSet::new(vec![], vec![])
+            .combined_bounds()
+            .is_none());
+    }
 }
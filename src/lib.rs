@@ -0,0 +1,48 @@
+//! A library to convert a 2-dimensional point set to finite Voronoi cells
+//! that are bound by an arbitrary polygon.
+
+pub use builder::{BoundedVoronoi, BoundedVoronoiBuilder};
+pub use diagram::VoronoiDiagram;
+pub use error::VoronoiError;
+pub use input::{
+    BoundKind, BoundedPointSet, Bounds, CircleBound, DeduplicationReport, InputPoint,
+    MultiGroupInput, Point2D, PointProperties, RejectReason, RejectedPoint,
+};
+#[cfg(feature = "shapefile")]
+pub use output::to_shapefile;
+pub use output::{
+    sites_to_wkt, to_adjacency_csv, to_coordinate_objects, to_csv, to_geojson, to_kml, to_svg,
+    to_table, to_topojson, to_wkt, Coordinate, DiagrammBoundingBox, OutputMetadata, SvgOptions,
+    VoronoiOutput,
+};
+#[cfg(feature = "proj")]
+pub use projection::Projection;
+pub use projection::{project_point_set, unproject_cell, CoordinateTransform};
+pub use query::locate;
+#[cfg(feature = "rstar")]
+pub use query::CellIndex;
+pub use sphere::compute_spherical_voronoi;
+pub use statistics::{
+    centered_bound_coverage, coverage, sample_density, shared_boundaries, union_cells,
+    verify_tessellation, Coverage, TessellationCheck,
+};
+pub use voronoi::{
+    compute_delaunay, compute_farthest_voronoi, compute_l1_voronoi, compute_power_diagram,
+    compute_voronoi, compute_voronoi_groups, compute_voronoi_iter,
+    compute_voronoi_iter_with_options, compute_voronoi_with_options, compute_voronoi_with_report,
+    compute_weighted_voronoi, delaunay_edges, drop_degenerate_cells, find_degenerate_cells,
+    inset_bound, round_to_decimals, voronoi_vertices, BoundedVoronoiCell, DegenerateReport,
+    DelaunayEdge, RelaxationReport, SitePlacement, SortKey, VoronoiOptions,
+};
+
+pub mod builder;
+pub mod diagram;
+pub mod error;
+mod hull;
+pub mod input;
+pub mod output;
+pub mod projection;
+pub mod query;
+pub mod sphere;
+pub mod statistics;
+pub mod voronoi;
@@ -1,25 +1,913 @@
-use arguments::CommandLineArguments;
-use clap::Parser;
-use input::BoundedPointSet;
-use voronoi::compute_voronoi;
+use std::{
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use arguments::{has_gzip_extension, CommandLineArguments, OutputFormat, RunConfig};
+use clap::{CommandFactory, FromArgMatches};
+use flate2::{read::MultiGzDecoder, write::GzEncoder, Compression};
+use geo::{Area, MapCoords};
+#[cfg(feature = "shapefile")]
+use geo_bounded_voronoi::to_shapefile;
+#[cfg(feature = "proj")]
+use geo_bounded_voronoi::Projection;
+use geo_bounded_voronoi::{
+    centered_bound_coverage, compute_delaunay, compute_voronoi_groups,
+    compute_voronoi_iter_with_options, compute_voronoi_with_report, coverage,
+    drop_degenerate_cells, find_degenerate_cells, inset_bound, project_point_set,
+    round_to_decimals, sites_to_wkt, to_adjacency_csv, to_coordinate_objects, to_csv, to_geojson,
+    to_kml, to_svg, to_table, to_topojson, to_wkt, unproject_cell, verify_tessellation,
+    voronoi_vertices, BoundKind, BoundedPointSet, BoundedVoronoiCell, CoordinateTransform,
+    MultiGroupInput, OutputMetadata, RejectReason, SvgOptions, VoronoiError, VoronoiOptions,
+    VoronoiOutput,
+};
+use serde::{ser::SerializeSeq, Serialize, Serializer};
+
+/// The area relative to the bound area below which a cell is considered a degenerate sliver.
+const DEGENERATE_CELL_AREA_RATIO: f64 = 1e-12;
+/// The area of gaps and overlaps relative to the bound area above which the
+/// tessellation is reported as erroneous.
+const VERIFICATION_TOLERANCE: f64 = 1e-9;
+/// The spacing of the grid the TopoJSON vertices are matched on if no snap precision is set.
+const TOPOLOGY_EPSILON: f64 = 1e-9;
+/// The maximum distance of two cell edges that are considered to overlap
+/// when computing the length of the boundary shared by neighboring cells.
+const SHARED_BOUNDARY_EPSILON: f64 = 1e-9;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Parses command line arguments.
-    let cl_args = CommandLineArguments::parse();
-    let input_file = cl_args.point_set_file();
-    let output_path = cl_args.output_path();
+    // Parses command line arguments and merges them with the configuration file.
+    let matches = CommandLineArguments::command().get_matches();
+    let mut cl_args =
+        CommandLineArguments::from_arg_matches(&matches).unwrap_or_else(|error| error.exit());
+    if let Some(config) = cl_args.config() {
+        cl_args.merge(RunConfig::from_file(config)?, &matches);
+    }
+    if cl_args.print_schema() {
+        return print_schema();
+    }
+    if cl_args.is_batch()
+        && cl_args
+            .point_set_files()
+            .iter()
+            .any(|point_set_file| cl_args.reads_stdin(point_set_file))
+    {
+        return Err("Stdin can only be read if a single point set file is specified.".into());
+    }
+    if cl_args.split_output().is_some()
+        && (!matches!(cl_args.format(), OutputFormat::Json | OutputFormat::Geojson)
+            || cl_args.delaunay()
+            || cl_args.vertices())
+    {
+        return Err(
+            "Cells can only be written to separate files in the JSON or GeoJSON format.".into()
+        );
+    }
+    if cl_args.seed().is_some() && cl_args.input_order() {
+        return Err("A seed cannot be combined with the input order.".into());
+    }
+
+    if cl_args.hull_bound().is_some() && cl_args.verify() {
+        return Err("The convex hull bound cannot be verified.".into());
+    }
+    if cl_args.groups()
+        && (cl_args.format() != OutputFormat::Json
+            || cl_args.delaunay()
+            || cl_args.vertices()
+            || cl_args.split_output().is_some()
+            || cl_args.project().is_some()
+            || cl_args.with_metadata()
+            || cl_args.echo_bound()
+            || cl_args.coord_object()
+            || cl_args.drop_degenerate()
+            || cl_args.stats()
+            || cl_args.verify())
+    {
+        return Err("Groups of points can only be written as JSON cells.".into());
+    }
+    if cl_args.echo_bound()
+        && (cl_args.format() != OutputFormat::Json
+            || cl_args.delaunay()
+            || cl_args.vertices()
+            || cl_args.split_output().is_some())
+    {
+        return Err("The bound can only be echoed in the JSON output of the cells.".into());
+    }
+    if cl_args.format() == OutputFormat::Shapefile
+        && cl_args.point_set_files().iter().any(|point_set_file| {
+            cl_args.writes_stdout(point_set_file) || cl_args.writes_gzip(point_set_file)
+        })
+    {
+        return Err("Shapefiles can only be written to uncompressed files.".into());
+    }
+
+    // Reads the bound shared by all point set files once.
+    let bound = cl_args.bound().as_deref().map(read_bound).transpose()?;
+
+    // Processes each point set file and reports errors without aborting the batch
+    // unless requested.
+    let mut failed_files = 0;
+    for point_set_file in cl_args.point_set_files() {
+        if let Err(error) = process_point_set_file(&cl_args, point_set_file, bound.as_ref()) {
+            if !cl_args.is_batch() || cl_args.fail_fast() {
+                return Err(error);
+            }
+            eprintln!("Error processing {}: {}", point_set_file.display(), error);
+            failed_files += 1;
+        }
+    }
+    if failed_files > 0 {
+        return Err(format!(
+            "{} of {} point set files could not be processed.",
+            failed_files,
+            cl_args.point_set_files().len()
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+/// Writes the JSON Schema of the point set file to stdout.
+#[cfg(feature = "schema")]
+fn print_schema() -> Result<(), Box<dyn std::error::Error>> {
+    let mut output = std::io::stdout().lock();
+    serde_json::to_writer_pretty(&mut output, &schemars::schema_for!(BoundedPointSet))?;
+    writeln!(output)?;
+    Ok(())
+}
+
+/// Fails, as the JSON Schema is only available with the `schema` feature.
+#[cfg(not(feature = "schema"))]
+fn print_schema() -> Result<(), Box<dyn std::error::Error>> {
+    Err("The schema can only be printed if the `schema` feature is enabled.".into())
+}
+
+/// Returns the projection of the input coordinates if requested.
+///
+/// # Parameters
+///
+/// * `cl_args` - the command line arguments
+#[cfg(feature = "proj")]
+fn projection(
+    cl_args: &CommandLineArguments,
+) -> Result<Option<Box<dyn CoordinateTransform>>, Box<dyn std::error::Error>> {
+    match cl_args.project() {
+        Some(definition) => Ok(Some(Box::new(Projection::new(definition)?))),
+        None => Ok(None),
+    }
+}
 
-    // Parses the input file.
-    let point_set_input: BoundedPointSet =
-        serde_json::from_reader(std::fs::File::open(input_file)?)?;
+/// Returns no projection or fails if a projection has been requested,
+/// as projections are only available with the `proj` feature.
+///
+/// # Parameters
+///
+/// * `cl_args` - the command line arguments
+#[cfg(not(feature = "proj"))]
+fn projection(
+    cl_args: &CommandLineArguments,
+) -> Result<Option<Box<dyn CoordinateTransform>>, Box<dyn std::error::Error>> {
+    match cl_args.project() {
+        Some(_) => Err("Projections are only available if the `proj` feature is enabled.".into()),
+        None => Ok(None),
+    }
+}
+
+/// Reads the bound from a JSON file, which is decompressed if it has a `.gz` extension.
+///
+/// # Parameters
+///
+/// * `bound_file` - the path to the bound file
+fn read_bound(bound_file: &Path) -> Result<BoundKind, Box<dyn std::error::Error>> {
+    let input: Box<dyn Read> = Box::new(std::fs::File::open(bound_file)?);
+    let input: Box<dyn Read> = if has_gzip_extension(bound_file) {
+        Box::new(MultiGzDecoder::new(BufReader::new(input)))
+    } else {
+        input
+    };
+    serde_json::from_reader(BufReader::new(input)).map_err(|error| {
+        format!("The bound file {} is invalid: {}", bound_file.display(), error).into()
+    })
+}
+
+/// Computes the Voronoi diagramm or Delaunay triangulation of a single
+/// point set file and writes it to the according output.
+///
+/// # Parameters
+///
+/// * `cl_args` - the command line arguments
+/// * `point_set_file` - the point set file to process
+/// * `bound` - the bound replacing the bound of the point set file if any
+fn process_point_set_file(
+    cl_args: &CommandLineArguments,
+    point_set_file: &Path,
+    bound: Option<&BoundKind>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Parses the input file or stdin.
+    let input: Box<dyn Read> = if cl_args.reads_stdin(point_set_file) {
+        Box::new(std::io::stdin().lock())
+    } else {
+        Box::new(std::fs::File::open(point_set_file)?)
+    };
+    let input: Box<dyn Read> = if cl_args.reads_gzip(point_set_file) {
+        Box::new(MultiGzDecoder::new(BufReader::new(input)))
+    } else {
+        input
+    };
+    if cl_args.groups() {
+        return process_groups(cl_args, point_set_file, input, bound);
+    }
+    let point_set_input: BoundedPointSet = serde_json::from_reader(BufReader::new(input))?;
+    let point_set_input = match bound {
+        Some(bound) => {
+            if point_set_input.has_bound() {
+                eprintln!(
+                    "Warning: The bound of {} is replaced by the bound specified with --bound.",
+                    point_set_file.display()
+                );
+            }
+            point_set_input.with_bound(bound.clone())
+        },
+        None => point_set_input,
+    };
+    // The convex hull is only used if no bound has been specified.
+    let hull_margin = cl_args
+        .hull_bound()
+        .filter(|_| !point_set_input.has_bound());
+    if cl_args.hull_bound().is_some() && hull_margin.is_none() {
+        eprintln!(
+            "Warning: The bound of {} is used instead of the convex hull.",
+            point_set_file.display()
+        );
+    }
+    let (_, deduplication_report) = point_set_input.point_set_with_report();
+    if !deduplication_report.dropped().is_empty() {
+        eprintln!(
+            "Warning: {} duplicate points have been removed.",
+            deduplication_report.dropped().len()
+        );
+    }
+    if cl_args.strict() && point_set_input.has_bound() {
+        check_sites_within_bound(&point_set_input)?;
+    }
+
+    if cl_args.check() {
+        // Only validates the input without writing any output.
+        let report = point_set_input.validate()?;
+        println!(
+            "{}: valid with {} unique points and {} duplicate points.",
+            point_set_file.display(),
+            report.kept(),
+            report.dropped().len()
+        );
+        return Ok(());
+    }
+
+    // Projects the input to planar coordinates if requested.
+    let projection = projection(cl_args)?;
+    let point_set_input = match &projection {
+        Some(projection) => project_point_set(&point_set_input, projection.as_ref())?,
+        None => point_set_input,
+    };
+    // Simplifies and densifies the cells and transforms them back to the input coordinates
+    // and rounds them if requested.
+    let unproject = |mut cell: BoundedVoronoiCell| {
+        if let Some(epsilon) = cl_args.simplify() {
+            cell.simplify(epsilon);
+        }
+        if let Some(max_segment_len) = cl_args.densify() {
+            cell.densify(max_segment_len);
+        }
+        let cell = match &projection {
+            Some(projection) => unproject_cell(cell, projection.as_ref())?,
+            None => cell,
+        };
+        Ok::<_, VoronoiError>(match cl_args.decimals() {
+            Some(decimals) => round_cell(cell, decimals),
+            None => cell,
+        })
+    };
+
+    // Transforms single coordinates back to the input coordinates and rounds them if requested.
+    let unproject_coordinate = |coordinate: [f64; 2]| {
+        let coordinate = match &projection {
+            Some(projection) => projection.inverse(coordinate)?,
+            None => coordinate,
+        };
+        Ok::<_, VoronoiError>(match cl_args.decimals() {
+            Some(decimals) => coordinate.map(|value| round_to_decimals(value, decimals)),
+            None => coordinate,
+        })
+    };
+
+    if cl_args.delaunay() {
+        // Creates the Delaunay triangulation and saves it together with the sites it indexes.
+        let triangles = compute_delaunay(&point_set_input)?;
+        let sites: Vec<[f64; 2]> = point_set_input
+            .sorted_point_set()
+            .into_iter()
+            .map(|point| unproject_coordinate([point.x(), point.y()]))
+            .collect::<Result<_, _>>()?;
+        let mut output = open_output(cl_args, point_set_file)?;
+        write_json(
+            &mut output,
+            &serde_json::json!({ "sites": sites, "triangles": triangles }),
+            cl_args.pretty(),
+        )?;
+        finish_output(output)?;
+    } else if cl_args.vertices() {
+        // Saves the Voronoi vertices, which are only clipped to the bound if the
+        // unbounded diagramm has not been requested.
+        let vertices: Vec<[f64; 2]> = voronoi_vertices(&point_set_input, !cl_args.unbounded())?
+            .into_iter()
+            .map(unproject_coordinate)
+            .collect::<Result<_, _>>()?;
+        let mut output = open_output(cl_args, point_set_file)?;
+        write_json(&mut output, &vertices, cl_args.pretty())?;
+        finish_output(output)?;
+    } else {
+        // Creats the Voronoi representation and saves it to the output file.
+        let options = voronoi_options(cl_args, point_set_file, hull_margin);
+        if cl_args.format() == OutputFormat::Json
+            && cl_args.split_output().is_none()
+            && cl_args.sort_by().is_none()
+            && !cl_args.stats()
+            && !cl_args.verify()
+            && !cl_args.with_metadata()
+            && !cl_args.echo_bound()
+            && !cl_args.coord_object()
+            && !cl_args.drop_degenerate()
+        {
+            // Streams the cells to the output, so that they are not kept in memory.
+            let min_area = degenerate_cell_area(&point_set_input, hull_margin)?;
+            let mut degenerate_cells = 0;
+            let mut cells = compute_voronoi_iter_with_options(&point_set_input, options)
+                .map(|cell| {
+                    if matches!(&cell, Ok(cell) if cell.is_degenerate(min_area)) {
+                        degenerate_cells += 1;
+                    }
+                    cell.and_then(unproject)
+                })
+                .peekable();
+            if let Some(Err(error)) = cells.peek() {
+                return Err(error.clone().into());
+            }
+            let mut output = open_output(cl_args, point_set_file)?;
+            if let Err(error) = write_json_cells(&mut output, cells, cl_args.pretty()) {
+                // Removes the partially written output file.
+                drop(output);
+                if !cl_args.writes_stdout(point_set_file) {
+                    std::fs::remove_file(cl_args.output_path(point_set_file))?;
+                }
+                return Err(error);
+            }
+            finish_output(output)?;
+            if degenerate_cells > 0 {
+                eprintln!("Warning: {} degenerate cells have been found.", degenerate_cells);
+            }
+            return Ok(());
+        }
+        let (voronoi, relaxation_report) = compute_voronoi_with_report(&point_set_input, options)?;
+        let min_area = degenerate_cell_area(&point_set_input, hull_margin)?;
+        let voronoi = if cl_args.drop_degenerate() {
+            let (voronoi, degenerate_report) = drop_degenerate_cells(voronoi, min_area);
+            if !degenerate_report.indices().is_empty() {
+                eprintln!(
+                    "Warning: {} degenerate cells have been removed.",
+                    degenerate_report.indices().len()
+                );
+            }
+            voronoi
+        } else {
+            let degenerate_report = find_degenerate_cells(&voronoi, min_area);
+            if !degenerate_report.indices().is_empty() {
+                eprintln!(
+                    "Warning: {} degenerate cells have been found.",
+                    degenerate_report.indices().len()
+                );
+            }
+            voronoi
+        };
+        // The cells are compared with the bound they have been clipped to.
+        let clipping_bound = || {
+            point_set_input
+                .bounding_multi_polygon()
+                .and_then(|bound| inset_bound(bound, cl_args.inset().unwrap_or_default()))
+        };
+        if cl_args.stats() {
+            let (_, rejects) = point_set_input.point_set_with_rejects();
+            eprintln!(
+                "Rejected points: {} (NaN: {}, infinite: {})",
+                rejects.len(),
+                rejects
+                    .iter()
+                    .filter(|reject| reject.reason() == RejectReason::NaN)
+                    .count(),
+                rejects
+                    .iter()
+                    .filter(|reject| reject.reason() == RejectReason::Infinite)
+                    .count()
+            );
+            // The convex hull is not centered around the points, so the cells cover it as a whole.
+            let coverage = match hull_margin {
+                Some(margin) => {
+                    let hull = point_set_input
+                        .convex_hull(margin)
+                        .ok_or(VoronoiError::DegenerateHull)?;
+                    let hull = inset_bound(
+                        geo::MultiPolygon::new(vec![hull]),
+                        cl_args.inset().unwrap_or_default(),
+                    )?;
+                    coverage(&voronoi, hull)
+                },
+                None => centered_bound_coverage(&voronoi, clipping_bound()?)?,
+            };
+            eprintln!(
+                "Total cell area: {}, bound area: {}, coverage ratio: {}",
+                coverage.total_cell_area(),
+                coverage.bound_area(),
+                coverage.ratio()
+            );
+        }
+        if cl_args.verify() {
+            let check = verify_tessellation(&voronoi, clipping_bound()?)?;
+            let tolerance = VERIFICATION_TOLERANCE * check.region_area();
+            if check.symmetric_difference_area() > tolerance || check.overlap_area() > tolerance {
+                eprintln!(
+                    "Warning: The cells deviate from the bound with a symmetric difference of {} \
+                     and an overlap of {}.",
+                    check.symmetric_difference_area(),
+                    check.overlap_area()
+                );
+            }
+        }
+        let voronoi = voronoi
+            .into_iter()
+            .map(unproject)
+            .collect::<Result<Vec<BoundedVoronoiCell>, VoronoiError>>()?;
+        if let Some(split_output) = cl_args.split_output() {
+            write_split_output(cl_args, point_set_file, split_output, &voronoi)?;
+            return Ok(());
+        }
+        if cl_args.format() == OutputFormat::Shapefile {
+            return write_shapefile(cl_args, point_set_file, &voronoi);
+        }
+        let mut output = open_output(cl_args, point_set_file)?;
+        match cl_args.format() {
+            OutputFormat::Json if cl_args.with_metadata() || cl_args.echo_bound() => {
+                let mut metadata = OutputMetadata::new(
+                    deduplication_report.kept(),
+                    deduplication_report.dropped().len(),
+                    relaxation_report.iterations(),
+                );
+                if let Some(bounding_box) = relaxation_report.bounding_box() {
+                    metadata = metadata.with_bounding_box(bounding_box);
+                }
+                let mut voronoi_output = VoronoiOutput::new(metadata, voronoi);
+                if cl_args.echo_bound() {
+                    // Echoes the hull or the validated bound in the input coordinates.
+                    let bound = match hull_margin {
+                        Some(margin) => geo::MultiPolygon::new(vec![point_set_input
+                            .convex_hull(margin)
+                            .ok_or(VoronoiError::DegenerateHull)?]),
+                        None => point_set_input.bounding_multi_polygon()?,
+                    };
+                    let bound = bound.try_map_coords(|coord| {
+                        unproject_coordinate([coord.x, coord.y]).map(|[x, y]| geo::Coord { x, y })
+                    })?;
+                    voronoi_output = voronoi_output.with_bound(bound);
+                }
+                if cl_args.coord_object() {
+                    write_json(
+                        &mut output,
+                        &voronoi_output.to_coordinate_objects()?,
+                        cl_args.pretty(),
+                    )?
+                } else {
+                    write_json(&mut output, &voronoi_output, cl_args.pretty())?
+                }
+            },
+            OutputFormat::Json if cl_args.coord_object() => {
+                write_json(&mut output, &to_coordinate_objects(&voronoi)?, cl_args.pretty())?
+            },
+            OutputFormat::Json => write_json(&mut output, &voronoi, cl_args.pretty())?,
+            OutputFormat::Geojson => {
+                write_json(&mut output, &to_geojson(&voronoi), cl_args.pretty())?
+            },
+            OutputFormat::Topojson => write_json(
+                &mut output,
+                &to_topojson(&voronoi, cl_args.snap_precision().unwrap_or(TOPOLOGY_EPSILON)),
+                cl_args.pretty(),
+            )?,
+            OutputFormat::Wkt => {
+                for (cell, site) in to_wkt(&voronoi).into_iter().zip(sites_to_wkt(&voronoi)) {
+                    writeln!(output, "{}\t{}", cell, site)?;
+                }
+            },
+            OutputFormat::Csv => to_csv(&voronoi, &mut output)?,
+            OutputFormat::Table => write!(output, "{}", to_table(&voronoi))?,
+            OutputFormat::Svg => write!(output, "{}", to_svg(&voronoi, SvgOptions::default()))?,
+            OutputFormat::Kml => write!(output, "{}", to_kml(&voronoi))?,
+            // Shapefiles consist of several files and have already been written.
+            OutputFormat::Shapefile => {},
+            OutputFormat::Adjacency => to_adjacency_csv(
+                &voronoi,
+                &mut output,
+                cl_args.boundary_length().then_some(SHARED_BOUNDARY_EPSILON),
+            )?,
+        }
+        finish_output(output)?;
+    }
 
-    // Creats the Voronoi representation and saves it to the output file.
-    let voronoi = compute_voronoi(point_set_input)?;
-    serde_json::to_writer(std::fs::File::create(output_path)?, &voronoi)?;
+    Ok(())
+}
+
+/// Returns the area below which a cell is considered degenerate,
+/// which is relative to the area of the bound or hull the cells are clipped to.
+///
+/// # Parameters
+///
+/// * `point_set` - the point set the cells are computed for
+/// * `hull_margin` - the margin of the convex hull if it is used as bound
+fn degenerate_cell_area(
+    point_set: &BoundedPointSet,
+    hull_margin: Option<f64>,
+) -> Result<f64, VoronoiError> {
+    let bound_area = match hull_margin {
+        Some(margin) => point_set
+            .convex_hull(margin)
+            .ok_or(VoronoiError::DegenerateHull)?
+            .unsigned_area(),
+        None => point_set.bounding_multi_polygon()?.unsigned_area(),
+    };
+    Ok(DEGENERATE_CELL_AREA_RATIO * bound_area)
+}
+
+/// Fails if sites lie outside the bounding rectangle of the bound centered around
+/// the point set, which is cheaper than checking each site against the bound
+/// centered around it.
+///
+/// # Parameters
+///
+/// * `point_set` - the point set to check
+fn check_sites_within_bound(point_set: &BoundedPointSet) -> Result<(), Box<dyn std::error::Error>> {
+    let outside = point_set.sites_outside_bound()?;
+    if !outside.is_empty() {
+        return Err(VoronoiError::SitesOutsideBound {
+            sites: outside.len(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Returns the options controlling the computation of the Voronoi cells.
+///
+/// # Parameters
+///
+/// * `cl_args` - the command line arguments
+/// * `point_set_file` - the point set file to process
+/// * `hull_margin` - the margin of the convex hull used as bound if any
+fn voronoi_options(
+    cl_args: &CommandLineArguments,
+    point_set_file: &Path,
+    hull_margin: Option<f64>,
+) -> VoronoiOptions {
+    let mut options = VoronoiOptions::default()
+        .include_area(cl_args.area())
+        .include_index(
+            cl_args.index()
+                || cl_args.split_output().is_some()
+                || cl_args.format() == OutputFormat::Shapefile,
+        )
+        .include_unbounded(cl_args.unbounded())
+        .close_rings(cl_args.close_rings())
+        .input_order(cl_args.input_order())
+        .lloyd_iterations(cl_args.lloyd())
+        .site_placement(cl_args.site_placement())
+        .snap_precision(cl_args.snap_precision().unwrap_or_default())
+        .inset(cl_args.inset().unwrap_or_default())
+        .merge_tolerance(cl_args.merge_tolerance().unwrap_or_default())
+        .show_timing(cl_args.timing())
+        .show_progress(
+            !cl_args.quiet()
+                && !cl_args.reads_stdin(point_set_file)
+                && !cl_args.writes_stdout(point_set_file),
+        );
+    if let Some(tolerance) = cl_args.lloyd_tolerance() {
+        options = options.lloyd_until(tolerance, cl_args.max_lloyd_iterations());
+    }
+    if let Some(seed) = cl_args.seed() {
+        options = options.seed(seed);
+    }
+    if let Some(max_sites) = cl_args.max_sites() {
+        options = options.max_sites(max_sites);
+    }
+    if let Some(threads) = cl_args.threads() {
+        options = options.threads(threads);
+    }
+    if let Some(sort_key) = cl_args.sort_by() {
+        options = options.sort_by(sort_key);
+    }
+    if let Some(margin) = hull_margin {
+        options = options.hull_bound(margin);
+    }
+    options
+}
+
+/// Computes one Voronoi diagramm per group of points sharing one bound
+/// and writes them as JSON array of cell arrays to the according output.
+///
+/// # Parameters
+///
+/// * `cl_args` - the command line arguments
+/// * `point_set_file` - the point set file to process
+/// * `input` - the reader of the point set file
+/// * `bound` - the bound replacing the bound of the point set file if any
+fn process_groups(
+    cl_args: &CommandLineArguments,
+    point_set_file: &Path,
+    input: Box<dyn Read>,
+    bound: Option<&BoundKind>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let group_input: MultiGroupInput = serde_json::from_reader(BufReader::new(input))?;
+    let group_input = match bound {
+        Some(bound) => {
+            if group_input.has_bound() {
+                eprintln!(
+                    "Warning: The bound of {} is replaced by the bound specified with --bound.",
+                    point_set_file.display()
+                );
+            }
+            group_input.with_bound(bound.clone())
+        },
+        None => group_input,
+    };
+    // The convex hull of each group is only used if no bound has been specified.
+    let hull_margin = cl_args.hull_bound().filter(|_| !group_input.has_bound());
+    if cl_args.hull_bound().is_some() && hull_margin.is_none() {
+        eprintln!(
+            "Warning: The bound of {} is used instead of the convex hull.",
+            point_set_file.display()
+        );
+    }
+    if cl_args.strict() && group_input.has_bound() {
+        for point_set in group_input.point_sets() {
+            check_sites_within_bound(&point_set)?;
+        }
+    }
+    if cl_args.check() {
+        // Only validates the input without writing any output.
+        for (group, point_set) in group_input.point_sets().iter().enumerate() {
+            let report = point_set.validate()?;
+            println!(
+                "{} (group {}): valid with {} unique points and {} duplicate points.",
+                point_set_file.display(),
+                group,
+                report.kept(),
+                report.dropped().len()
+            );
+        }
+        return Ok(());
+    }
+
+    let options = voronoi_options(cl_args, point_set_file, hull_margin);
+    let groups: Vec<Vec<BoundedVoronoiCell>> = compute_voronoi_groups(&group_input, options)?
+        .into_iter()
+        .map(|cells| {
+            cells
+                .into_iter()
+                .map(|mut cell| {
+                    if let Some(epsilon) = cl_args.simplify() {
+                        cell.simplify(epsilon);
+                    }
+                    if let Some(max_segment_len) = cl_args.densify() {
+                        cell.densify(max_segment_len);
+                    }
+                    match cl_args.decimals() {
+                        Some(decimals) => round_cell(cell, decimals),
+                        None => cell,
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    let mut output = open_output(cl_args, point_set_file)?;
+    write_json(&mut output, &groups, cl_args.pretty())?;
+    finish_output(output)?;
+    Ok(())
+}
+
+/// Rounds the coordinates of the cell to the specified number of decimal places
+/// and warns if this collapses the cell into a degenerate shape.
+///
+/// # Parameters
+///
+/// * `cell` - the cell to round
+/// * `decimals` - the number of decimal places to round to
+fn round_cell(cell: BoundedVoronoiCell, decimals: u32) -> BoundedVoronoiCell {
+    let was_degenerate = cell.is_degenerate(f64::MIN_POSITIVE);
+    let site = cell.site();
+    let rounded_cell = cell.with_rounded_coords(decimals);
+    if !was_degenerate && rounded_cell.is_degenerate(f64::MIN_POSITIVE) {
+        eprintln!(
+            "Warning: Rounding to {} decimals collapses the cell of site [{}, {}].",
+            decimals, site[0], site[1]
+        );
+    }
+    rounded_cell
+}
+
+/// The destination the result is written to, which is optionally compressed with gzip.
+enum OutputStream {
+    /// An uncompressed output.
+    Plain(Box<dyn Write>),
+    /// A gzip-compressed output.
+    Gzip(GzEncoder<Box<dyn Write>>),
+}
+
+impl Write for OutputStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            OutputStream::Plain(output) => output.write(buf),
+            OutputStream::Gzip(output) => output.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            OutputStream::Plain(output) => output.flush(),
+            OutputStream::Gzip(output) => output.flush(),
+        }
+    }
+}
+
+/// Opens the output for the point set file, which is only created once the result
+/// has been computed, so that no empty output files are left behind on errors.
+///
+/// # Parameters
+///
+/// * `cl_args` - the command line arguments
+/// * `point_set_file` - the point set file to process
+fn open_output(
+    cl_args: &CommandLineArguments,
+    point_set_file: &Path,
+) -> Result<BufWriter<OutputStream>, std::io::Error> {
+    let output: Box<dyn Write> = if cl_args.writes_stdout(point_set_file) {
+        Box::new(std::io::stdout().lock())
+    } else {
+        Box::new(std::fs::File::create(cl_args.output_path(point_set_file))?)
+    };
+    let output = if cl_args.writes_gzip(point_set_file) {
+        OutputStream::Gzip(GzEncoder::new(output, Compression::default()))
+    } else {
+        OutputStream::Plain(output)
+    };
+    Ok(BufWriter::new(output))
+}
+
+/// Writes each cell to its own file in the split output directory, which is named
+/// after the index of the site of the cell.
+///
+/// # Parameters
+///
+/// * `cl_args` - the command line arguments
+/// * `point_set_file` - the point set file to process
+/// * `split_output` - the directory the cells are written to
+/// * `voronoi` - the cells to write
+fn write_split_output(
+    cl_args: &CommandLineArguments,
+    point_set_file: &Path,
+    split_output: &Path,
+    voronoi: &[BoundedVoronoiCell],
+) -> Result<(), Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(split_output)?;
+    for cell in voronoi {
+        // The index is always included if the output is split.
+        let index = cell.index().ok_or("The index of a cell is missing.")?;
+        let path = cl_args
+            .split_output_path(point_set_file, index)
+            .ok_or("The split output directory is missing.")?;
+        let mut output = BufWriter::new(std::fs::File::create(path)?);
+        match cl_args.format() {
+            OutputFormat::Geojson => {
+                write_json(&mut output, &to_geojson(std::slice::from_ref(cell)), cl_args.pretty())?
+            },
+            _ => write_json(&mut output, cell, cl_args.pretty())?,
+        }
+        output.flush()?;
+    }
+    Ok(())
+}
+
+/// Writes the cells as shapefile to the `.shp`, `.shx` and `.dbf` files, which replace
+/// the extension of the output path.
+///
+/// # Parameters
+///
+/// * `cl_args` - the command line arguments
+/// * `point_set_file` - the point set file to process
+/// * `voronoi` - the cells to write
+#[cfg(feature = "shapefile")]
+fn write_shapefile(
+    cl_args: &CommandLineArguments,
+    point_set_file: &Path,
+    voronoi: &[BoundedVoronoiCell],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let output_path = cl_args.output_path(point_set_file);
+    let create = |extension: &str| {
+        std::fs::File::create(output_path.with_extension(extension)).map(BufWriter::new)
+    };
+    to_shapefile(voronoi, create("shp")?, create("shx")?, create("dbf")?)?;
+    Ok(())
+}
+
+/// Fails, as shapefiles can only be written with the `shapefile` feature.
+///
+/// # Parameters
+///
+/// * `_cl_args` - the command line arguments
+/// * `_point_set_file` - the point set file to process
+/// * `_voronoi` - the cells to write
+#[cfg(not(feature = "shapefile"))]
+fn write_shapefile(
+    _cl_args: &CommandLineArguments,
+    _point_set_file: &Path,
+    _voronoi: &[BoundedVoronoiCell],
+) -> Result<(), Box<dyn std::error::Error>> {
+    Err("Shapefiles can only be written if the `shapefile` feature is enabled.".into())
+}
+
+/// Flushes the output and writes the gzip trailer if the output is compressed.
+///
+/// # Parameters
+///
+/// * `output` - the output opened with [`open_output`]
+fn finish_output(output: BufWriter<OutputStream>) -> Result<(), std::io::Error> {
+    match output.into_inner().map_err(|error| error.into_error())? {
+        OutputStream::Plain(mut output) => output.flush(),
+        OutputStream::Gzip(output) => output.finish()?.flush(),
+    }
+}
+
+/// Writes the value as JSON, which is indented if requested.
+///
+/// # Parameters
+///
+/// * `writer` - the writer to write the JSON to
+/// * `value` - the value to serialize
+/// * `pretty` - if the JSON should be indented
+fn write_json<W: Write, T: Serialize>(
+    writer: W,
+    value: &T,
+    pretty: bool,
+) -> Result<(), serde_json::Error> {
+    if pretty {
+        serde_json::to_writer_pretty(writer, value)
+    } else {
+        serde_json::to_writer(writer, value)
+    }
+}
+
+/// Writes the cells as JSON array while they are computed, which is indented if requested.
+/// Fails on the first cell that cannot be computed.
+///
+/// # Parameters
+///
+/// * `writer` - the writer to write the JSON to
+/// * `cells` - the cells to serialize
+/// * `pretty` - if the JSON should be indented
+fn write_json_cells<W: Write, I: Iterator<Item = Result<BoundedVoronoiCell, VoronoiError>>>(
+    writer: W,
+    cells: I,
+    pretty: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if pretty {
+        write_json_sequence(&mut serde_json::Serializer::pretty(writer), cells)
+    } else {
+        write_json_sequence(&mut serde_json::Serializer::new(writer), cells)
+    }
+}
 
+/// Serializes the cells as sequence while they are computed.
+///
+/// # Parameters
+///
+/// * `serializer` - the serializer to write the sequence to
+/// * `cells` - the cells to serialize
+fn write_json_sequence<
+    S: Serializer<Error = serde_json::Error>,
+    I: Iterator<Item = Result<BoundedVoronoiCell, VoronoiError>>,
+>(
+    serializer: S,
+    cells: I,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut sequence = serializer.serialize_seq(None)?;
+    for cell in cells {
+        sequence.serialize_element(&cell?)?;
+    }
+    sequence.end()?;
     Ok(())
 }
 
 mod arguments;
-mod input;
-mod voronoi;
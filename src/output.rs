@@ -0,0 +1,1418 @@
+//! This module converts computed Voronoi cells to different output formats.
+
+use std::{borrow::Borrow, collections::HashMap, fmt::Write as FmtWrite, io::Write};
+
+use geo::MultiPolygon;
+use geojson::{Feature, FeatureCollection, Geometry, JsonObject, JsonValue, Value};
+use getset::{CopyGetters, Getters};
+use serde::{Deserialize, Serialize};
+use wkt::ToWkt;
+
+use crate::{
+    input::Bounds,
+    statistics::shared_boundaries,
+    voronoi::{BoundedVoronoiCell, CoordinateObjectCell},
+};
+
+/// Converts the Voronoi cells to a GeoJSON feature collection.
+/// Each cell is represented as polygon feature carrying its site as property
+/// or as multi polygon feature if the bound splits the cell into several pieces.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells to convert
+pub fn to_geojson(cells: &[BoundedVoronoiCell]) -> FeatureCollection {
+    FeatureCollection {
+        bbox: None,
+        features: cells.iter().map(cell_to_feature).collect(),
+        foreign_members: None,
+    }
+}
+
+/// A vertex snapped to the grid used for matching the TopoJSON arcs.
+type VertexKey = (i64, i64);
+
+/// Converts the Voronoi cells to a TopoJSON topology with the single object `cells`.
+/// Each cell is represented as polygon geometry carrying its site as property,
+/// whose holes are stored as additional rings, or as multi polygon geometry
+/// if the bound splits the cell into several pieces.
+/// Every edge of the cell polygons is stored once in the arc table, so that the
+/// shared edge of two adjacent cells is referenced by both cells instead of being
+/// duplicated. Vertices are matched after snapping them to a grid with the spacing
+/// of the positive epsilon to account for floating point errors of the clipping.
+/// Cells without a polygon are represented as null geometry.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells to convert
+/// * `epsilon` - the spacing of the grid the vertices are matched on
+pub fn to_topojson(cells: &[BoundedVoronoiCell], epsilon: f64) -> JsonValue {
+    let vertex_key = |point: [f64; 2]| {
+        ((point[0] / epsilon).round() as i64, (point[1] / epsilon).round() as i64)
+    };
+    let mut arcs: Vec<[[f64; 2]; 2]> = Vec::new();
+    let mut arc_indices: HashMap<(VertexKey, VertexKey), i64> = HashMap::new();
+    let mut geometries = Vec::with_capacity(cells.len());
+    for cell in cells {
+        let mut piece_arcs = Vec::new();
+        for rings in cell_pieces(cell) {
+            let mut polygon_arcs = Vec::new();
+            for ring in rings {
+                let mut ring_arcs = Vec::new();
+                for edge in ring.windows(2) {
+                    let (start, end) = (vertex_key(edge[0]), vertex_key(edge[1]));
+                    if start == end {
+                        continue;
+                    }
+                    // An edge traversed in reverse is referenced by the one's complement
+                    // of its index.
+                    let arc_index = if let Some(index) = arc_indices.get(&(end, start)) {
+                        !index
+                    } else {
+                        *arc_indices.entry((start, end)).or_insert_with(|| {
+                            arcs.push([edge[0], edge[1]]);
+                            arcs.len() as i64 - 1
+                        })
+                    };
+                    ring_arcs.push(arc_index);
+                }
+                if !ring_arcs.is_empty() {
+                    polygon_arcs.push(ring_arcs);
+                }
+            }
+            if !polygon_arcs.is_empty() {
+                piece_arcs.push(polygon_arcs);
+            }
+        }
+        let mut geometry = JsonObject::new();
+        match piece_arcs.len() {
+            0 => {
+                geometry.insert("type".to_string(), JsonValue::Null);
+            },
+            1 => {
+                geometry.insert("type".to_string(), JsonValue::from("Polygon"));
+                geometry.insert("arcs".to_string(), JsonValue::from(piece_arcs.swap_remove(0)));
+            },
+            _ => {
+                geometry.insert("type".to_string(), JsonValue::from("MultiPolygon"));
+                geometry.insert("arcs".to_string(), JsonValue::from(piece_arcs));
+            },
+        }
+        geometry.insert("properties".to_string(), JsonValue::Object(cell_properties(cell)));
+        geometries.push(JsonValue::Object(geometry));
+    }
+    serde_json::json!({
+        "type": "Topology",
+        "objects": {
+            "cells": {
+                "type": "GeometryCollection",
+                "geometries": geometries,
+            },
+        },
+        "arcs": arcs,
+    })
+}
+
+/// A coordinate with named axes, which is serialized as object `{"x": .., "y": ..}`
+/// instead of an array to avoid ambiguity about the axis order.
+#[derive(Clone, Copy, CopyGetters, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Coordinate {
+    /// The x-coordinate, e.g. the longitude.
+    #[getset(get_copy = "pub")]
+    x: f64,
+    /// The y-coordinate, e.g. the latitude.
+    #[getset(get_copy = "pub")]
+    y: f64,
+}
+
+impl From<[f64; 2]> for Coordinate {
+    fn from(coordinate: [f64; 2]) -> Self {
+        Self {
+            x: coordinate[0],
+            y: coordinate[1],
+        }
+    }
+}
+
+impl From<Coordinate> for [f64; 2] {
+    fn from(coordinate: Coordinate) -> Self {
+        [coordinate.x, coordinate.y]
+    }
+}
+
+/// Converts the Voronoi cells to JSON in the same structure as their default
+/// serialization, but with each coordinate represented as [`Coordinate`] object
+/// instead of an array.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells to convert
+pub fn to_coordinate_objects(cells: &[BoundedVoronoiCell]) -> Result<JsonValue, serde_json::Error> {
+    let cells: Vec<CoordinateObjectCell> = cells
+        .iter()
+        .map(BoundedVoronoiCell::coordinate_objects)
+        .collect();
+    serde_json::to_value(cells)
+}
+
+/// Converts the ring to [`Coordinate`] objects.
+///
+/// # Parameters
+///
+/// * `ring` - the ring to convert
+pub(crate) fn coordinate_ring(ring: &[[f64; 2]]) -> Vec<Coordinate> {
+    ring.iter().copied().map(Coordinate::from).collect()
+}
+
+/// The output serialized in the same structure as [`VoronoiOutput`], but with each
+/// coordinate of the cells and the bound represented as [`Coordinate`] object.
+#[derive(Serialize)]
+struct CoordinateObjectOutput<'a> {
+    /// The metadata about the generation of the cells.
+    metadata: &'a OutputMetadata,
+    /// The Voronoi cells.
+    cells: Vec<CoordinateObjectCell<'a>>,
+    /// The bounding regions of the input if they have been recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bound: Option<Vec<Vec<Vec<Coordinate>>>>,
+}
+
+/// The Voronoi cells together with metadata about how they have been generated.
+#[derive(Clone, Debug, Deserialize, Getters, Serialize)]
+pub struct VoronoiOutput {
+    /// The metadata about the generation of the cells.
+    #[getset(get = "pub")]
+    metadata: OutputMetadata,
+    /// The Voronoi cells.
+    #[getset(get = "pub")]
+    cells: Vec<BoundedVoronoiCell>,
+    /// The validated and reoriented bounding regions of the input if they have been
+    /// recorded, each as list of closed rings starting with the exterior ring.
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bound: Option<Vec<Vec<Vec<[f64; 2]>>>>,
+}
+
+impl VoronoiOutput {
+    /// Creates a new output from the metadata and the cells.
+    ///
+    /// # Parameters
+    ///
+    /// * `metadata` - the metadata about the generation of the cells
+    /// * `cells` - the Voronoi cells
+    pub fn new(metadata: OutputMetadata, cells: Vec<BoundedVoronoiCell>) -> Self {
+        Self {
+            metadata,
+            cells,
+            bound: None,
+        }
+    }
+
+    /// Consumes the output and returns it with the processed bound, e.g. as returned by
+    /// [`BoundedPointSet::bounding_multi_polygon`](crate::BoundedPointSet::bounding_multi_polygon).
+    ///
+    /// # Parameters
+    ///
+    /// * `bound` - the processed bounding regions of the input
+    pub fn with_bound<T: Borrow<MultiPolygon>>(mut self, bound: T) -> Self {
+        self.bound = Some(multi_polygon_rings(bound.borrow()));
+        self
+    }
+
+    /// Converts the output to JSON in the same structure as its default serialization,
+    /// but with each coordinate of the cells and the bound represented as
+    /// [`Coordinate`] object instead of an array.
+    pub fn to_coordinate_objects(&self) -> Result<JsonValue, serde_json::Error> {
+        serde_json::to_value(CoordinateObjectOutput {
+            metadata: &self.metadata,
+            cells: self
+                .cells
+                .iter()
+                .map(BoundedVoronoiCell::coordinate_objects)
+                .collect(),
+            bound: self.bound.as_ref().map(|bound| {
+                bound
+                    .iter()
+                    .map(|polygon| polygon.iter().map(|ring| coordinate_ring(ring)).collect())
+                    .collect()
+            }),
+        })
+    }
+}
+
+/// Returns the closed rings of each polygon of the multi polygon starting with the exterior ring.
+///
+/// # Parameters
+///
+/// * `multi_polygon` - the multi polygon to convert
+pub(crate) fn multi_polygon_rings(multi_polygon: &MultiPolygon) -> Vec<Vec<Vec<[f64; 2]>>> {
+    multi_polygon
+        .iter()
+        .map(|polygon| {
+            std::iter::once(polygon.exterior())
+                .chain(polygon.interiors())
+                .map(|ring| ring.coords().map(|coord| [coord.x, coord.y]).collect())
+                .collect()
+        })
+        .collect()
+}
+
+/// Metadata about the generation of the Voronoi cells for reproducibility.
+#[derive(Clone, CopyGetters, Debug, Deserialize, Getters, PartialEq, Serialize)]
+pub struct OutputMetadata {
+    /// The version of this crate that generated the cells.
+    #[getset(get = "pub")]
+    version: String,
+    /// The number of unique points.
+    #[getset(get_copy = "pub")]
+    site_count: usize,
+    /// The number of duplicate points that have been removed.
+    #[getset(get_copy = "pub")]
+    dropped_duplicates: usize,
+    /// The number of Lloyd relaxation iterations that have been run.
+    #[getset(get_copy = "pub")]
+    lloyd_iterations: usize,
+    /// The bounding box the unbounded diagramm has been built in if it has been recorded.
+    #[getset(get_copy = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bounding_box: Option<DiagrammBoundingBox>,
+}
+
+impl OutputMetadata {
+    /// Creates new metadata for the current crate version.
+    ///
+    /// # Parameters
+    ///
+    /// * `site_count` - the number of unique points
+    /// * `dropped_duplicates` - the number of duplicate points that have been removed
+    /// * `lloyd_iterations` - the number of Lloyd relaxation iterations that have been run
+    pub fn new(site_count: usize, dropped_duplicates: usize, lloyd_iterations: usize) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            site_count,
+            dropped_duplicates,
+            lloyd_iterations,
+            bounding_box: None,
+        }
+    }
+
+    /// Consumes the metadata and returns it with the bounding box the unbounded
+    /// diagramm has been built in as reported by
+    /// [`RelaxationReport::bounding_box`](crate::RelaxationReport::bounding_box).
+    ///
+    /// # Parameters
+    ///
+    /// * `bounding_box` - the bounding box of the unbounded diagramm
+    pub fn with_bounding_box<T: Borrow<Bounds>>(mut self, bounding_box: T) -> Self {
+        self.bounding_box = Some(DiagrammBoundingBox::from(bounding_box.borrow()));
+        self
+    }
+}
+
+/// The bounding box the unbounded Voronoi diagramm has been built in as passed to `voronoice`.
+#[derive(Clone, Copy, CopyGetters, Debug, Deserialize, PartialEq, Serialize)]
+pub struct DiagrammBoundingBox {
+    /// The center of the bounding box.
+    #[getset(get_copy = "pub")]
+    center: [f64; 2],
+    /// The width of the bounding box.
+    #[getset(get_copy = "pub")]
+    width: f64,
+    /// The height of the bounding box.
+    #[getset(get_copy = "pub")]
+    height: f64,
+}
+
+impl<T: Borrow<Bounds>> From<T> for DiagrammBoundingBox {
+    fn from(bounds: T) -> Self {
+        let bounds = bounds.borrow();
+        Self {
+            center: [bounds.centre_x(), bounds.centre_y()],
+            width: bounds.diff_x(),
+            height: bounds.diff_y(),
+        }
+    }
+}
+
+/// A row of the CSV output.
+#[derive(Serialize)]
+struct CsvRow {
+    /// The x-coordinate of the site.
+    site_x: f64,
+    /// The y-coordinate of the site.
+    site_y: f64,
+    /// The number of vertices of all pieces of the cell polygon.
+    vertex_count: usize,
+    /// The area of all pieces of the cell polygon.
+    area: f64,
+    /// The Polsby-Popper compactness of the cell polygon.
+    compactness: f64,
+}
+
+/// Writes the Voronoi cells as CSV with one row per cell
+/// and the columns `site_x`, `site_y`, `vertex_count`, `area` and `compactness`.
+/// Cells without vertices are written with a vertex count and area of 0
+/// and a compactness of NaN.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells to write
+/// * `writer` - the writer to write the CSV to
+pub fn to_csv<W: Write>(cells: &[BoundedVoronoiCell], writer: W) -> Result<(), csv::Error> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+    for cell in cells {
+        csv_writer.serialize(CsvRow {
+            site_x: cell.site()[0],
+            site_y: cell.site()[1],
+            vertex_count: cell.vertex_count(),
+            area: cell.area(),
+            compactness: cell.compactness(),
+        })?;
+    }
+    csv_writer.flush()?;
+    Ok(())
+}
+
+/// Converts the Voronoi cells to an aligned text table for quick inspection on the
+/// terminal with one row per cell and the columns `site_x`, `site_y`, `vertices`
+/// and `area`. Unlike [`to_csv`] the table is meant to be read, not parsed.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells to convert
+pub fn to_table(cells: &[BoundedVoronoiCell]) -> String {
+    let header = ["site_x", "site_y", "vertices", "area"].map(String::from);
+    let rows: Vec<[String; 4]> = std::iter::once(header)
+        .chain(cells.iter().map(|cell| {
+            [
+                cell.site()[0].to_string(),
+                cell.site()[1].to_string(),
+                cell.vertex_count().to_string(),
+                format!("{:.6}", cell.area()),
+            ]
+        }))
+        .collect();
+    let mut widths = [0; 4];
+    for row in &rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(value.chars().count());
+        }
+    }
+    let mut table = String::new();
+    for row in &rows {
+        let line = row
+            .iter()
+            .zip(widths)
+            .map(|(value, width)| format!("{:>width$}", value, width = width))
+            .collect::<Vec<String>>()
+            .join("  ");
+        table.push_str(&line);
+        table.push('\n');
+    }
+    table
+}
+
+/// Writes the adjacency of the Voronoi cells as CSV edge list with one row per pair
+/// of neighboring cells and the columns `i` and `j`, the indices of the cells with
+/// `i < j`. The rows are ordered by `i` and then by `j` and each pair is written once.
+/// If an epsilon is specified, the length of the shared boundary is added as column
+/// `length`, which is 0 for neighbors whose cells do not touch after the bound has
+/// been applied. See [`shared_boundaries`] for details.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells to write the adjacency of
+/// * `writer` - the writer to write the CSV to
+/// * `boundary_epsilon` - the maximum distance of overlapping edges if the length
+///   of the shared boundaries should be included
+pub fn to_adjacency_csv<W: Write>(
+    cells: &[BoundedVoronoiCell],
+    writer: W,
+    boundary_epsilon: Option<f64>,
+) -> Result<(), csv::Error> {
+    let lengths: HashMap<(usize, usize), f64> = boundary_epsilon
+        .map(|epsilon| {
+            shared_boundaries(cells, epsilon)
+                .into_iter()
+                .map(|(index, neighbor, length)| ((index, neighbor), length))
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut csv_writer = csv::Writer::from_writer(writer);
+    if boundary_epsilon.is_some() {
+        csv_writer.write_record(["i", "j", "length"])?;
+    } else {
+        csv_writer.write_record(["i", "j"])?;
+    }
+    for (index, cell) in cells.iter().enumerate() {
+        let mut neighbors: Vec<usize> = cell
+            .neighbors()
+            .iter()
+            .copied()
+            .filter(|&neighbor| neighbor > index && neighbor < cells.len())
+            .collect();
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        for neighbor in neighbors {
+            if boundary_epsilon.is_some() {
+                let length = lengths.get(&(index, neighbor)).copied().unwrap_or(0.0);
+                csv_writer.serialize((index, neighbor, length))?;
+            } else {
+                csv_writer.serialize((index, neighbor))?;
+            }
+        }
+    }
+    csv_writer.flush()?;
+    Ok(())
+}
+
+/// Converts the Voronoi cells to WKT `POLYGON` strings with closed rings or to
+/// `MULTIPOLYGON` strings if the bound splits a cell into several pieces.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells to convert
+pub fn to_wkt(cells: &[BoundedVoronoiCell]) -> Vec<String> {
+    cells
+        .iter()
+        .map(|cell| {
+            if cell.multi_polygon().0.len() > 1 {
+                cell.multi_polygon().wkt_string()
+            } else {
+                cell.as_polygon().wkt_string()
+            }
+        })
+        .collect()
+}
+
+/// Converts the sites of the Voronoi cells to WKT `POINT` strings.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells to convert the sites of
+pub fn sites_to_wkt(cells: &[BoundedVoronoiCell]) -> Vec<String> {
+    cells
+        .iter()
+        .map(|cell| geo::Point::new(cell.site()[0], cell.site()[1]).wkt_string())
+        .collect()
+}
+
+/// Converts the Voronoi cells to a KML document, e.g. for Google Earth.
+/// Each cell is written as `<Placemark>` with the cell polygon as `<Polygon>`, or as
+/// `<MultiGeometry>` of polygons if the bound splits the cell into several pieces, and
+/// the site as well as the label and index if present as `<ExtendedData>`.
+/// The label is used as name of the placemark if present.
+/// KML expects longitude and latitude, so the coordinates of the cells are written
+/// as they are and must already be geographic, e.g. by projecting them back.
+/// Cells without a polygon are written without geometry.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells to convert
+pub fn to_kml(cells: &[BoundedVoronoiCell]) -> String {
+    let mut kml = String::new();
+    // Writing to a string cannot fail.
+    let _ = write!(
+        kml,
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+         <kml xmlns=\"http://www.opengis.net/kml/2.2\"><Document>"
+    );
+    let kml_coordinates = |ring: &[[f64; 2]]| {
+        ring.iter()
+            .map(|point| format!("{},{}", point[0], point[1]))
+            .collect::<Vec<String>>()
+            .join(" ")
+    };
+    for cell in cells {
+        kml.push_str("<Placemark>");
+        if let Some(label) = cell.label() {
+            let _ = write!(kml, "<name>{}</name>", escape_xml(label));
+        }
+        kml.push_str("<ExtendedData>");
+        let mut data = vec![
+            ("site_x", cell.site()[0].to_string()),
+            ("site_y", cell.site()[1].to_string()),
+        ];
+        if let Some(label) = cell.label() {
+            data.push(("label", label.clone()));
+        }
+        if let Some(index) = cell.index() {
+            data.push(("index", index.to_string()));
+        }
+        for (name, value) in data {
+            let _ =
+                write!(kml, "<Data name=\"{}\"><value>{}</value></Data>", name, escape_xml(&value));
+        }
+        kml.push_str("</ExtendedData>");
+        let pieces: Vec<Vec<Vec<[f64; 2]>>> = cell_pieces(cell)
+            .into_iter()
+            .filter(|rings| rings[0].len() > 2)
+            .collect();
+        if pieces.len() > 1 {
+            kml.push_str("<MultiGeometry>");
+        }
+        for rings in &pieces {
+            let _ = write!(
+                kml,
+                "<Polygon><outerBoundaryIs><LinearRing><coordinates>{}</coordinates>\
+                 </LinearRing></outerBoundaryIs>",
+                kml_coordinates(&rings[0])
+            );
+            for hole in &rings[1..] {
+                let _ = write!(
+                    kml,
+                    "<innerBoundaryIs><LinearRing><coordinates>{}</coordinates>\
+                     </LinearRing></innerBoundaryIs>",
+                    kml_coordinates(hole)
+                );
+            }
+            kml.push_str("</Polygon>");
+        }
+        if pieces.len() > 1 {
+            kml.push_str("</MultiGeometry>");
+        }
+        kml.push_str("</Placemark>");
+    }
+    kml.push_str("</Document></kml>");
+    kml
+}
+
+/// The numeric fields of the DBF attribute table as name, length and decimal count.
+#[cfg(feature = "shapefile")]
+const DBF_FIELDS: [(&str, u8, u8); 3] = [("SITE_X", 24, 10), ("SITE_Y", 24, 10), ("INDEX", 10, 0)];
+
+/// Writes the Voronoi cells as ESRI shapefile with the [`shapefile`] crate, which consists
+/// of the geometry (`.shp`), the index of the geometry records (`.shx`) and the attribute
+/// table (`.dbf`).
+/// Each cell is written as polygon record with all its pieces and holes and the
+/// attributes `SITE_X`, `SITE_Y` and `INDEX`, which is the index of the site if it
+/// has been included with [`crate::VoronoiOptions::include_index`] and null otherwise.
+/// As required by the shapefile specification the rings are closed and the exterior
+/// rings are oriented clockwise, while the holes are oriented counter-clockwise.
+/// A polygon shapefile cannot be written with null shapes, so a cell without a polygon
+/// results in an error. This requires the `shapefile` feature.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells to write
+/// * `shp` - the writer to write the geometry to
+/// * `shx` - the writer to write the index of the geometry records to
+/// * `dbf` - the writer to write the attribute table to
+#[cfg(feature = "shapefile")]
+pub fn to_shapefile<W: Write + std::io::Seek>(
+    cells: &[BoundedVoronoiCell],
+    shp: W,
+    shx: W,
+    dbf: W,
+) -> Result<(), shapefile::Error> {
+    use shapefile::dbase::{FieldName, FieldValue, Record, TableWriterBuilder};
+
+    let mut table_builder = TableWriterBuilder::new();
+    for (name, length, decimals) in DBF_FIELDS {
+        // The names of the fields are valid constants.
+        let name = FieldName::try_from(name).expect("invalid field name");
+        table_builder = table_builder.add_numeric_field(name, length, decimals);
+    }
+    let mut writer = shapefile::Writer::new(
+        shapefile::ShapeWriter::with_shx(shp, shx),
+        table_builder.build_with_dest(dbf),
+    );
+    for cell in cells {
+        let polygon = shape_polygon(cell).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "The cell of site {:?} has no polygon and cannot be written as shape.",
+                    cell.site()
+                ),
+            )
+        })?;
+        let mut record = Record::default();
+        record.insert(DBF_FIELDS[0].0.to_string(), FieldValue::Numeric(Some(cell.site()[0])));
+        record.insert(DBF_FIELDS[1].0.to_string(), FieldValue::Numeric(Some(cell.site()[1])));
+        record.insert(
+            DBF_FIELDS[2].0.to_string(),
+            FieldValue::Numeric(cell.index().map(|index| index as f64)),
+        );
+        writer.write_shape_and_record(&polygon, &record)?;
+    }
+    Ok(())
+}
+
+/// Returns the polygon shape of the cell with all pieces and holes or [`None`]
+/// if the cell has no polygon.
+///
+/// # Parameters
+///
+/// * `cell` - the Voronoi cell to convert
+#[cfg(feature = "shapefile")]
+fn shape_polygon(cell: &BoundedVoronoiCell) -> Option<shapefile::Polygon> {
+    use geo::{orient::Direction, LineString, Orient};
+    use shapefile::{Point, PolygonRing};
+
+    let points = |ring: &LineString| -> Vec<Point> {
+        ring.coords()
+            .map(|coordinate| Point::new(coordinate.x, coordinate.y))
+            .collect()
+    };
+    let rings: Vec<PolygonRing<Point>> = cell
+        .multi_polygon()
+        .orient(Direction::Reversed)
+        .iter()
+        .filter(|piece| piece.exterior().0.len() > 3)
+        .flat_map(|piece| {
+            let holes = piece
+                .interiors()
+                .iter()
+                .map(|hole| PolygonRing::Inner(points(hole)));
+            std::iter::once(PolygonRing::Outer(points(piece.exterior()))).chain(holes)
+        })
+        .collect();
+    (!rings.is_empty()).then(|| shapefile::Polygon::with_rings(rings))
+}
+
+/// Replaces the characters with a special meaning in XML by their entities.
+///
+/// # Parameters
+///
+/// * `text` - the text to escape
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// The fill colours of the SVG cells.
+const SVG_PALETTE: [&str; 6] = [
+    "#8dd3c7", "#ffffb3", "#bebada", "#fb8072", "#80b1d3", "#fdb462",
+];
+
+/// Options controlling the SVG rendering of the Voronoi cells.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SvgOptions {
+    /// The width of the image.
+    width: f64,
+    /// The height of the image.
+    height: f64,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            width: 800.0,
+            height: 800.0,
+        }
+    }
+}
+
+impl SvgOptions {
+    /// Consumes the options and returns them with the specified image width.
+    ///
+    /// # Parameters
+    ///
+    /// * `width` - the width of the image
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Consumes the options and returns them with the specified image height.
+    ///
+    /// # Parameters
+    ///
+    /// * `height` - the height of the image
+    pub fn height(mut self, height: f64) -> Self {
+        self.height = height;
+        self
+    }
+}
+
+/// Renders the Voronoi cells as SVG image for visualization.
+/// Each piece of a cell is drawn as stroked `<polygon>` and its site as small `<circle>`.
+/// The view box is computed from the combined bounds of all cells and sites
+/// and the y-axis is flipped, so that the image is not upside down.
+/// The fill colours cycle through a palette, so that neighboring cells differ
+/// in colour where possible.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells to render
+/// * `options` - the options controlling the rendering
+pub fn to_svg(cells: &[BoundedVoronoiCell], options: SvgOptions) -> String {
+    let (mut min_x, mut max_x, mut min_y, mut max_y) = (f64::MAX, f64::MIN, f64::MAX, f64::MIN);
+    for cell in cells {
+        for [x, y] in cell
+            .cells()
+            .into_iter()
+            .flatten()
+            .chain(std::iter::once(cell.site()))
+        {
+            min_x = min_x.min(x);
+            max_x = max_x.max(x);
+            min_y = min_y.min(y);
+            max_y = max_y.max(y);
+        }
+    }
+    if min_x > max_x {
+        (min_x, max_x, min_y, max_y) = (0.0, 1.0, 0.0, 1.0);
+    }
+    let size = (max_x - min_x).max(max_y - min_y).max(f64::EPSILON);
+    let padding = size * 0.02;
+    let stroke_width = size * 0.002;
+    let site_radius = size * 0.005;
+
+    let mut svg = String::new();
+    // Writing to a string cannot fail.
+    let _ = write!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"{} {} {} {}\">",
+        options.width,
+        options.height,
+        min_x - padding,
+        flip_y(max_y) - padding,
+        max_x - min_x + 2.0 * padding,
+        max_y - min_y + 2.0 * padding
+    );
+    for (cell, colour) in cells.iter().zip(cell_colours(cells)) {
+        for piece in cell.cells() {
+            let points: Vec<String> = piece
+                .iter()
+                .map(|point| format!("{},{}", point[0], flip_y(point[1])))
+                .collect();
+            let _ = write!(
+                svg,
+                "<polygon points=\"{}\" fill=\"{}\" stroke=\"black\" stroke-width=\"{}\"/>",
+                points.join(" "),
+                SVG_PALETTE[colour],
+                stroke_width
+            );
+        }
+        let _ = write!(
+            svg,
+            "<circle cx=\"{}\" cy=\"{}\" r=\"{}\" fill=\"black\"/>",
+            cell.site()[0],
+            flip_y(cell.site()[1]),
+            site_radius
+        );
+    }
+    svg.push_str("</svg>");
+    svg
+}
+
+/// Mirrors the y-coordinate, as the y-axis of SVG images points downwards.
+///
+/// # Parameters
+///
+/// * `y` - the y-coordinate to mirror
+fn flip_y(y: f64) -> f64 {
+    // Subtracting from 0 avoids a negative zero in the output.
+    0.0 - y
+}
+
+/// Returns the palette index of each cell.
+/// Each cell gets the first colour that is not used by an already coloured neighbor
+/// or cycles through the palette if all colours are taken.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells to colour
+fn cell_colours(cells: &[BoundedVoronoiCell]) -> Vec<usize> {
+    let mut colours: Vec<Option<usize>> = vec![None; cells.len()];
+    for (index, cell) in cells.iter().enumerate() {
+        let taken: Vec<usize> = cell
+            .neighbors()
+            .iter()
+            .filter_map(|&neighbor| colours.get(neighbor).copied().flatten())
+            .collect();
+        colours[index] = Some(
+            (0..SVG_PALETTE.len())
+                .find(|colour| !taken.contains(colour))
+                .unwrap_or(index % SVG_PALETTE.len()),
+        );
+    }
+    colours.into_iter().flatten().collect()
+}
+
+/// Converts a single Voronoi cell to a GeoJSON polygon feature or to a multi polygon
+/// feature if the bound splits the cell into several pieces.
+///
+/// # Parameters
+///
+/// * `cell` - the Voronoi cell to convert
+fn cell_to_feature(cell: &BoundedVoronoiCell) -> Feature {
+    let mut pieces: Vec<Vec<Vec<Vec<f64>>>> = cell_pieces(cell)
+        .into_iter()
+        .map(|rings| {
+            rings
+                .into_iter()
+                .map(|ring| ring.into_iter().map(|point| point.to_vec()).collect())
+                .collect()
+        })
+        .collect();
+    let geometry = if pieces.len() > 1 {
+        Value::MultiPolygon(pieces)
+    } else {
+        Value::Polygon(pieces.swap_remove(0))
+    };
+    Feature {
+        bbox: None,
+        geometry: Some(Geometry::new(geometry)),
+        id: None,
+        properties: Some(cell_properties(cell)),
+        foreign_members: None,
+    }
+}
+
+/// Returns the properties of a single Voronoi cell, which are shared by the
+/// GeoJSON and TopoJSON output.
+/// The properties of the input point are included as well, but are replaced
+/// by the properties of the cell like `site` if their keys collide.
+///
+/// # Parameters
+///
+/// * `cell` - the Voronoi cell to convert
+fn cell_properties(cell: &BoundedVoronoiCell) -> JsonObject {
+    let mut properties = cell.properties().clone().unwrap_or_default();
+    properties.insert("site".to_string(), JsonValue::from(cell.site().to_vec()));
+    properties.insert("neighbors".to_string(), JsonValue::from(cell.neighbors().clone()));
+    if let Some(label) = cell.label() {
+        properties.insert("label".to_string(), JsonValue::from(label.clone()));
+    }
+    if let Some(index) = cell.index() {
+        properties.insert("index".to_string(), JsonValue::from(index));
+    }
+    if let Some(original_site) = cell.original_site() {
+        properties.insert("original_site".to_string(), JsonValue::from(original_site.to_vec()));
+    }
+    properties
+}
+
+/// Returns the closed rings of each piece of the cell polygon starting with the piece
+/// containing the site, where the exterior ring of each piece is followed by the
+/// closed rings of the holes that lie within it.
+///
+/// # Parameters
+///
+/// * `cell` - the Voronoi cell to return the pieces of
+fn cell_pieces(cell: &BoundedVoronoiCell) -> Vec<Vec<Vec<[f64; 2]>>> {
+    cell.multi_polygon()
+        .iter()
+        .map(|polygon| {
+            std::iter::once(polygon.exterior())
+                .chain(polygon.interiors())
+                .map(|ring| ring.coords().map(|coord| [coord.x, coord.y]).collect())
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::input::{BoundedPointSet, Point2D};
+
+    #[test]
+    fn test_cell_pieces_open() {
+        let cell = BoundedVoronoiCell::new([0.5, 0.5], vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]);
+        assert_eq!(
+            cell_pieces(&cell),
+            vec![vec![vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]]
+        );
+    }
+
+    #[test]
+    fn test_cell_pieces_closed() {
+        let ring = vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]];
+        let cell = BoundedVoronoiCell::new([0.5, 0.5], ring.clone());
+        assert_eq!(cell_pieces(&cell), vec![vec![ring]]);
+    }
+
+    #[test]
+    fn test_to_kml() {
+        let cells = vec![
+            BoundedVoronoiCell::new(
+                [0.25, 0.5],
+                vec![[0.0, 0.0], [0.5, 0.0], [0.5, 1.0], [0.0, 1.0]],
+            ),
+            serde_json::from_value(serde_json::json!({
+                "site": [0.75, 0.5],
+                "cell": [[0.5, 0.0], [1.0, 0.0], [1.0, 1.0], [0.5, 1.0]],
+                "label": "Fields <A & B>"
+            }))
+            .unwrap(),
+            BoundedVoronoiCell::new([2.0, 2.0], Vec::new()),
+        ];
+        let kml = to_kml(&cells);
+        assert!(kml.starts_with("<?xml"));
+        assert!(kml.ends_with("</Document></kml>"));
+        assert_eq!(kml.matches("<Placemark>").count(), 3);
+        assert_eq!(kml.matches("<Polygon>").count(), 2);
+        assert!(kml.contains("<coordinates>0,0 0.5,0 0.5,1 0,1 0,0</coordinates>"));
+        assert!(kml.contains("<Data name=\"site_x\"><value>0.75</value></Data>"));
+        assert!(kml.contains("<name>Fields &lt;A &amp; B&gt;</name>"));
+    }
+
+    #[test]
+    fn test_cell_pieces_empty() {
+        let cell = BoundedVoronoiCell::new([0.5, 0.5], Vec::new());
+        assert_eq!(cell_pieces(&cell), vec![vec![Vec::<[f64; 2]>::new()]]);
+    }
+
+    #[test]
+    fn test_voronoioutput_serialize() {
+        let cell = BoundedVoronoiCell::new([0.0, 0.0], vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]);
+        let output = VoronoiOutput::new(OutputMetadata::new(1, 2, 3), vec![cell]);
+        let serialized = serde_json::to_value(&output).unwrap();
+        assert_eq!(
+            serialized["metadata"],
+            serde_json::json!({
+                "version": env!("CARGO_PKG_VERSION"),
+                "site_count": 1,
+                "dropped_duplicates": 2,
+                "lloyd_iterations": 3
+            })
+        );
+        assert_eq!(serialized["cells"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_voronoioutput_with_bound() {
+        // The clockwise and unclosed bound is closed and reoriented counter-clockwise.
+        let bps = BoundedPointSet::new(
+            vec![[0.5, 0.5]],
+            vec![[0.0, 0.0], [0.0, 2.0], [2.0, 2.0], [2.0, 0.0]],
+        )
+        .with_holes(vec![vec![[0.5, 0.5], [1.0, 0.5], [1.0, 1.0]]]);
+        let bound = bps.bounding_multi_polygon().unwrap();
+        let output =
+            VoronoiOutput::new(OutputMetadata::new(1, 0, 0), Vec::new()).with_bound(&bound);
+        let serialized = serde_json::to_value(&output).unwrap();
+        assert_eq!(
+            serialized["bound"],
+            serde_json::json!([[
+                [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0], [0.0, 0.0]],
+                [[0.5, 0.5], [1.0, 1.0], [1.0, 0.5], [0.5, 0.5]]
+            ]])
+        );
+        assert_eq!(output.bound().as_ref().unwrap(), &multi_polygon_rings(&bound));
+        assert_eq!(
+            output.to_coordinate_objects().unwrap()["bound"][0][1][0],
+            serde_json::json!({"x": 0.5, "y": 0.5})
+        );
+        let without_bound = VoronoiOutput::new(OutputMetadata::new(1, 0, 0), Vec::new());
+        assert!(serde_json::to_value(without_bound)
+            .unwrap()
+            .get("bound")
+            .is_none());
+    }
+
+    #[test]
+    fn test_outputmetadata_with_bounding_box() {
+        let point_set: HashSet<Point2D> = Point2D::from_coordinates([[-1.0, 0.0], [3.0, 1.0]])
+            .into_iter()
+            .collect();
+        let bounds = Bounds::from_point_set(point_set).unwrap();
+        let metadata = OutputMetadata::new(2, 0, 0).with_bounding_box(bounds);
+        assert_eq!(
+            serde_json::to_value(metadata).unwrap()["bounding_box"],
+            serde_json::json!({ "center": [1.0, 0.5], "width": 4.0, "height": 1.0 })
+        );
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let cells = vec![
+            BoundedVoronoiCell::new(
+                [0.5, 0.5],
+                vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.0, 0.0]],
+            ),
+            BoundedVoronoiCell::new([1.5, -0.5], vec![]),
+        ];
+        let mut output = Vec::new();
+        to_csv(&cells, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            format!(
+                "site_x,site_y,vertex_count,area,compactness\n0.5,0.5,4,1.0,{}\n1.5,-0.5,0,0.0,NaN\n",
+                std::f64::consts::FRAC_PI_4
+            )
+        );
+    }
+
+    #[test]
+    fn test_to_table() {
+        let cells = vec![
+            BoundedVoronoiCell::new(
+                [0.5, 0.5],
+                vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+            ),
+            BoundedVoronoiCell::new([-12.25, 0.5], vec![]),
+        ];
+        let table = to_table(&cells);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "site_x  site_y  vertices      area",
+                "   0.5     0.5         4  1.000000",
+                "-12.25     0.5         0  0.000000",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_adjacency_csv() {
+        // The sites are not cocircular, so the triangulation is unique and only the
+        // sites [1, -1.5] and [1, 1.5] on opposite sides of the diagonal are not adjacent.
+        // The cells are ordered by their sites:
+        // 0: [0, 0], 1: [1, -1.5], 2: [1, 1.5], 3: [2, 0].
+        let bps = crate::input::BoundedPointSet::with_bbox(
+            vec![[0.0, 0.0], [2.0, 0.0], [1.0, 1.5], [1.0, -1.5]],
+            [-5.0, -5.0, 5.0, 5.0],
+        );
+        let cells = crate::voronoi::compute_voronoi(&bps).unwrap();
+        let mut output = Vec::new();
+        to_adjacency_csv(&cells, &mut output, None).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "i,j\n0,1\n0,2\n0,3\n1,3\n2,3\n");
+        let mut output = Vec::new();
+        to_adjacency_csv(&cells, &mut output, Some(1e-9)).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let rows: Vec<(&str, f64)> = output
+            .lines()
+            .skip(1)
+            .map(|row| {
+                let (pair, length) = row.rsplit_once(',').unwrap();
+                (pair, length.parse().unwrap())
+            })
+            .collect();
+        assert_eq!(output.lines().next(), Some("i,j,length"));
+        let pairs: Vec<&str> = rows.iter().map(|&(pair, _)| pair).collect();
+        assert_eq!(pairs, vec!["0,1", "0,2", "0,3", "1,3", "2,3"]);
+        // The edge between the sites on the diagonal connects the circumcentres
+        // at [1, -5 / 12] and [1, 5 / 12], while the other edges reach the bound.
+        assert!((rows[2].1 - 5.0 / 6.0).abs() < 1e-9);
+        assert!(rows.iter().all(|&(_, length)| length > 0.0));
+    }
+
+    #[test]
+    fn test_to_wkt() {
+        let cells = vec![
+            BoundedVoronoiCell::new([0.5, 0.5], vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]),
+            BoundedVoronoiCell::new(
+                [1.5, 0.5],
+                vec![[1.0, 0.0], [2.0, 0.0], [2.0, 1.0], [1.0, 0.0]],
+            ),
+        ];
+        assert_eq!(
+            to_wkt(&cells),
+            vec![
+                "POLYGON((0 0,1 0,1 1,0 0))".to_string(),
+                "POLYGON((1 0,2 0,2 1,1 0))".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sites_to_wkt() {
+        let cells = vec![
+            BoundedVoronoiCell::new([0.5, 0.5], vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]),
+            BoundedVoronoiCell::new([1.5, -0.5], vec![[1.0, 0.0], [2.0, 0.0], [2.0, 1.0]]),
+        ];
+        assert_eq!(
+            sites_to_wkt(&cells),
+            vec!["POINT(0.5 0.5)".to_string(), "POINT(1.5 -0.5)".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_to_geojson() {
+        let cells = vec![
+            BoundedVoronoiCell::new([0.5, 0.5], vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]),
+            BoundedVoronoiCell::new(
+                [1.5, 0.5],
+                vec![[1.0, 0.0], [2.0, 0.0], [2.0, 1.0], [1.0, 0.0]],
+            ),
+        ];
+        let collection = to_geojson(&cells);
+        assert_eq!(collection.features.len(), cells.len());
+        for (feature, cell) in collection.features.iter().zip(cells.iter()) {
+            let site = feature.property("site").unwrap().as_array().unwrap();
+            assert_eq!(site[0].as_f64().unwrap(), cell.site()[0]);
+            assert_eq!(site[1].as_f64().unwrap(), cell.site()[1]);
+            match &feature.geometry.as_ref().unwrap().value {
+                Value::Polygon(rings) => {
+                    assert_eq!(rings.len(), 1);
+                    assert_eq!(rings[0].first(), rings[0].last());
+                    assert_eq!(rings[0][0], cell.cell()[0].to_vec());
+                },
+                _ => panic!("The geometry must be a polygon."),
+            }
+        }
+    }
+
+    #[test]
+    fn test_cell_coordinate_arrays() {
+        let cell = BoundedVoronoiCell::new([0.5, 0.5], vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]);
+        let value = serde_json::to_value(&cell).unwrap();
+        assert_eq!(value["site"], serde_json::json!([0.5, 0.5]));
+        assert_eq!(value["cell"], serde_json::json!([[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]));
+    }
+
+    #[test]
+    fn test_to_coordinate_objects() {
+        let cell = BoundedVoronoiCell::new([0.5, 0.5], vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]);
+        let value = to_coordinate_objects(&[cell]).unwrap();
+        assert_eq!(value[0]["site"], serde_json::json!({"x": 0.5, "y": 0.5}));
+        assert_eq!(
+            value[0]["cell"],
+            serde_json::json!([{"x": 0.0, "y": 0.0}, {"x": 1.0, "y": 0.0}, {"x": 1.0, "y": 1.0}])
+        );
+        let coordinate: Coordinate = serde_json::from_value(value[0]["site"].clone()).unwrap();
+        assert_eq!(<[f64; 2]>::from(coordinate), [0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_to_coordinate_objects_holes() {
+        let bps = crate::input::BoundedPointSet::new(
+            vec![[-2.0, 0.0], [2.0, 0.0]],
+            vec![[-4.0, -4.0], [4.0, -4.0], [4.0, 4.0], [-4.0, 4.0]],
+        )
+        .with_holes(vec![vec![[0.5, 0.5], [1.5, 0.5], [1.5, 1.5], [0.5, 1.5]]]);
+        let cells = crate::voronoi::compute_voronoi(&bps).unwrap();
+        let value = to_coordinate_objects(&cells).unwrap();
+        for cell in value.as_array().unwrap() {
+            assert!(cell["holes"][0][0]["x"].is_f64());
+            assert!(cell["cell"][0]["y"].is_f64());
+        }
+    }
+
+    /// Returns a cell, which the bound has split into a square and a triangle.
+    fn split_cell() -> BoundedVoronoiCell {
+        serde_json::from_value(serde_json::json!({
+            "site": [0.5, 0.5],
+            "cell": [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+            "cells": [
+                [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+                [[2.0, 0.0], [3.0, 0.0], [2.5, 1.0]]
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_split_cell_pieces() {
+        let cells = [split_cell()];
+        let geojson = to_geojson(&cells);
+        match &geojson.features[0].geometry.as_ref().unwrap().value {
+            Value::MultiPolygon(pieces) => {
+                assert_eq!(pieces.len(), 2);
+                assert_eq!(pieces[1][0].len(), 4);
+                assert_eq!(pieces[1][0].first(), pieces[1][0].last());
+            },
+            _ => panic!("The geometry must be a multi polygon."),
+        }
+        assert_eq!(
+            to_wkt(&cells),
+            vec!["MULTIPOLYGON(((0 0,1 0,1 1,0 1,0 0)),((2 0,3 0,2.5 1,2 0)))".to_string()]
+        );
+        let mut output = Vec::new();
+        to_csv(&cells, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.lines().nth(1).unwrap().starts_with("0.5,0.5,7,1.5,"));
+        let kml = to_kml(&cells);
+        assert_eq!(kml.matches("<MultiGeometry><Polygon>").count(), 1);
+        assert_eq!(kml.matches("<Polygon>").count(), 2);
+        assert!(kml.contains("<coordinates>2,0 3,0 2.5,1 2,0</coordinates>"));
+        assert!(kml.contains("</Polygon></MultiGeometry></Placemark>"));
+        let topojson = to_topojson(&cells, 1e-9);
+        let geometry = &topojson["objects"]["cells"]["geometries"][0];
+        assert_eq!(geometry["type"], "MultiPolygon");
+        assert_eq!(geometry["arcs"].as_array().unwrap().len(), 2);
+        assert_eq!(geometry["arcs"][1][0].as_array().unwrap().len(), 3);
+        assert_eq!(topojson["arcs"].as_array().unwrap().len(), 7);
+        let svg = to_svg(&cells, SvgOptions::default());
+        assert_eq!(svg.matches("<polygon").count(), 2);
+        assert!(svg.contains("points=\"2,0 3,0 2.5,-1\""));
+    }
+
+    /// Returns four unit square cells around the origin.
+    fn symmetric_cells() -> Vec<BoundedVoronoiCell> {
+        [[-1.0, -1.0], [0.0, -1.0], [0.0, 0.0], [-1.0, 0.0]]
+            .into_iter()
+            .map(|[x, y]| {
+                BoundedVoronoiCell::new(
+                    [x + 0.5, y + 0.5],
+                    vec![
+                        [x, y],
+                        [x + 1.0, y],
+                        [x + 1.0, y + 1.0],
+                        [x, y + 1.0],
+                        [x, y],
+                    ],
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the arc references of the polygon geometries in the TopoJSON topology.
+    fn topojson_arc_references(topology: &JsonValue) -> Vec<Vec<i64>> {
+        topology["objects"]["cells"]["geometries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|geometry| {
+                assert_eq!(geometry["type"], "Polygon");
+                geometry["arcs"][0]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|arc| arc.as_i64().unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_to_geojson_point_properties() {
+        let json = r#"{
+            "points": [
+                {"coord": [-2.0, 0.0], "properties": {"name": "west", "value": 1, "site": "ignored"}},
+                [2.0, 0.0]
+            ],
+            "bound": [[-4.0, -4.0], [4.0, -4.0], [4.0, 4.0], [-4.0, 4.0]]
+        }"#;
+        let bps: crate::input::BoundedPointSet = serde_json::from_str(json).unwrap();
+        let cells = crate::voronoi::compute_voronoi(&bps).unwrap();
+        let geojson = to_geojson(&cells);
+        let west = &geojson.features[0];
+        assert_eq!(west.property("name"), Some(&JsonValue::from("west")));
+        assert_eq!(west.property("value"), Some(&JsonValue::from(1)));
+        // The properties of the cell take precedence over colliding point properties.
+        assert_eq!(west.property("site"), Some(&serde_json::json!([-2.0, 0.0])));
+        let east = &geojson.features[1];
+        assert!(east.property("name").is_none());
+        assert_eq!(east.properties.as_ref().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_to_geojson_holes() {
+        let bps = crate::input::BoundedPointSet::new(
+            vec![[-2.0, 0.0], [2.0, 0.0]],
+            vec![[-4.0, -4.0], [4.0, -4.0], [4.0, 4.0], [-4.0, 4.0]],
+        )
+        .with_holes(vec![vec![[0.5, 0.5], [1.5, 0.5], [1.5, 1.5], [0.5, 1.5]]]);
+        let cells = crate::voronoi::compute_voronoi(&bps).unwrap();
+        let geojson = to_geojson(&cells);
+        for feature in &geojson.features {
+            match &feature.geometry.as_ref().unwrap().value {
+                Value::Polygon(rings) => {
+                    assert_eq!(rings.len(), 2);
+                    assert_eq!(rings[1].len(), 5);
+                    assert_eq!(rings[1].first(), rings[1].last());
+                },
+                _ => panic!("Expected a polygon."),
+            }
+        }
+        let topojson = to_topojson(&cells, 1e-9);
+        for geometry in topojson["objects"]["cells"]["geometries"]
+            .as_array()
+            .unwrap()
+        {
+            let rings = geometry["arcs"].as_array().unwrap();
+            assert_eq!(rings.len(), 2);
+            assert_eq!(rings[1].as_array().unwrap().len(), 4);
+        }
+        assert_eq!(to_wkt(&cells)[0].matches('(').count(), 3);
+    }
+
+    #[test]
+    fn test_to_topojson_shared_arcs() {
+        let cells = symmetric_cells();
+        let topology = to_topojson(&cells, 1e-9);
+        assert_eq!(topology["type"], "Topology");
+        // 16 edges of which 4 are shared by two cells.
+        assert_eq!(topology["arcs"].as_array().unwrap().len(), 12);
+        let references = topojson_arc_references(&topology);
+        assert_eq!(references.len(), 4);
+        assert!(references.iter().all(|ring| ring.len() == 4));
+        let mut counts = [0; 12];
+        for &reference in references.iter().flatten() {
+            // Each arc is traversed forwards by one cell and in reverse by its neighbor.
+            let index = if reference < 0 { !reference } else { reference };
+            counts[index as usize] += 1;
+        }
+        assert_eq!(counts.iter().filter(|&&count| count == 2).count(), 4);
+        assert_eq!(counts.iter().filter(|&&count| count == 1).count(), 8);
+        assert_eq!(
+            references
+                .iter()
+                .flatten()
+                .filter(|&&reference| reference < 0)
+                .count(),
+            4
+        );
+        assert_eq!(
+            topology["objects"]["cells"]["geometries"][2]["properties"]["site"],
+            serde_json::json!([0.5, 0.5])
+        );
+    }
+
+    #[test]
+    fn test_to_topojson_epsilon() {
+        let mut cells = symmetric_cells();
+        cells[2] = BoundedVoronoiCell::new(
+            [0.5, 0.5],
+            vec![
+                [1e-12, 0.0],
+                [1.0, 0.0],
+                [1.0, 1.0],
+                [0.0, 1.0],
+                [1e-12, 0.0],
+            ],
+        );
+        assert_eq!(to_topojson(&cells, 1e-9)["arcs"].as_array().unwrap().len(), 12);
+        assert_eq!(to_topojson(&cells, 1e-15)["arcs"].as_array().unwrap().len(), 14);
+        let empty = to_topojson(&[BoundedVoronoiCell::new([0.0, 0.0], Vec::new())], 1e-9);
+        assert!(empty["objects"]["cells"]["geometries"][0]["type"].is_null());
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let cells = vec![
+            BoundedVoronoiCell::new([0.5, 0.5], vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]]),
+            BoundedVoronoiCell::new(
+                [1.5, 0.5],
+                vec![[1.0, 0.0], [2.0, 0.0], [2.0, 1.0], [1.0, 0.0]],
+            ),
+        ];
+        let svg = to_svg(&cells, SvgOptions::default().width(200.0).height(100.0));
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+        assert!(svg.contains("width=\"200\" height=\"100\""));
+        assert_eq!(svg.matches("<polygon").count(), cells.len());
+        assert_eq!(svg.matches("<circle").count(), cells.len());
+    }
+
+    #[test]
+    fn test_cell_colours_neighbors() {
+        let point_set = (0..4)
+            .flat_map(|i| (0..4).map(move |j| [i as f64 + 0.1 * j as f64, j as f64]))
+            .collect();
+        let bps = crate::BoundedPointSet::new(
+            point_set,
+            vec![[-2.0, -2.0], [2.0, -2.0], [2.0, 2.0], [-2.0, 2.0]],
+        );
+        let cells = crate::compute_voronoi(&bps).unwrap();
+        let colours = cell_colours(&cells);
+        assert_eq!(colours.len(), cells.len());
+        for (cell, colour) in cells.iter().zip(colours.iter()) {
+            for &neighbor in cell.neighbors() {
+                assert_ne!(colours[neighbor], *colour);
+            }
+        }
+    }
+}
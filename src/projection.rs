@@ -0,0 +1,181 @@
+//! This module projects geographic coordinates to a planar coordinate reference system
+//! and back, so that the Voronoi diagramm is computed with planar distances.
+
+use crate::{error::VoronoiError, input::BoundedPointSet, voronoi::BoundedVoronoiCell};
+
+/// The coordinate reference system of the input coordinates
+/// as longitude / latitude in degrees.
+#[cfg(feature = "proj")]
+const GEOGRAPHIC_CRS: &str = "EPSG:4326";
+
+/// A transformation between the input coordinates and planar coordinates.
+pub trait CoordinateTransform {
+    /// Transforms an input coordinate to planar coordinates.
+    ///
+    /// # Parameters
+    ///
+    /// * `coordinate` - the input coordinate
+    fn forward(&self, coordinate: [f64; 2]) -> Result<[f64; 2], VoronoiError>;
+
+    /// Transforms a planar coordinate back to input coordinates.
+    ///
+    /// # Parameters
+    ///
+    /// * `coordinate` - the planar coordinate
+    fn inverse(&self, coordinate: [f64; 2]) -> Result<[f64; 2], VoronoiError>;
+}
+
+/// A projection of longitude / latitude coordinates (EPSG:4326)
+/// to a planar coordinate reference system.
+#[cfg(feature = "proj")]
+pub struct Projection {
+    /// The transformation to the planar coordinate reference system.
+    forward: proj::Proj,
+    /// The transformation back to longitude / latitude.
+    inverse: proj::Proj,
+}
+
+#[cfg(feature = "proj")]
+impl Projection {
+    /// Creates a new projection from longitude / latitude to the specified
+    /// planar coordinate reference system.
+    ///
+    /// # Parameters
+    ///
+    /// * `definition` - the PROJ string or identifier of the planar coordinate reference system
+    pub fn new(definition: &str) -> Result<Self, VoronoiError> {
+        let invalid_projection = |_| VoronoiError::InvalidProjection {
+            definition: definition.to_string(),
+        };
+        Ok(Self {
+            forward: proj::Proj::new_known_crs(GEOGRAPHIC_CRS, definition, None)
+                .map_err(invalid_projection)?,
+            inverse: proj::Proj::new_known_crs(definition, GEOGRAPHIC_CRS, None)
+                .map_err(invalid_projection)?,
+        })
+    }
+}
+
+#[cfg(feature = "proj")]
+impl CoordinateTransform for Projection {
+    fn forward(&self, coordinate: [f64; 2]) -> Result<[f64; 2], VoronoiError> {
+        self.forward
+            .convert((coordinate[0], coordinate[1]))
+            .map(|(x, y)| [x, y])
+            .map_err(|_| VoronoiError::ProjectionFailed { coordinate })
+    }
+
+    fn inverse(&self, coordinate: [f64; 2]) -> Result<[f64; 2], VoronoiError> {
+        self.inverse
+            .convert((coordinate[0], coordinate[1]))
+            .map(|(x, y)| [x, y])
+            .map_err(|_| VoronoiError::ProjectionFailed { coordinate })
+    }
+}
+
+/// Returns the bounded point set with the points and the bound transformed
+/// to planar coordinates.
+///
+/// # Parameters
+///
+/// * `bounded_point_set` - the bounded point set in input coordinates
+/// * `transform` - the transformation to planar coordinates
+pub fn project_point_set<T: CoordinateTransform + ?Sized>(
+    bounded_point_set: &BoundedPointSet,
+    transform: &T,
+) -> Result<BoundedPointSet, VoronoiError> {
+    bounded_point_set.try_map_coords(|coordinate| transform.forward(coordinate))
+}
+
+/// Returns the cell with its coordinates transformed back from planar coordinates
+/// to input coordinates.
+/// A stored area still refers to the planar coordinates.
+///
+/// # Parameters
+///
+/// * `cell` - the cell in planar coordinates
+/// * `transform` - the transformation to planar coordinates
+pub fn unproject_cell<T: CoordinateTransform + ?Sized>(
+    cell: BoundedVoronoiCell,
+    transform: &T,
+) -> Result<BoundedVoronoiCell, VoronoiError> {
+    cell.try_map_coords(|coordinate| transform.inverse(coordinate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::voronoi::compute_voronoi;
+
+    /// A transformation that keeps the coordinates unchanged.
+    struct IdentityTransform;
+
+    impl CoordinateTransform for IdentityTransform {
+        fn forward(&self, coordinate: [f64; 2]) -> Result<[f64; 2], VoronoiError> {
+            Ok(coordinate)
+        }
+
+        fn inverse(&self, coordinate: [f64; 2]) -> Result<[f64; 2], VoronoiError> {
+            Ok(coordinate)
+        }
+    }
+
+    /// A transformation that stretches the x-axis by a factor of 2.
+    struct StretchTransform;
+
+    impl CoordinateTransform for StretchTransform {
+        fn forward(&self, coordinate: [f64; 2]) -> Result<[f64; 2], VoronoiError> {
+            Ok([coordinate[0] * 2.0, coordinate[1]])
+        }
+
+        fn inverse(&self, coordinate: [f64; 2]) -> Result<[f64; 2], VoronoiError> {
+            Ok([coordinate[0] / 2.0, coordinate[1]])
+        }
+    }
+
+    /// Returns four points and an invalid point within a square bounding rectangle.
+    fn bounded_point_set() -> BoundedPointSet {
+        BoundedPointSet::with_bbox(
+            vec![
+                [0.0, 0.0],
+                [1.0, 0.0],
+                [0.0, 1.0],
+                [1.0, 1.0],
+                [f64::NAN, 0.0],
+            ],
+            [-1.0, -1.0, 1.0, 1.0],
+        )
+    }
+
+    #[test]
+    fn test_project_point_set_identity_round_trip() {
+        let bps = bounded_point_set();
+        let projected = project_point_set(&bps, &IdentityTransform).unwrap();
+        assert!(projected.bbox().is_none());
+        assert_eq!(projected.bounding_polygon().unwrap(), bps.bounding_polygon().unwrap());
+        let cells: Vec<BoundedVoronoiCell> = compute_voronoi(&projected)
+            .unwrap()
+            .into_iter()
+            .map(|cell| unproject_cell(cell, &IdentityTransform).unwrap())
+            .collect();
+        let expected_cells = compute_voronoi(&bps).unwrap();
+        assert_eq!(cells.len(), expected_cells.len());
+        for (cell, expected_cell) in cells.iter().zip(&expected_cells) {
+            assert_eq!(cell.site(), expected_cell.site());
+            assert_eq!(cell.cell(), expected_cell.cell());
+        }
+    }
+
+    #[test]
+    fn test_project_point_set_stretch() {
+        let bps = bounded_point_set();
+        let projected = project_point_set(&bps, &StretchTransform).unwrap();
+        assert_eq!(projected.point_set().len(), 4);
+        let cells = compute_voronoi(&projected).unwrap();
+        // The cell is bound by the bisector at the projected x-coordinate of 1.
+        let cell = unproject_cell(cells[0].clone(), &StretchTransform).unwrap();
+        assert_eq!(cell.site(), [0.0, 0.0]);
+        assert!(cell.cell().iter().all(|vertex| vertex[0] <= 0.5));
+        assert!(cell.cell().iter().any(|vertex| vertex[0] == -1.0));
+    }
+}
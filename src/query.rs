@@ -0,0 +1,192 @@
+//! This module answers point queries against the computed Voronoi cells.
+
+use std::borrow::Borrow;
+
+#[cfg(feature = "rstar")]
+use geo::BoundingRect;
+use geo::{Contains, Intersects, MultiPolygon};
+#[cfg(feature = "rstar")]
+use rstar::{
+    primitives::{GeomWithData, Rectangle},
+    RTree,
+};
+
+use crate::voronoi::BoundedVoronoiCell;
+
+/// Returns the index of the cell that contains the point or [`None`]
+/// if the point does not lie within any cell, e.g. outside of the bound.
+/// A point on the border between cells is assigned to the cell with the lowest index.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells
+/// * `point` - the point to locate
+pub fn locate(cells: &[BoundedVoronoiCell], point: [f64; 2]) -> Option<usize> {
+    first_containing_cell(
+        cells
+            .iter()
+            .map(BoundedVoronoiCell::multi_polygon)
+            .enumerate(),
+        point,
+    )
+}
+
+/// Returns the index of the first cell whose interior contains the point or
+/// otherwise the index of the first cell whose border contains the point.
+///
+/// # Parameters
+///
+/// * `cells` - the indices and pieces of the cells in ascending order of the indices
+/// * `point` - the point to locate
+fn first_containing_cell<T: Borrow<MultiPolygon>, I: IntoIterator<Item = (usize, T)>>(
+    cells: I,
+    point: [f64; 2],
+) -> Option<usize> {
+    let point = geo::Point::new(point[0], point[1]);
+    let mut border_cell = None;
+    for (index, pieces) in cells {
+        let pieces: &MultiPolygon = pieces.borrow();
+        if pieces.contains(&point) {
+            return Some(index);
+        }
+        if border_cell.is_none() && pieces.intersects(&point) {
+            border_cell = Some(index);
+        }
+    }
+    border_cell
+}
+
+/// A spatial index of the Voronoi cells for answering many point queries.
+#[cfg(feature = "rstar")]
+#[derive(Clone, Debug)]
+pub struct CellIndex {
+    /// The pieces of the cells in the same order as the cells.
+    pieces: Vec<MultiPolygon>,
+    /// The bounding rectangles of the cells paired with the cell indices.
+    envelopes: RTree<GeomWithData<Rectangle<[f64; 2]>, usize>>,
+    /// The sites of the cells paired with the cell indices.
+    sites: RTree<GeomWithData<[f64; 2], usize>>,
+}
+
+#[cfg(feature = "rstar")]
+impl CellIndex {
+    /// Creates a new index of the cells.
+    ///
+    /// # Parameters
+    ///
+    /// * `cells` - the Voronoi cells to index
+    pub fn new(cells: &[BoundedVoronoiCell]) -> Self {
+        let pieces: Vec<MultiPolygon> = cells
+            .iter()
+            .map(|cell| cell.multi_polygon().clone())
+            .collect();
+        let envelopes = pieces
+            .iter()
+            .enumerate()
+            .filter_map(|(index, cell_pieces)| {
+                cell_pieces.bounding_rect().map(|rect| {
+                    GeomWithData::new(
+                        Rectangle::from_corners(rect.min().x_y().into(), rect.max().x_y().into()),
+                        index,
+                    )
+                })
+            })
+            .collect();
+        let sites = cells
+            .iter()
+            .enumerate()
+            .map(|(index, cell)| GeomWithData::new(cell.site(), index))
+            .collect();
+        Self {
+            pieces,
+            envelopes: RTree::bulk_load(envelopes),
+            sites: RTree::bulk_load(sites),
+        }
+    }
+
+    /// Returns the index of the cell that contains the point with the same
+    /// semantics as [`locate`].
+    ///
+    /// # Parameters
+    ///
+    /// * `point` - the point to locate
+    pub fn locate(&self, point: [f64; 2]) -> Option<usize> {
+        let mut candidates: Vec<usize> = self
+            .envelopes
+            .locate_all_at_point(&point)
+            .map(|envelope| envelope.data)
+            .collect();
+        candidates.sort_unstable();
+        first_containing_cell(
+            candidates
+                .into_iter()
+                .map(|index| (index, &self.pieces[index])),
+            point,
+        )
+    }
+
+    /// Returns the index of the cell whose site is nearest to the point or [`None`]
+    /// if there are no cells.
+    /// In contrast to [`CellIndex::locate`] the bound is not taken into account.
+    ///
+    /// # Parameters
+    ///
+    /// * `point` - the point to find the nearest site for
+    pub fn nearest(&self, point: [f64; 2]) -> Option<usize> {
+        self.sites.nearest_neighbor(&point).map(|site| site.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns two adjacent unit square cells sharing the edge at x = 1.
+    fn adjacent_cells() -> Vec<BoundedVoronoiCell> {
+        vec![
+            BoundedVoronoiCell::new(
+                [0.5, 0.5],
+                vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.0, 0.0]],
+            ),
+            BoundedVoronoiCell::new(
+                [1.5, 0.5],
+                vec![[1.0, 0.0], [2.0, 0.0], [2.0, 1.0], [1.0, 1.0], [1.0, 0.0]],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_locate() {
+        let cells = adjacent_cells();
+        assert_eq!(locate(&cells, [0.2, 0.7]), Some(0));
+        assert_eq!(locate(&cells, [1.8, 0.1]), Some(1));
+    }
+
+    #[test]
+    fn test_locate_shared_edge() {
+        let cells = adjacent_cells();
+        assert_eq!(locate(&cells, [1.0, 0.5]), Some(0));
+        assert_eq!(locate(&cells, [2.0, 0.5]), Some(1));
+    }
+
+    #[test]
+    fn test_locate_outside_bound() {
+        let cells = adjacent_cells();
+        assert_eq!(locate(&cells, [3.0, 0.5]), None);
+        assert_eq!(locate(&cells, [1.0, -0.1]), None);
+        assert_eq!(locate(&[], [0.5, 0.5]), None);
+    }
+
+    #[test]
+    #[cfg(feature = "rstar")]
+    fn test_cellindex() {
+        let cells = adjacent_cells();
+        let index = CellIndex::new(&cells);
+        for point in [[0.2, 0.7], [1.8, 0.1], [1.0, 0.5], [2.0, 0.5], [3.0, 0.5]] {
+            assert_eq!(index.locate(point), locate(&cells, point));
+        }
+        assert_eq!(index.nearest([3.0, 0.5]), Some(1));
+        assert_eq!(index.nearest([-1.0, 0.0]), Some(0));
+        assert_eq!(CellIndex::new(&[]).nearest([0.0, 0.0]), None);
+    }
+}
@@ -0,0 +1,510 @@
+//! This module computes the bounded Voronoi diagramm on the surface of a sphere.
+//!
+//! The sites and the bound are specified as longitude and latitude in degrees and
+//! mapped to the unit sphere, where the distance between two points is the angle
+//! between them. The cell of a site is the intersection of the hemispheres bounded
+//! by the great circles that bisect the site and each other site.
+//!
+//! Each cell is computed in the gnomonic projection centered at its site, which
+//! maps great circles to straight lines, so that the hemispheres become half-planes
+//! and the cell is clipped like a planar Voronoi cell. The bound is clipped to the
+//! visible part of the sphere in three dimensions before it is projected and
+//! intersected with the cell. Finally the cell vertices are projected back to
+//! longitude and latitude.
+
+use std::{borrow::Borrow, collections::HashSet};
+
+use geo::{
+    orient::Direction, BooleanOps, Contains, Intersects, LineString, MultiPolygon, Orient, Point,
+    Polygon,
+};
+
+use crate::{
+    error::VoronoiError,
+    input::Point2D,
+    voronoi::{clip_half_plane, half_plane_neighbors, BoundedVoronoiCell},
+};
+
+/// The half side length of the square window of the gnomonic projection around
+/// each site, which covers all points up to about 89.4 degrees away from the site.
+const GNOMONIC_WINDOW: f64 = 100.0;
+
+/// The minimal distance of two sites on the unit sphere to be treated as different sites.
+const SITE_EPSILON: f64 = 1.0e-12;
+
+/// A point on the unit sphere.
+type Vector = [f64; 3];
+
+/// Returns the dot product of two vectors.
+///
+/// # Parameters
+///
+/// * `a` - the first vector
+/// * `b` - the second vector
+fn dot(a: Vector, b: Vector) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// Returns the vector scaled to unit length.
+///
+/// # Parameters
+///
+/// * `vector` - the vector to scale
+fn normalize(vector: Vector) -> Vector {
+    let length = dot(vector, vector).sqrt();
+    vector.map(|value| value / length)
+}
+
+/// Converts longitude and latitude in degrees to a point on the unit sphere.
+///
+/// # Parameters
+///
+/// * `lon_lat` - the longitude and latitude in degrees
+fn to_unit_vector([lon, lat]: [f64; 2]) -> Vector {
+    let (lon, lat) = (lon.to_radians(), lat.to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+/// Converts a point on the unit sphere to longitude and latitude in degrees.
+///
+/// # Parameters
+///
+/// * `vector` - the point on the unit sphere
+fn to_lon_lat(vector: Vector) -> [f64; 2] {
+    [
+        vector[1].atan2(vector[0]).to_degrees(),
+        vector[2].clamp(-1.0, 1.0).asin().to_degrees(),
+    ]
+}
+
+/// The gnomonic projection centered at a site, which maps the hemisphere around
+/// the site to the plane tangent to the sphere at the site.
+struct GnomonicFrame {
+    /// The site the projection is centered at.
+    centre: Vector,
+    /// The direction of increasing longitude at the site.
+    east: Vector,
+    /// The direction of increasing latitude at the site.
+    north: Vector,
+}
+
+impl GnomonicFrame {
+    /// Creates the gnomonic projection centered at the site.
+    ///
+    /// # Parameters
+    ///
+    /// * `site` - the longitude and latitude of the site in degrees
+    fn new(site: [f64; 2]) -> Self {
+        let (lon, lat) = (site[0].to_radians(), site[1].to_radians());
+        Self {
+            centre: to_unit_vector(site),
+            east: [-lon.sin(), lon.cos(), 0.0],
+            north: [-lat.sin() * lon.cos(), -lat.sin() * lon.sin(), lat.cos()],
+        }
+    }
+
+    /// Projects a point of the hemisphere around the site to the tangent plane.
+    ///
+    /// # Parameters
+    ///
+    /// * `vector` - the point on the unit sphere
+    fn forward(&self, vector: Vector) -> [f64; 2] {
+        let scale = dot(vector, self.centre);
+        [
+            dot(vector, self.east) / scale,
+            dot(vector, self.north) / scale,
+        ]
+    }
+
+    /// Projects a point of the tangent plane back to the unit sphere.
+    ///
+    /// # Parameters
+    ///
+    /// * `point` - the point in the tangent plane
+    fn inverse(&self, [x, y]: [f64; 2]) -> Vector {
+        normalize(
+            [0, 1, 2].map(|axis| self.centre[axis] + x * self.east[axis] + y * self.north[axis]),
+        )
+    }
+
+    /// Returns the normals of the great circles bounding the square window of the
+    /// projection, where a point lies within the window if its dot product with
+    /// all normals is not negative.
+    fn window(&self) -> [Vector; 4] {
+        let normal = |direction: Vector, sign: f64| {
+            [0, 1, 2].map(|axis| GNOMONIC_WINDOW * self.centre[axis] + sign * direction[axis])
+        };
+        [
+            normal(self.east, 1.0),
+            normal(self.east, -1.0),
+            normal(self.north, 1.0),
+            normal(self.north, -1.0),
+        ]
+    }
+}
+
+/// Computes the polygon-bound Voronoi diagramm of sites on the surface of a sphere.
+/// The sites and the vertices of the bound are specified as longitude and latitude
+/// in degrees and the distance between two points is the great-circle distance.
+/// The edges of the bound and the cells are great-circle arcs between consecutive
+/// vertices.
+///
+/// The cells are computed with `O(n²)` half-plane clippings in the gnomonic
+/// projection centered at each site and are ordered by their site, first by the
+/// longitude and then by the latitude. Sites with a non-finite coordinate or a
+/// latitude outside of `[-90, 90]` are ignored and sites that describe the same
+/// point on the sphere, e.g. at the longitudes -180 and 180, are only kept once.
+/// Sites outside of the bound result in an empty cell polygon unless their cell
+/// intersects the bound. The neighbors refer to the cells sharing an edge before
+/// the bound is applied.
+///
+/// The diagramm is subject to the following limitations:
+///
+/// * The bound is not centered around the sites and must lie within a hemisphere,
+///   as its interior is otherwise ambiguous.
+/// * Each cell is restricted to the points less than about 89.4 degrees away from
+///   its site, which only truncates cells of diagramms with very few sites.
+/// * The longitudes of the cell vertices are unwrapped relative to the longitude of
+///   the site, so cells crossing the antimeridian contain longitudes beyond ±180.
+/// * Cells around a pole are closed along the pole, so they are valid polygons in
+///   longitude and latitude.
+/// * The cell vertices are connected by great-circle arcs, which deviate from the
+///   straight lines drawn between them in longitude and latitude for long edges.
+///
+/// # Parameters
+///
+/// * `sites` - the longitude and latitude of the sites in degrees
+/// * `bound` - the polygon to apply as bound with longitude and latitude in degrees
+pub fn compute_spherical_voronoi<T: Borrow<Polygon>>(
+    sites: &[[f64; 2]],
+    bound: T,
+) -> Result<Vec<BoundedVoronoiCell>, VoronoiError> {
+    let bound = bound.borrow();
+    let exterior = ring_to_unit_vectors(bound.exterior())?;
+    if exterior.len() < 3 {
+        return Err(VoronoiError::TooFewBoundPoints);
+    }
+    let mut bound_rings = vec![exterior];
+    for hole in bound.interiors() {
+        let hole = ring_to_unit_vectors(hole)?;
+        if hole.len() < 3 {
+            return Err(VoronoiError::TooFewHolePoints);
+        }
+        bound_rings.push(hole);
+    }
+
+    let unique_sites: HashSet<Point2D> = Point2D::from_coordinates(sites.iter().copied())
+        .into_iter()
+        .filter(|site| site.y().abs() <= 90.0)
+        .collect();
+    let mut sorted_sites: Vec<Point2D> = unique_sites.into_iter().collect();
+    sorted_sites.sort();
+    let mut site_coordinates: Vec<[f64; 2]> = Vec::with_capacity(sorted_sites.len());
+    let mut site_vectors: Vec<Vector> = Vec::with_capacity(sorted_sites.len());
+    for site in sorted_sites {
+        let vector = to_unit_vector([site.x(), site.y()]);
+        if site_vectors
+            .iter()
+            .all(|&other| 1.0 - dot(vector, other) > SITE_EPSILON)
+        {
+            site_coordinates.push([site.x(), site.y()]);
+            site_vectors.push(vector);
+        }
+    }
+    if site_coordinates.is_empty() {
+        return Err(VoronoiError::EmptyPointSet);
+    }
+
+    let window: Vec<([f64; 2], Option<usize>)> = vec![
+        ([-GNOMONIC_WINDOW, -GNOMONIC_WINDOW], None),
+        ([GNOMONIC_WINDOW, -GNOMONIC_WINDOW], None),
+        ([GNOMONIC_WINDOW, GNOMONIC_WINDOW], None),
+        ([-GNOMONIC_WINDOW, GNOMONIC_WINDOW], None),
+    ];
+    let mut cells = Vec::with_capacity(site_coordinates.len());
+    for (index, &site) in site_coordinates.iter().enumerate() {
+        let frame = GnomonicFrame::new(site);
+        let mut cell = window.clone();
+        for (other_index, &other_site) in site_vectors.iter().enumerate() {
+            if other_index == index || cell.is_empty() {
+                continue;
+            }
+            // The cell is the half-plane normal · x <= offset, where the site is
+            // at least as close as the other site.
+            let normal = [dot(frame.east, other_site), dot(frame.north, other_site)];
+            let offset = 1.0 - dot(frame.centre, other_site);
+            cell = clip_half_plane(&cell, normal, offset, other_index);
+        }
+        let neighbors = half_plane_neighbors(&cell);
+
+        let mut pieces: Vec<Vec<[f64; 2]>> = Vec::new();
+        let mut holes: Vec<Vec<[f64; 2]>> = Vec::new();
+        if let Some(projected_bound) = project_bound(&bound_rings, &frame) {
+            let cell_polygon = MultiPolygon::new(vec![Polygon::new(
+                cell.iter().map(|&(vertex, _)| vertex).collect(),
+                Vec::new(),
+            )
+            .orient(Direction::Default)]);
+            let origin = Point::new(0.0, 0.0);
+            for intersection in cell_polygon
+                .intersection(&projected_bound)
+                .orient(Direction::Default)
+            {
+                holes.extend(
+                    intersection
+                        .interiors()
+                        .iter()
+                        .map(|hole| ring_to_lon_lat(hole, &frame, site[0])),
+                );
+                let piece = ring_to_lon_lat(intersection.exterior(), &frame, site[0]);
+                if intersection.intersects(&origin) {
+                    pieces.insert(0, piece);
+                } else {
+                    pieces.push(piece);
+                }
+            }
+        }
+        cells.push(BoundedVoronoiCell::from_pieces(site, pieces, holes, neighbors));
+    }
+    Ok(cells)
+}
+
+/// Converts a ring in longitude and latitude to points on the unit sphere
+/// without the closing point.
+///
+/// # Parameters
+///
+/// * `ring` - the ring with longitude and latitude in degrees
+fn ring_to_unit_vectors(ring: &LineString) -> Result<Vec<Vector>, VoronoiError> {
+    let mut vectors: Vec<Vector> = Vec::with_capacity(ring.0.len());
+    for coordinate in ring.coords() {
+        if !coordinate.x.is_finite() || !coordinate.y.is_finite() || coordinate.y.abs() > 90.0 {
+            return Err(VoronoiError::InvalidPolygon);
+        }
+        vectors.push(to_unit_vector([coordinate.x, coordinate.y]));
+    }
+    if vectors.len() > 1 && vectors.first() == vectors.last() {
+        vectors.pop();
+    }
+    Ok(vectors)
+}
+
+/// Clips the bound to the window of the gnomonic projection and projects it to the
+/// tangent plane. Returns [`None`] if the bound lies completely outside of the window.
+///
+/// # Parameters
+///
+/// * `bound_rings` - the exterior ring of the bound followed by its holes
+/// * `frame` - the gnomonic projection
+fn project_bound(bound_rings: &[Vec<Vector>], frame: &GnomonicFrame) -> Option<MultiPolygon> {
+    let project_ring = |ring: &Vec<Vector>| {
+        let clipped = frame
+            .window()
+            .into_iter()
+            .fold(ring.clone(), |clipped, normal| clip_ring(&clipped, normal));
+        (!clipped.is_empty()).then(|| {
+            clipped
+                .into_iter()
+                .map(|vector| frame.forward(vector))
+                .collect::<LineString>()
+        })
+    };
+    let exterior = project_ring(&bound_rings[0])?;
+    let holes = bound_rings[1..].iter().filter_map(project_ring).collect();
+    Some(MultiPolygon::new(vec![Polygon::new(exterior, holes).orient(Direction::Default)]))
+}
+
+/// Clips a ring on the unit sphere with the hemisphere of all points whose dot
+/// product with the normal is not negative. The edges of the ring are great-circle
+/// arcs, so the crossings are the points of the arcs on the bounding great circle.
+/// Returns an empty ring if the ring lies completely outside of the hemisphere.
+///
+/// # Parameters
+///
+/// * `ring` - the ring without the closing point
+/// * `normal` - the normal of the great circle bounding the hemisphere
+fn clip_ring(ring: &[Vector], normal: Vector) -> Vec<Vector> {
+    let mut clipped = Vec::with_capacity(ring.len() + 1);
+    for (index, &current) in ring.iter().enumerate() {
+        let next = ring[(index + 1) % ring.len()];
+        let current_distance = dot(current, normal);
+        let next_distance = dot(next, normal);
+        let crossing = || {
+            normalize([0, 1, 2].map(|axis| {
+                current_distance.abs() * next[axis] + next_distance.abs() * current[axis]
+            }))
+        };
+        if current_distance >= 0.0 {
+            clipped.push(current);
+            if next_distance < 0.0 {
+                clipped.push(crossing());
+            }
+        } else if next_distance >= 0.0 {
+            clipped.push(crossing());
+        }
+    }
+    if clipped.len() < 3 {
+        Vec::new()
+    } else {
+        clipped
+    }
+}
+
+/// Projects a closed ring from the tangent plane back to longitude and latitude.
+/// The longitudes are unwrapped starting from the longitude of the site, so that
+/// consecutive vertices never differ by more than 180 degrees. A ring around a pole
+/// does not close in longitude and is therefore closed along the pole.
+///
+/// # Parameters
+///
+/// * `ring` - the closed ring in the tangent plane
+/// * `frame` - the gnomonic projection
+/// * `site_lon` - the longitude of the site in degrees
+fn ring_to_lon_lat(ring: &LineString, frame: &GnomonicFrame, site_lon: f64) -> Vec<[f64; 2]> {
+    let mut lon_lat: Vec<[f64; 2]> = Vec::with_capacity(ring.0.len() + 3);
+    let mut previous_lon = site_lon;
+    for coordinate in ring.coords() {
+        let [lon, lat] = to_lon_lat(frame.inverse([coordinate.x, coordinate.y]));
+        let lon = lon + 360.0 * ((previous_lon - lon) / 360.0).round();
+        previous_lon = lon;
+        lon_lat.push([lon, lat]);
+    }
+    if let (Some(&first), Some(&last)) = (lon_lat.first(), lon_lat.last()) {
+        if (last[0] - first[0]).abs() > 180.0 {
+            let north_pole = [0.0, 0.0, 1.0];
+            let contains_north_pole = dot(frame.centre, north_pole) > 0.0
+                && Polygon::new(ring.clone(), Vec::new())
+                    .contains(&Point::from(frame.forward(north_pole)));
+            let pole_lat = if contains_north_pole { 90.0 } else { -90.0 };
+            lon_lat.extend([[last[0], pole_lat], [first[0], pole_lat], first]);
+        }
+    }
+    lon_lat
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Asserts that each vertex of the cells lies closer to the site of its cell
+    /// than to any other site, up to a small tolerance.
+    ///
+    /// # Parameters
+    ///
+    /// * `cells` - the cells to check
+    fn assert_nearest_site(cells: &[BoundedVoronoiCell]) {
+        let sites: Vec<Vector> = cells
+            .iter()
+            .map(|cell| to_unit_vector(cell.site()))
+            .collect();
+        for (index, cell) in cells.iter().enumerate() {
+            for vertex in cell.cells().iter().flatten() {
+                let vertex = to_unit_vector(*vertex);
+                for other_site in &sites {
+                    assert!(dot(vertex, sites[index]) >= dot(vertex, *other_site) - 1.0e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_compute_spherical_voronoi() {
+        let sites = vec![
+            [-60.0, -20.0],
+            [0.0, -30.0],
+            [60.0, -10.0],
+            [-30.0, 30.0],
+            [40.0, 35.0],
+            [0.0, 5.0],
+        ];
+        let bound = Polygon::new(
+            LineString::from(vec![(-80.0, -50.0), (80.0, -50.0), (80.0, 55.0), (-80.0, 55.0)]),
+            Vec::new(),
+        );
+        let cells = compute_spherical_voronoi(&sites, &bound).unwrap();
+        assert_eq!(cells.len(), 6);
+        assert_eq!(cells[0].site(), [-60.0, -20.0]);
+        assert_nearest_site(&cells);
+        for (index, cell) in cells.iter().enumerate() {
+            assert!(cell.cell().len() > 3);
+            for &neighbor in cell.neighbors() {
+                assert!(cells[neighbor].neighbors().contains(&index));
+            }
+        }
+        // The corners of the bound are vertices of the cell of their nearest site.
+        for corner in [[-80.0, -50.0], [80.0, -50.0], [80.0, 55.0], [-80.0, 55.0]] {
+            let nearest_cell = cells
+                .iter()
+                .max_by(|a, b| {
+                    let distance = |cell: &&BoundedVoronoiCell| {
+                        dot(to_unit_vector(cell.site()), to_unit_vector(corner))
+                    };
+                    distance(a).total_cmp(&distance(b))
+                })
+                .unwrap();
+            assert!(nearest_cell.cell().iter().any(|vertex| {
+                (vertex[0] - corner[0]).abs() < 1.0e-9 && (vertex[1] - corner[1]).abs() < 1.0e-9
+            }));
+        }
+    }
+
+    #[test]
+    fn test_compute_spherical_voronoi_pole() {
+        let sites = vec![[0.0, 90.0], [0.0, 0.0], [120.0, 0.0], [-120.0, 0.0]];
+        let bound = Polygon::new(
+            (0..8)
+                .map(|index| (-180.0 + 45.0 * index as f64, 20.0))
+                .collect(),
+            Vec::new(),
+        );
+        let cells = compute_spherical_voronoi(&sites, &bound).unwrap();
+        assert_eq!(cells.len(), 4);
+        assert_nearest_site(&cells);
+        // The cell of the pole spans all longitudes and is closed along the pole.
+        let pole_cell = &cells[2];
+        assert_eq!(pole_cell.site(), [0.0, 90.0]);
+        let lons: Vec<f64> = pole_cell.cell().iter().map(|vertex| vertex[0]).collect();
+        let lon_span = lons.iter().copied().fold(f64::MIN, f64::max)
+            - lons.iter().copied().fold(f64::MAX, f64::min);
+        assert!((lon_span - 360.0).abs() < 1.0e-9);
+        assert_eq!(
+            pole_cell
+                .cell()
+                .iter()
+                .filter(|vertex| vertex[1] == 90.0)
+                .count(),
+            2
+        );
+        assert!(cells.iter().all(|cell| !cell.cell().is_empty()));
+    }
+
+    #[test]
+    fn test_compute_spherical_voronoi_sites() {
+        let bound = Polygon::new(
+            LineString::from(vec![(-10.0, -10.0), (10.0, -10.0), (10.0, 10.0), (-10.0, 10.0)]),
+            Vec::new(),
+        );
+        // Sites at the same point of the sphere are only kept once and invalid
+        // sites are ignored.
+        let cells = compute_spherical_voronoi(
+            &[
+                [-180.0, 0.0],
+                [180.0, 0.0],
+                [0.0, 100.0],
+                [f64::NAN, 0.0],
+                [0.0, 0.0],
+            ],
+            &bound,
+        )
+        .unwrap();
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[0].site(), [-180.0, 0.0]);
+        assert!(cells[0].cell().is_empty());
+        assert_eq!(cells[1].site(), [0.0, 0.0]);
+        assert!(!cells[1].cell().is_empty());
+        assert!(matches!(
+            compute_spherical_voronoi(&[[0.0, 100.0]], &bound),
+            Err(VoronoiError::EmptyPointSet)
+        ));
+    }
+}
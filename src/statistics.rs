@@ -0,0 +1,450 @@
+//! This module computes summary statistics of the Voronoi cells.
+
+use std::borrow::Borrow;
+
+use geo::{Area, BooleanOps, Coord, EuclideanLength, Intersects, Line, MultiPolygon, Point};
+use getset::CopyGetters;
+
+use crate::{
+    error::VoronoiError,
+    input::Bounds,
+    voronoi::{center_polygon, BoundedVoronoiCell},
+};
+
+/// The area covered by the Voronoi cells in relation to the area of a bound.
+#[derive(Clone, Copy, CopyGetters, Debug, PartialEq)]
+pub struct Coverage {
+    /// The sum of the areas of all cells.
+    #[getset(get_copy = "pub")]
+    total_cell_area: f64,
+    /// The area of the bound.
+    #[getset(get_copy = "pub")]
+    bound_area: f64,
+    /// The ratio of the total cell area to the bound area.
+    #[getset(get_copy = "pub")]
+    ratio: f64,
+}
+
+impl Coverage {
+    /// Creates a new coverage from the total cell area and the bound area.
+    /// The ratio is 0 if the bound does not cover any area.
+    ///
+    /// # Parameters
+    ///
+    /// * `total_cell_area` - the sum of the areas of all cells
+    /// * `bound_area` - the area of the bound
+    pub fn new(total_cell_area: f64, bound_area: f64) -> Self {
+        let ratio = if bound_area > 0.0 {
+            total_cell_area / bound_area
+        } else {
+            0.0
+        };
+        Self {
+            total_cell_area,
+            bound_area,
+            ratio,
+        }
+    }
+}
+
+/// Returns the area covered by the cells in relation to the area of the bound.
+/// As the cell bound is centered around each site, the bound passed here should
+/// be the region that is expected to be covered by the cells as a whole,
+/// in which case a ratio of about 1 indicates that clipping did not drop any regions.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells
+/// * `bound` - the region expected to be covered by the cells
+pub fn coverage<T: Borrow<MultiPolygon>>(cells: &[BoundedVoronoiCell], bound: T) -> Coverage {
+    Coverage::new(total_cell_area(cells), bound.borrow().unsigned_area())
+}
+
+/// The result of comparing the union of the Voronoi cells with the region they should cover.
+#[derive(Clone, Copy, CopyGetters, Debug, PartialEq)]
+pub struct TessellationCheck {
+    /// The area of the symmetric difference between the union of the cells and the region,
+    /// which covers gaps between the cells as well as cells extending beyond the region.
+    #[getset(get_copy = "pub")]
+    symmetric_difference_area: f64,
+    /// The area covered by more than one cell, which is the difference
+    /// between the sum of the cell areas and the area of their union.
+    #[getset(get_copy = "pub")]
+    overlap_area: f64,
+    /// The area of the region the cells should cover.
+    #[getset(get_copy = "pub")]
+    region_area: f64,
+}
+
+/// Returns the union of all pieces of all cells.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells
+pub fn union_cells(cells: &[BoundedVoronoiCell]) -> MultiPolygon {
+    cells
+        .iter()
+        .fold(MultiPolygon::new(Vec::new()), |union, cell| union.union(cell.multi_polygon()))
+}
+
+/// Compares the union of the cells with the union of the cell bound centered around
+/// each site as integrity check of the clipping.
+/// For a correct tessellation both the symmetric difference and the overlap are close
+/// to 0, unless locations within the bound of a site are closer to a site, whose bound
+/// does not cover them, as described for [`centered_bound_coverage`].
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells
+/// * `bound` - the cell bound
+pub fn verify_tessellation<T: Borrow<MultiPolygon>>(
+    cells: &[BoundedVoronoiCell],
+    bound: T,
+) -> Result<TessellationCheck, VoronoiError> {
+    let region = centered_bound_region(cells, bound)?;
+    let union = union_cells(cells);
+    Ok(TessellationCheck {
+        symmetric_difference_area: union.xor(&region).unsigned_area(),
+        overlap_area: (total_cell_area(cells) - union.unsigned_area()).max(0.0),
+        region_area: region.unsigned_area(),
+    })
+}
+
+/// Returns the area covered by the cells in relation to the area of the union
+/// of the cell bound centered around each site.
+/// The ratio is 1 if every location within the bound of a site is covered,
+/// while it falls below 1 if locations are closer to a site, whose bound does not
+/// cover them, than to the sites, whose bound does.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells
+/// * `bound` - the cell bound
+pub fn centered_bound_coverage<T: Borrow<MultiPolygon>>(
+    cells: &[BoundedVoronoiCell],
+    bound: T,
+) -> Result<Coverage, VoronoiError> {
+    let region = centered_bound_region(cells, bound)?;
+    Ok(Coverage::new(total_cell_area(cells), region.unsigned_area()))
+}
+
+/// Returns the union of the cell bound centered around each site.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells
+/// * `bound` - the cell bound
+fn centered_bound_region<T: Borrow<MultiPolygon>>(
+    cells: &[BoundedVoronoiCell],
+    bound: T,
+) -> Result<MultiPolygon, VoronoiError> {
+    let bound: &MultiPolygon = bound.borrow();
+    let mut region = MultiPolygon::new(Vec::new());
+    for cell in cells {
+        let centered_bound = center_polygon(bound, cell.site()[0], cell.site()[1])?;
+        region = region.union(&centered_bound);
+    }
+    Ok(region)
+}
+
+/// Returns the length of the boundary shared by each pair of neighboring cells
+/// as `(first index, second index, length)` with the first index being the smaller one.
+/// Only the parts of the cell edges that overlap after the bound has been applied
+/// are taken into account, so edges partially running along the bound only contribute
+/// their shared part. Pairs without any shared boundary are omitted.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells
+/// * `epsilon` - the maximum distance of two edges that are considered to overlap
+pub fn shared_boundaries(cells: &[BoundedVoronoiCell], epsilon: f64) -> Vec<(usize, usize, f64)> {
+    let edges: Vec<Vec<Line>> = cells
+        .iter()
+        .map(|cell| {
+            cell.multi_polygon()
+                .iter()
+                .flat_map(|piece| piece.exterior().lines().collect::<Vec<Line>>())
+                .collect()
+        })
+        .collect();
+    let mut boundaries = Vec::new();
+    for (index, cell) in cells.iter().enumerate() {
+        let mut neighbors: Vec<usize> = cell
+            .neighbors()
+            .iter()
+            .copied()
+            .filter(|&neighbor| neighbor > index && neighbor < cells.len())
+            .collect();
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        for neighbor in neighbors {
+            let length: f64 = edges[index]
+                .iter()
+                .flat_map(|edge| {
+                    edges[neighbor]
+                        .iter()
+                        .map(move |other_edge| overlap_length(edge, other_edge, epsilon))
+                })
+                .sum();
+            if length > 0.0 {
+                boundaries.push((index, neighbor, length));
+            }
+        }
+    }
+    boundaries
+}
+
+/// Counts the samples of a regular grid over the combined bounds of all cells that fall
+/// into each cell, e.g. for heatmaps or to validate the cell sizes.
+/// The grid has `resolution` squares along the longer side of the combined bounds and is
+/// sampled at the centres of its squares, so the counts are roughly proportional to the
+/// cell areas. Samples within holes are not counted and a sample on the boundary of
+/// several cells is only counted for the first of them.
+/// Fails if the resolution is 0.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells
+/// * `resolution` - the number of grid squares along the longer side of the combined bounds
+pub fn sample_density(
+    cells: &[BoundedVoronoiCell],
+    resolution: usize,
+) -> Result<Vec<usize>, VoronoiError> {
+    if resolution == 0 {
+        return Err(VoronoiError::InvalidResolution);
+    }
+    let polygons: Vec<(&MultiPolygon, Option<Bounds>)> = cells
+        .iter()
+        .map(|cell| {
+            let polygon = cell.multi_polygon();
+            let bounds = Bounds::from_multi_polygon(polygon);
+            (polygon, bounds)
+        })
+        .collect();
+    let mut counts = vec![0; cells.len()];
+    let Some(bounds) = polygons
+        .iter()
+        .filter_map(|(_, bounds)| *bounds)
+        .reduce(|combined, bounds| combined.union(bounds))
+    else {
+        return Ok(counts);
+    };
+    let step = bounds.diff_x().max(bounds.diff_y()) / resolution as f64;
+    if step <= 0.0 {
+        return Ok(counts);
+    }
+    let columns = (bounds.diff_x() / step).ceil().max(1.0) as usize;
+    let rows = (bounds.diff_y() / step).ceil().max(1.0) as usize;
+    let min_x = bounds.centre_x() - columns as f64 * step / 2.0;
+    let min_y = bounds.centre_y() - rows as f64 * step / 2.0;
+    for row in 0..rows {
+        for column in 0..columns {
+            let x = min_x + (column as f64 + 0.5) * step;
+            let y = min_y + (row as f64 + 0.5) * step;
+            let sample = Point::new(x, y);
+            // The bounds are checked first to skip the exact test for most cells.
+            if let Some(index) = polygons.iter().position(|(polygon, bounds)| {
+                bounds.is_some_and(|bounds| {
+                    bounds.min_x() <= x
+                        && x <= bounds.max_x()
+                        && bounds.min_y() <= y
+                        && y <= bounds.max_y()
+                }) && polygon.intersects(&sample)
+            }) {
+                counts[index] += 1;
+            }
+        }
+    }
+    Ok(counts)
+}
+
+/// Returns the length of the part of the first edge that is overlapped by the second edge
+/// or 0 if the edges are not collinear within the epsilon.
+///
+/// # Parameters
+///
+/// * `edge` - the edge to measure the overlap on
+/// * `other_edge` - the edge overlapping the first edge
+/// * `epsilon` - the maximum distance of the second edge from the line of the first edge
+fn overlap_length(edge: &Line, other_edge: &Line, epsilon: f64) -> f64 {
+    let length = edge.euclidean_length();
+    if length <= epsilon {
+        return 0.0;
+    }
+    let direction = (edge.delta().x / length, edge.delta().y / length);
+    let offset = |point: Coord| (point.x - edge.start.x, point.y - edge.start.y);
+    let distance = |point: Coord| {
+        let (x, y) = offset(point);
+        (x * direction.1 - y * direction.0).abs()
+    };
+    if distance(other_edge.start) > epsilon || distance(other_edge.end) > epsilon {
+        return 0.0;
+    }
+    let project = |point: Coord| {
+        let (x, y) = offset(point);
+        x * direction.0 + y * direction.1
+    };
+    let (start, end) = (project(other_edge.start), project(other_edge.end));
+    (start.max(end).min(length) - start.min(end).max(0.0)).max(0.0)
+}
+
+/// Returns the sum of the areas of all cells.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells
+fn total_cell_area(cells: &[BoundedVoronoiCell]) -> f64 {
+    cells.iter().map(BoundedVoronoiCell::area).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_abs_diff_eq;
+
+    use super::*;
+    use crate::{compute_voronoi, BoundedPointSet};
+
+    /// Returns four symmetric sites with a square bound of size 2.
+    fn symmetric_bounded_point_set() -> BoundedPointSet {
+        BoundedPointSet::new(
+            vec![[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]],
+            vec![
+                [-1.0, -1.0],
+                [1.0, -1.0],
+                [1.0, 1.0],
+                [-1.0, 1.0],
+                [-1.0, -1.0],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_sample_density() {
+        let cells = compute_voronoi(symmetric_bounded_point_set()).unwrap();
+        let counts = sample_density(&cells, 100).unwrap();
+        // The four equal cells cover the square from -2 to 2, so each receives a quarter
+        // of the samples.
+        assert_eq!(counts.iter().sum::<usize>(), 100 * 100);
+        for count in &counts {
+            assert!(count.abs_diff(2500) <= 25);
+        }
+        assert_eq!(sample_density(&cells, 0), Err(VoronoiError::InvalidResolution));
+        assert_eq!(sample_density(&[], 10), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_coverage_symmetric() {
+        let cells = compute_voronoi(symmetric_bounded_point_set()).unwrap();
+        // The bounds centered around the sites tile the square from -2 to 2.
+        let region = BoundedPointSet::with_bbox(vec![], [-2.0, -2.0, 2.0, 2.0])
+            .bounding_multi_polygon()
+            .unwrap();
+        let coverage = coverage(&cells, region);
+        assert_abs_diff_eq!(coverage.total_cell_area(), 16.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(coverage.bound_area(), 16.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(coverage.ratio(), 1.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_centered_bound_coverage_symmetric() {
+        let bounded_point_set = symmetric_bounded_point_set();
+        let cells = compute_voronoi(&bounded_point_set).unwrap();
+        let coverage =
+            centered_bound_coverage(&cells, bounded_point_set.bounding_multi_polygon().unwrap())
+                .unwrap();
+        assert_abs_diff_eq!(coverage.bound_area(), 16.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(coverage.ratio(), 1.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_union_cells() {
+        let cells = compute_voronoi(symmetric_bounded_point_set()).unwrap();
+        let union = union_cells(&cells);
+        assert_eq!(union.0.len(), 1);
+        assert_abs_diff_eq!(union.unsigned_area(), 16.0, epsilon = 1e-9);
+        assert!(union_cells(&[]).0.is_empty());
+    }
+
+    #[test]
+    fn test_verify_tessellation_symmetric() {
+        let bounded_point_set = symmetric_bounded_point_set();
+        let cells = compute_voronoi(&bounded_point_set).unwrap();
+        let check =
+            verify_tessellation(&cells, bounded_point_set.bounding_multi_polygon().unwrap())
+                .unwrap();
+        assert_abs_diff_eq!(check.symmetric_difference_area(), 0.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(check.overlap_area(), 0.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(check.region_area(), 16.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_verify_tessellation_defects() {
+        // The second cell overlaps the first one by 1 and the square from 1 to 2 is not covered.
+        let cells: Vec<BoundedVoronoiCell> = serde_json::from_value(serde_json::json!([
+            {"site": [0.5, 0.5], "cell": [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]},
+            {"site": [1.5, 0.5], "cell": [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]},
+        ]))
+        .unwrap();
+        let bound = BoundedPointSet::with_bbox(vec![], [0.0, 0.0, 1.0, 1.0])
+            .bounding_multi_polygon()
+            .unwrap();
+        let check = verify_tessellation(&cells, bound).unwrap();
+        assert_abs_diff_eq!(check.symmetric_difference_area(), 1.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(check.overlap_area(), 1.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(check.region_area(), 2.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_shared_boundaries_bisector() {
+        // The bisector x = 0 splits the bound of width 2 around each site into two cells.
+        let bounded_point_set = BoundedPointSet::new(
+            vec![[-0.5, 0.0], [0.5, 0.0]],
+            vec![
+                [-1.0, -1.0],
+                [1.0, -1.0],
+                [1.0, 1.0],
+                [-1.0, 1.0],
+                [-1.0, -1.0],
+            ],
+        );
+        let cells = compute_voronoi(&bounded_point_set).unwrap();
+        let boundaries = shared_boundaries(&cells, 1e-9);
+        assert_eq!(boundaries.len(), 1);
+        assert_eq!((boundaries[0].0, boundaries[0].1), (0, 1));
+        assert_abs_diff_eq!(boundaries[0].2, 2.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_shared_boundaries_partial_edge() {
+        // Only the lower half of the right edge of the first cell is shared
+        // and the left edge of the second cell deviates slightly from it.
+        let cells: Vec<BoundedVoronoiCell> = serde_json::from_value(serde_json::json!([
+            {
+                "site": [0.5, 1.0],
+                "cell": [[0.0, 0.0], [1.0, 0.0], [1.0, 2.0], [0.0, 2.0], [0.0, 0.0]],
+                "neighbors": [1, 2],
+            },
+            {
+                "site": [1.5, 0.5],
+                "cell": [[1.0, 0.0], [2.0, 0.0], [2.0, 1.0], [1.0 + 1e-12, 1.0], [1.0, 0.0]],
+                "neighbors": [0],
+            },
+            {
+                "site": [5.5, 0.5],
+                "cell": [[5.0, 0.0], [6.0, 0.0], [6.0, 1.0], [5.0, 1.0], [5.0, 0.0]],
+                "neighbors": [0],
+            },
+        ]))
+        .unwrap();
+        let boundaries = shared_boundaries(&cells, 1e-9);
+        assert_eq!(boundaries.len(), 1);
+        assert_eq!((boundaries[0].0, boundaries[0].1), (0, 1));
+        assert_abs_diff_eq!(boundaries[0].2, 1.0, epsilon = 1e-9);
+        assert!(shared_boundaries(&cells, 1e-15).is_empty());
+    }
+
+    #[test]
+    fn test_coverage_empty_bound() {
+        assert_eq!(Coverage::new(1.0, 0.0).ratio(), 0.0);
+    }
+}
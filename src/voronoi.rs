@@ -1,133 +1,5045 @@
 //! This module computes the bounded voronoi diagramm.
 
-use std::borrow::Borrow;
+use std::{
+    borrow::Borrow,
+    collections::{BTreeSet, HashMap, HashSet},
+    convert::Infallible,
+    sync::{Arc, OnceLock},
+    time::{Duration, Instant},
+};
 
-use geo::{BooleanOps, BoundingRect, Contains, CoordsIter, LineString, Polygon};
+use geo::{
+    orient::Direction, Area, BooleanOps, BoundingRect, Centroid, Contains, ConvexHull, CoordsIter,
+    Densify, EuclideanDistance, EuclideanLength, Intersects, LineString, MapCoords, MultiPolygon,
+    Orient, Polygon, Simplify, Translate, TriangulateEarcut,
+};
+use getset::{CopyGetters, Getters};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
-use voronoice::{BoundingBox, VoronoiBuilder};
+use voronoice::{BoundingBox, ClipBehavior, VoronoiBuilder};
 
-use crate::input::{BoundedPointSet, Bounds};
+use crate::{
+    error::VoronoiError,
+    hull::lower_hull_neighbors,
+    input::{
+        find_self_intersection, BoundedPointSet, Bounds, MultiGroupInput, Point2D, PointProperties,
+    },
+    output::{coordinate_ring, Coordinate},
+};
 
-/// Computes the polygon-bound voronoi diagramm of the input point set.
-/// 
+/// The margin around the diagramm area relative to its larger side.
+const DIAGRAMM_MARGIN: f64 = 0.1;
+
+/// The number of cells the iterator returned by [`compute_voronoi_iter_with_options`]
+/// applies the bound to at once.
+const ITERATOR_BATCH_SIZE: usize = 1024;
+
+/// The number of sites above which a progress bar is shown if requested.
+#[cfg(feature = "progress")]
+const PROGRESS_THRESHOLD: usize = 10_000;
+
+/// The behaviour for sites that do not lie within the bound centered around them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SitePlacement {
+    /// An error is returned.
+    Error,
+    /// The sites are removed before the Voronoi diagramm is computed.
+    Drop,
+    /// The sites are kept, which results in an error if their cell
+    /// does not intersect the bound.
+    #[default]
+    Keep,
+    /// The sites are kept and their cells are returned without any polygon
+    /// if they do not intersect the bound.
+    KeepEmpty,
+}
+
+/// The key the cells are sorted by after the bound has been applied.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortKey {
+    /// The cells are sorted by descending area, so that the largest cell comes first.
+    Area,
+    /// The cells are sorted by ascending area, so that the smallest cell comes first.
+    AreaAscending,
+    /// The cells are sorted by ascending x-coordinate of their site.
+    SiteX,
+    /// The cells are sorted by ascending y-coordinate of their site.
+    SiteY,
+}
+
+/// The number of parts each side of a triangle is divided into when integrating
+/// a density over a cell.
+const DENSITY_SUBDIVISIONS: usize = 8;
+
+/// The distance relative to the extent of the point set, below which
+/// Voronoi vertices are considered the same vertex.
+const VERTEX_TOLERANCE: f64 = 1e-9;
+
+/// A scalar density field weighting the centroids of Lloyd relaxation.
+#[derive(Clone)]
+struct LloydDensity(Arc<dyn Fn([f64; 2]) -> f64 + Send + Sync>);
+
+impl LloydDensity {
+    /// Returns the density at the specified location, where negative and
+    /// non-finite densities are treated as 0.
+    ///
+    /// # Parameters
+    ///
+    /// * `location` - the location to evaluate the density at
+    fn evaluate(&self, location: [f64; 2]) -> f64 {
+        let density = (self.0)(location);
+        if density.is_finite() && density > 0.0 {
+            density
+        } else {
+            0.0
+        }
+    }
+}
+
+impl std::fmt::Debug for LloydDensity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("LloydDensity(..)")
+    }
+}
+
+impl PartialEq for LloydDensity {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Options controlling the computation of the bounded Voronoi diagramm.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct VoronoiOptions {
+    /// If the area of each cell should be included in the output.
+    include_area: bool,
+    /// If the index of each site should be included in the output.
+    include_index: bool,
+    /// If the unbounded cell polygon should be included in the output.
+    include_unbounded: bool,
+    /// The number of Lloyd relaxation iterations or the maximum number
+    /// of iterations if a tolerance has been specified.
+    lloyd_iterations: usize,
+    /// The displacement of the sites below which Lloyd relaxation stops.
+    lloyd_tolerance: Option<f64>,
+    /// The density weighting the centroids of Lloyd relaxation.
+    lloyd_density: Option<LloydDensity>,
+    /// The behaviour for sites outside of the bound.
+    site_placement: SitePlacement,
+    /// If a progress bar should be shown for large point sets.
+    show_progress: bool,
+    /// If the wall time of the phases of the computation should be written to stderr.
+    show_timing: bool,
+    /// The precision of the grid the coordinates are snapped to before clipping.
+    snap_precision: Option<f64>,
+    /// The distance the bound is shrunk inwards by before clipping.
+    inset: Option<f64>,
+    /// If the first vertex of each ring should be repeated at its end.
+    close_rings: bool,
+    /// If the cells should be ordered like the first occurrences of their input points.
+    input_order: bool,
+    /// The seed of the random number generator shuffling the sites.
+    seed: Option<u64>,
+    /// The maximum number of unique sites or [`None`] if unlimited.
+    max_sites: Option<usize>,
+    /// The maximum coordinate difference of sites that are merged.
+    merge_tolerance: Option<f64>,
+    /// The key the cells are sorted by after the bound has been applied.
+    sort_by: Option<SortKey>,
+    /// The margin the convex hull of the sites is expanded by if it replaces the bound.
+    hull_bound: Option<f64>,
+    /// The number of threads the bound is applied to the cells with.
+    threads: Option<usize>,
+}
+
+impl VoronoiOptions {
+    /// Consumes the options and returns them with the specified area setting.
+    ///
+    /// # Parameters
+    ///
+    /// * `include_area` - if the area of each cell should be included in the output
+    pub fn include_area(mut self, include_area: bool) -> Self {
+        self.include_area = include_area;
+        self
+    }
+
+    /// Consumes the options and returns them with the specified index setting.
+    /// The index of a site refers to the unique, valid points ordered as returned by
+    /// [`BoundedPointSet::sorted_point_set`] or [`BoundedPointSet::ordered_point_set`] if
+    /// [`VoronoiOptions::input_order`] has been enabled, e.g. to correlate the cells with
+    /// an external array. The indices are only contiguous if no cells are dropped,
+    /// e.g. because of [`SitePlacement::Drop`] or [`drop_degenerate_cells`].
+    ///
+    /// # Parameters
+    ///
+    /// * `include_index` - if the index of each site should be included in the output
+    pub fn include_index(mut self, include_index: bool) -> Self {
+        self.include_index = include_index;
+        self
+    }
+
+    /// Consumes the options and returns them with the specified setting
+    /// for including the cell polygons before the bound is applied.
+    ///
+    /// # Parameters
+    ///
+    /// * `include_unbounded` - if the unbounded cell polygon should be included in the output
+    pub fn include_unbounded(mut self, include_unbounded: bool) -> Self {
+        self.include_unbounded = include_unbounded;
+        self
+    }
+
+    /// Consumes the options and returns them with the specified number of
+    /// Lloyd relaxation iterations.
+    /// Each iteration moves the sites towards the centroids of their cells,
+    /// which results in more evenly spaced sites.
+    /// The relaxed sites are reported as cell sites, while the original input
+    /// points are reported as original sites.
+    /// With 0 iterations the input points are used as sites exactly.
+    /// This replaces a previously set [`VoronoiOptions::lloyd_until`].
+    ///
+    /// # Parameters
+    ///
+    /// * `lloyd_iterations` - the number of Lloyd relaxation iterations
+    pub fn lloyd_iterations(mut self, lloyd_iterations: usize) -> Self {
+        self.lloyd_iterations = lloyd_iterations;
+        self.lloyd_tolerance = None;
+        self
+    }
+
+    /// Consumes the options and returns them with Lloyd relaxation running until
+    /// convergence, which results in a centroidal Voronoi tessellation.
+    /// In each iteration every site is moved to the centroid of its cell after the
+    /// bound has been applied. The relaxation stops once no site has moved further
+    /// than the tolerance or after the maximum number of iterations.
+    /// Sites whose cell does not intersect the bound are not moved.
+    /// The number of iterations actually run is reported by [`compute_voronoi_with_report`].
+    /// This replaces a previously set [`VoronoiOptions::lloyd_iterations`].
+    ///
+    /// # Parameters
+    ///
+    /// * `tolerance` - the maximum displacement of a site below which the relaxation stops
+    /// * `max_iterations` - the maximum number of Lloyd relaxation iterations
+    pub fn lloyd_until(mut self, tolerance: f64, max_iterations: usize) -> Self {
+        self.lloyd_iterations = max_iterations;
+        self.lloyd_tolerance = Some(tolerance);
+        self
+    }
+
+    /// Consumes the options and returns them with Lloyd relaxation moving each site
+    /// to the centroid of its cell weighted by the specified density instead of the
+    /// plain centroid, which concentrates the sites where the density is high,
+    /// e.g. for importance sampling.
+    /// The density is integrated over the cell after the bound has been applied by
+    /// evaluating it at the centroids of a fine triangulation of the cell.
+    /// Negative and non-finite densities are treated as 0 and a cell without any
+    /// density falls back to its plain centroid.
+    /// The number of iterations is still set by [`VoronoiOptions::lloyd_iterations`]
+    /// or [`VoronoiOptions::lloyd_until`].
+    ///
+    /// # Parameters
+    ///
+    /// * `density` - the density at a location
+    pub fn lloyd_weighted<F: Fn([f64; 2]) -> f64 + Send + Sync + 'static>(
+        mut self,
+        density: F,
+    ) -> Self {
+        self.lloyd_density = Some(LloydDensity(Arc::new(density)));
+        self
+    }
+
+    /// Consumes the options and returns them with the specified behaviour
+    /// for sites that do not lie within the bound centered around them.
+    ///
+    /// # Parameters
+    ///
+    /// * `site_placement` - the behaviour for sites outside of the bound
+    pub fn site_placement(mut self, site_placement: SitePlacement) -> Self {
+        self.site_placement = site_placement;
+        self
+    }
+
+    /// Consumes the options and returns them with the specified progress setting.
+    /// The progress of applying the bound to the cells is shown on stderr
+    /// if more than 10000 sites are processed and stderr is a terminal.
+    /// This only has an effect if the `progress` feature is enabled.
+    ///
+    /// # Parameters
+    ///
+    /// * `show_progress` - if a progress bar should be shown for large point sets
+    pub fn show_progress(mut self, show_progress: bool) -> Self {
+        self.show_progress = show_progress;
+        self
+    }
+
+    /// Consumes the options and returns them with the specified timing setting.
+    /// The wall time of the deduplication of the sites, the construction of
+    /// the unbounded diagramm, the iteration over its cells and the clipping
+    /// of the cells is written to stderr, e.g. to find the bottleneck for large
+    /// point sets.
+    ///
+    /// # Parameters
+    ///
+    /// * `show_timing` - if the wall time of the phases should be written to stderr
+    pub fn show_timing(mut self, show_timing: bool) -> Self {
+        self.show_timing = show_timing;
+        self
+    }
+
+    /// Consumes the options and returns them with the specified snap precision.
+    /// Before a cell is clipped, the coordinates of the cell and the bound are
+    /// rounded to a grid with the specified spacing, which avoids failures
+    /// and sliver polygons caused by floating point errors for nearly
+    /// collinear geometries. Non-positive or non-finite values disable snapping.
+    ///
+    /// # Parameters
+    ///
+    /// * `snap_precision` - the spacing of the grid the coordinates are snapped to
+    pub fn snap_precision(mut self, snap_precision: f64) -> Self {
+        self.snap_precision =
+            (snap_precision.is_finite() && snap_precision > 0.0).then_some(snap_precision);
+        self
+    }
+
+    /// Consumes the options and returns them with the specified inset.
+    /// The cells are clipped to the bound shrunk inwards by the distance as
+    /// described for [`inset_bound`], e.g. to leave gutters along the boundary.
+    /// Non-positive or non-finite values disable the inset.
+    ///
+    /// # Parameters
+    ///
+    /// * `inset` - the distance the bound is shrunk inwards by
+    pub fn inset(mut self, inset: f64) -> Self {
+        self.inset = (inset.is_finite() && inset > 0.0).then_some(inset);
+        self
+    }
+
+    /// Consumes the options and returns them with the specified ring setting.
+    /// If enabled, the first vertex of each cell ring is repeated at its end
+    /// unless the ring is already closed.
+    ///
+    /// # Parameters
+    ///
+    /// * `close_rings` - if the rings of the cells should be closed
+    pub fn close_rings(mut self, close_rings: bool) -> Self {
+        self.close_rings = close_rings;
+        self
+    }
+
+    /// Consumes the options and returns them with the specified site limit.
+    /// The computation fails with [`VoronoiError::TooManySites`] before the diagramm
+    /// is built if the point set contains more unique sites, e.g. to guard against
+    /// accidentally huge inputs exhausting the memory.
+    ///
+    /// # Parameters
+    ///
+    /// * `max_sites` - the maximum number of unique sites
+    pub fn max_sites(mut self, max_sites: usize) -> Self {
+        self.max_sites = Some(max_sites);
+        self
+    }
+
+    /// Consumes the options and returns them with the specified merge tolerance.
+    /// Sites whose coordinates both differ by at most the tolerance are merged into
+    /// the site occurring first in the input as described for
+    /// [`BoundedPointSet::merged_point_set`], e.g. to prevent degenerate triangulations
+    /// caused by near-duplicate sites. Non-positive or non-finite values merge only
+    /// exact duplicates.
+    ///
+    /// # Parameters
+    ///
+    /// * `merge_tolerance` - the maximum coordinate difference of merged sites
+    pub fn merge_tolerance(mut self, merge_tolerance: f64) -> Self {
+        self.merge_tolerance =
+            (merge_tolerance.is_finite() && merge_tolerance > 0.0).then_some(merge_tolerance);
+        self
+    }
+
+    /// Consumes the options and returns them with the specified cell order.
+    /// If enabled, the cells are ordered like the first occurrences of their
+    /// input points instead of by their coordinates.
+    ///
+    /// # Parameters
+    ///
+    /// * `input_order` - if the cells should be ordered like the input points
+    pub fn input_order(mut self, input_order: bool) -> Self {
+        self.input_order = input_order;
+        self
+    }
+
+    /// Consumes the options and returns them with the sites shuffled by a random
+    /// number generator seeded with the specified seed after they have been sorted.
+    /// The shuffled order decides the order of the cells, the insertion order of the
+    /// triangulation and thereby how ties between cocircular sites are broken.
+    /// The output is deterministic without a seed as well, as the sites are always
+    /// sorted first, so the seed only selects a different order of the cells.
+    /// [`VoronoiOptions::sort_by`] still sorts the shuffled cells and the indices
+    /// of the sites are not affected. The seed cannot be combined with
+    /// [`VoronoiOptions::input_order`], which results in
+    /// [`VoronoiError::ConflictingSiteOrder`].
+    ///
+    /// # Parameters
+    ///
+    /// * `seed` - the seed of the random number generator
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Consumes the options and returns them with the cells sorted by the specified key
+    /// after the bound has been applied, e.g. to render the largest cells first.
+    /// Ties are broken by the site, first by the x- and then by the y-coordinate.
+    /// The neighbors are updated to the new cell order, while the indices of the
+    /// sites are kept. As sorting requires all cells, it is not applied by
+    /// [`compute_voronoi_iter_with_options`].
+    ///
+    /// # Parameters
+    ///
+    /// * `sort_key` - the key the cells are sorted by
+    pub fn sort_by(mut self, sort_key: SortKey) -> Self {
+        self.sort_by = Some(sort_key);
+        self
+    }
+
+    /// Consumes the options and returns them with the cells clipped to the convex hull
+    /// of the points expanded by the margin instead of the bound of the point set,
+    /// which does not need to be specified then.
+    /// In contrast to the bound, the hull is not centered around each site, so the cells
+    /// tile the hull. All sites lie within the hull, so the site placement has no effect,
+    /// and Lloyd relaxation moves the sites to the centroids of their cells within the hull.
+    /// Fails with [`VoronoiError::DegenerateHull`] if the hull has no area.
+    ///
+    /// # Parameters
+    ///
+    /// * `margin` - the distance the hull is expanded by
+    pub fn hull_bound(mut self, margin: f64) -> Self {
+        self.hull_bound = Some(margin);
+        self
+    }
+
+    /// Consumes the options and returns them with the number of threads the bound
+    /// is applied to the cells with in a local thread pool instead of the global
+    /// `rayon` thread pool. A single thread applies the bound sequentially,
+    /// while 0 threads let `rayon` choose the number of threads.
+    /// This only has an effect if the `rayon` feature is enabled.
+    ///
+    /// # Parameters
+    ///
+    /// * `threads` - the number of threads
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+}
+
+/// Computes the polygon-bound voronoi diagramm of the input point set
+/// using the default [`VoronoiOptions`].
+///
 /// # Parameters
-/// 
+///
 /// * `bounded_point_set` - the input point set and bounding geometry
 pub fn compute_voronoi<T: Borrow<BoundedPointSet>>(
     bounded_point_set: T,
-) -> Result<Vec<BoundedVoronoiCell>, &'static str> {
-    let bounded_point_set: &BoundedPointSet = bounded_point_set.borrow();
+) -> Result<Vec<BoundedVoronoiCell>, VoronoiError> {
+    compute_voronoi_with_options(bounded_point_set, VoronoiOptions::default())
+}
 
-    let bound = bounded_point_set.bounding_polygon()?;
-    let bound_bounds = Bounds::from_polygon(&bound).ok_or("The bounding polygon is invalid.")?;
-    let bound_point_set = Bounds::from_point_set(&bounded_point_set.point_set())
-        .ok_or("The point set does not contain enough valid points.")?;
+/// Computes the polygon-bound voronoi diagramm of the input point set
+/// using the default [`VoronoiOptions`] and returns an iterator,
+/// which applies the bound to the cells lazily.
+/// See [`compute_voronoi_iter_with_options`] for details.
+///
+/// # Parameters
+///
+/// * `bounded_point_set` - the input point set and bounding geometry
+pub fn compute_voronoi_iter<T: Borrow<BoundedPointSet>>(
+    bounded_point_set: T,
+) -> impl Iterator<Item = Result<BoundedVoronoiCell, VoronoiError>> {
+    compute_voronoi_iter_with_options(bounded_point_set, VoronoiOptions::default())
+}
 
-    let sites = bounded_point_set.voronoi_point_set();
-    let voronoi_digramm = VoronoiBuilder::default()
-        .set_sites(sites)
-        .set_bounding_box(BoundingBox::new(
-            voronoice::Point {
-                x: bound_point_set.centre_x(),
-                y: bound_point_set.centre_y(),
+/// Computes the polygon-bound voronoi diagramm of the input point set and returns
+/// an iterator, which applies the bound to the cells lazily in the same order
+/// as [`compute_voronoi_with_options`] unless [`VoronoiOptions::sort_by`] has been set.
+/// Only the unbounded diagramm is computed upfront, so that the bounded cells
+/// can be processed one by one without keeping all of them in memory.
+/// The bound is applied to small batches of cells with the threads set in
+/// [`VoronoiOptions::threads`] like in [`compute_voronoi_with_options`].
+/// If the diagramm cannot be computed, the iterator yields a single error.
+///
+/// # Parameters
+///
+/// * `bounded_point_set` - the input point set and bounding geometry
+/// * `options` - the options controlling the computation
+pub fn compute_voronoi_iter_with_options<T: Borrow<BoundedPointSet>>(
+    bounded_point_set: T,
+    options: VoronoiOptions,
+) -> impl Iterator<Item = Result<BoundedVoronoiCell, VoronoiError>> {
+    let (unbounded_cells, bound, threads, error) =
+        match compute_unbounded_cells(bounded_point_set.borrow(), &options).and_then(
+            |(unbounded_cells, bound, _)| {
+                Ok((unbounded_cells, bound, ClippingThreads::new(&options)?))
             },
-            bound_point_set.diff_x() + bound_bounds.diff_x(),
-            bound_point_set.diff_y() + bound_bounds.diff_y(),
-        ))
-        // .set_lloyd_relaxation_iterations(5) // This alters the initial sites.
-        .build()
-        .ok_or("No Voronoi diagramm could be built for the specified point set.")?;
+        ) {
+            Ok((unbounded_cells, bound, threads)) => (unbounded_cells, bound, Some(threads), None),
+            Err(error) => (Vec::new(), MultiPolygon::new(Vec::new()), None, Some(error)),
+        };
+    #[cfg(feature = "progress")]
+    let progress = progress_bar(unbounded_cells.len(), options.show_progress);
+    // The cells are clipped lazily, so the clipping time is summed up until the last batch.
+    let mut remaining_cells = unbounded_cells.into_iter();
+    let mut clipping_time = Duration::ZERO;
+    let batches = std::iter::from_fn(move || {
+        let batch: Vec<BoundedVoronoiCell> =
+            remaining_cells.by_ref().take(ITERATOR_BATCH_SIZE).collect();
+        if batch.is_empty() {
+            return None;
+        }
+        let start = Instant::now();
+        let bounded_cells = threads.as_ref()?.apply_bound(batch, &bound, &options, || {
+            #[cfg(feature = "progress")]
+            progress.inc(1);
+        });
+        clipping_time += start.elapsed();
+        if remaining_cells.len() == 0 {
+            #[cfg(feature = "progress")]
+            progress.finish_and_clear();
+            report_timing("clipping", clipping_time, options.show_timing);
+        }
+        Some(bounded_cells)
+    });
+    error.map(Err).into_iter().chain(batches.flatten())
+}
 
-    let cells = voronoi_digramm
-        .iter_cells()
-        .map(|cell| BoundedVoronoiCell {
-            site: voronoi_point_to_array(cell.site_position()),
-            cell: cell.iter_vertices().map(voronoi_point_to_array).collect(),
-        })
-        .try_fold(Vec::new(), |mut acc, cell| {
-            acc.push(cell.apply_bound(&bound)?);
-            Ok(acc)
-        })?;
-    Ok(cells)
+/// Computes the polygon-bound voronoi diagramm of the input point set.
+/// The cells are ordered by their original site, first by the x- and then by the y-coordinate,
+/// unless [`VoronoiOptions::input_order`] or [`VoronoiOptions::sort_by`] has been set.
+/// A single site or collinear sites result in cells bounded only by the
+/// perpendicular bisectors of neighboring sites and Lloyd relaxation is not applied.
+///
+/// # Parameters
+///
+/// * `bounded_point_set` - the input point set and bounding geometry
+/// * `options` - the options controlling the computation
+pub fn compute_voronoi_with_options<T: Borrow<BoundedPointSet>, U: Borrow<VoronoiOptions>>(
+    bounded_point_set: T,
+    options: U,
+) -> Result<Vec<BoundedVoronoiCell>, VoronoiError> {
+    compute_voronoi_with_report(bounded_point_set, options).map(|(cells, _)| cells)
 }
 
-/// Centers the polygon around the specified coordinates.
+/// Computes one polygon-bound voronoi diagramm per group of the input,
+/// which all share the bound of the input, in the order of the groups.
 ///
 /// # Parameters
 ///
-/// * `polygon` - the input polygon to center
-/// * `x` - the x-coordinate of the new center
-/// * `y` - the y-coordinate of the new center
-fn center_polygon<T: Borrow<Polygon>>(polygon: T, x: f64, y: f64) -> Result<Polygon, &'static str> {
-    let polygon: &Polygon = polygon.borrow();
-    let centre = polygon
-        .bounding_rect()
-        .ok_or("Invalid polygon. Cannot calculate bounding rectangle.")?
-        .center();
-    let dif_x = centre.x - x;
-    let dif_y = centre.y - y;
-    let points: Vec<(f64, f64)> = polygon
-        .exterior()
-        .points()
-        .map(|point| (point.x() - dif_x, point.y() - dif_y))
-        .collect();
-    Ok(Polygon::new(LineString::from(points), Vec::new()))
+/// * `input` - the groups of points and their shared bounding geometry
+/// * `options` - the options controlling the computation of each group
+pub fn compute_voronoi_groups<T: Borrow<MultiGroupInput>, U: Borrow<VoronoiOptions>>(
+    input: T,
+    options: U,
+) -> Result<Vec<Vec<BoundedVoronoiCell>>, VoronoiError> {
+    let options: &VoronoiOptions = options.borrow();
+    input
+        .borrow()
+        .point_sets()
+        .iter()
+        .map(|point_set| compute_voronoi_with_options(point_set, options))
+        .collect()
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-/// A finite Voronoi diagramm cell.
-pub struct BoundedVoronoiCell {
-    /// The original point.
-    site: [f64; 2],
-    /// The cell polygon.
-    cell: Vec<[f64; 2]>,
+/// Computes the polygon-bound voronoi diagramm of the input point set like
+/// [`compute_voronoi_with_options`] and additionally returns a report of the
+/// Lloyd relaxation that has been applied.
+///
+/// # Parameters
+///
+/// * `bounded_point_set` - the input point set and bounding geometry
+/// * `options` - the options controlling the computation
+pub fn compute_voronoi_with_report<T: Borrow<BoundedPointSet>, U: Borrow<VoronoiOptions>>(
+    bounded_point_set: T,
+    options: U,
+) -> Result<(Vec<BoundedVoronoiCell>, RelaxationReport), VoronoiError> {
+    let options: &VoronoiOptions = options.borrow();
+    let (unbounded_cells, bound, report) =
+        compute_unbounded_cells(bounded_point_set.borrow(), options)?;
+    let start = Instant::now();
+    let bounded_cells = apply_bound_to_cells(unbounded_cells, &bound, options)?;
+    report_timing("clipping", start.elapsed(), options.show_timing);
+    let bounded_cells = match options.sort_by {
+        Some(sort_key) => sort_cells(bounded_cells, sort_key),
+        None => bounded_cells,
+    };
+    Ok((bounded_cells, report))
 }
 
-/// Helper function to convert a [`Point`](voronoice::Point) to
-/// a two dimensional array.
-fn voronoi_point_to_array(point: &voronoice::Point) -> [f64; 2] {
-    [point.x, point.y]
+/// Sorts the cells by the specified key and updates the neighbors to the new indices.
+/// Ties are broken by the site, first by the x- and then by the y-coordinate.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells
+/// * `sort_key` - the key the cells are sorted by
+fn sort_cells(cells: Vec<BoundedVoronoiCell>, sort_key: SortKey) -> Vec<BoundedVoronoiCell> {
+    let areas: Vec<f64> = cells.iter().map(BoundedVoronoiCell::area).collect();
+    let compare_sites = |a: usize, b: usize| {
+        let (site_a, site_b) = (cells[a].site, cells[b].site);
+        match sort_key {
+            SortKey::SiteY => site_a[1]
+                .total_cmp(&site_b[1])
+                .then(site_a[0].total_cmp(&site_b[0])),
+            _ => site_a[0]
+                .total_cmp(&site_b[0])
+                .then(site_a[1].total_cmp(&site_b[1])),
+        }
+    };
+    let mut order: Vec<usize> = (0..cells.len()).collect();
+    order.sort_by(|&a, &b| match sort_key {
+        SortKey::Area => areas[b].total_cmp(&areas[a]).then(compare_sites(a, b)),
+        SortKey::AreaAscending => areas[a].total_cmp(&areas[b]).then(compare_sites(a, b)),
+        SortKey::SiteX | SortKey::SiteY => compare_sites(a, b),
+    });
+    // Maps the old cell indices to the sorted cell indices.
+    let mut cell_indices = vec![0; cells.len()];
+    for (new_index, &old_index) in order.iter().enumerate() {
+        cell_indices[old_index] = new_index;
+    }
+    let mut cells: Vec<Option<BoundedVoronoiCell>> = cells.into_iter().map(Some).collect();
+    order
+        .into_iter()
+        .filter_map(|old_index| cells[old_index].take())
+        .map(|mut cell| {
+            cell.neighbors = cell
+                .neighbors
+                .iter()
+                .filter_map(|&neighbor| cell_indices.get(neighbor).copied())
+                .collect();
+            cell.neighbors.sort_unstable();
+            cell
+        })
+        .collect()
 }
 
-impl BoundedVoronoiCell {
-    /// Consumes the cell and returns the cell with the specified bound applied.
-    ///
-    /// # Parameters
-    ///
-    /// * `bound` - the polygon to apply as bound
-    pub fn apply_bound<T: Borrow<Polygon>>(self, bound: T) -> Result<Self, &'static str> {
-        let centered_bound = center_polygon(bound, self.site[0], self.site[1])?;
-        let cell_polygon = Polygon::new(
-            LineString::from(
-                self.cell
-                    .into_iter()
-                    .map(|point| (point[0], point[1]))
-                    .collect::<Vec<(f64, f64)>>(),
-            ),
-            Vec::new(),
+/// Writes the wall time of a phase of the computation to stderr if requested.
+///
+/// # Parameters
+///
+/// * `phase` - the name of the phase
+/// * `duration` - the wall time of the phase
+/// * `show_timing` - if the wall time should be written
+fn report_timing(phase: &str, duration: Duration, show_timing: bool) {
+    if show_timing {
+        eprintln!("Timing: {} took {:?}.", phase, duration);
+    }
+}
+
+/// A report of the Lloyd relaxation applied to the sites and the bounding box
+/// the unbounded diagramm has been built in.
+#[derive(Clone, Copy, CopyGetters, Debug, Default, PartialEq)]
+pub struct RelaxationReport {
+    /// The number of Lloyd relaxation iterations that have been run.
+    #[getset(get_copy = "pub")]
+    iterations: usize,
+    /// The maximum displacement of a site in the last iteration if the relaxation
+    /// has been run until convergence.
+    #[getset(get_copy = "pub")]
+    max_displacement: Option<f64>,
+    /// The bounding box the unbounded diagramm has been built in, which contains the
+    /// point set and the bound centered around each site enlarged by a margin.
+    #[getset(get_copy = "pub")]
+    bounding_box: Option<Bounds>,
+}
+
+/// Computes the unbounded cells of the voronoi diagramm of the input point set
+/// and returns them together with the bound to apply and a report of the relaxation.
+/// Fails with [`VoronoiError::ConflictingSiteOrder`] if both a seed and the input
+/// order have been set, as the shuffled sites would discard the input order.
+///
+/// # Parameters
+///
+/// * `bounded_point_set` - the input point set and bounding geometry
+/// * `options` - the options controlling the computation
+fn compute_unbounded_cells(
+    bounded_point_set: &BoundedPointSet,
+    options: &VoronoiOptions,
+) -> Result<(Vec<BoundedVoronoiCell>, MultiPolygon, RelaxationReport), VoronoiError> {
+    if options.seed.is_some() && options.input_order {
+        return Err(VoronoiError::ConflictingSiteOrder);
+    }
+    let bound = match options.hull_bound {
+        Some(margin) => MultiPolygon::new(vec![bounded_point_set
+            .convex_hull(margin)
+            .ok_or(VoronoiError::DegenerateHull)?]),
+        None => bounded_point_set.bounding_multi_polygon()?,
+    };
+    // The sites are placed and relaxed within the original bound, but the cells
+    // are clipped to the inset one.
+    let clipping_bound = match options.inset {
+        Some(inset) => inset_bound(&bound, inset)?,
+        None => bound.clone(),
+    };
+    let bound_bounds = Bounds::from_multi_polygon(&bound).ok_or(VoronoiError::InvalidPolygon)?;
+    let start = Instant::now();
+    let point_set = match options.merge_tolerance {
+        Some(merge_tolerance) => bounded_point_set.merged_point_set(merge_tolerance),
+        None => bounded_point_set.point_set(),
+    };
+    if let Some(max_sites) = options.max_sites {
+        if point_set.len() > max_sites {
+            return Err(VoronoiError::TooManySites {
+                sites: point_set.len(),
+                max_sites,
+            });
+        }
+    }
+    // All sites lie within their convex hull.
+    let point_set = match options.hull_bound {
+        Some(_) => point_set,
+        None => place_sites(point_set, &bound, options.site_placement)?,
+    };
+    let bound_point_set = Bounds::from_point_set(&point_set).ok_or(VoronoiError::EmptyPointSet)?;
+    let diagramm_bounds = diagramm_bounds(bound_point_set, bound_bounds);
+    let point_labels = bounded_point_set.point_labels()?;
+    let point_properties = bounded_point_set.point_properties();
+    let fixed_points = bounded_point_set.fixed_points()?;
+
+    // Orders the sites, so that the cell order does not depend on the hash set order.
+    let mut point_set: Vec<Point2D> = if options.input_order {
+        bounded_point_set
+            .ordered_point_set()
+            .into_iter()
+            .filter(|site| point_set.contains(site))
+            .collect()
+    } else {
+        let mut point_set: Vec<Point2D> = point_set.into_iter().collect();
+        point_set.sort();
+        point_set
+    };
+    if let Some(seed) = options.seed {
+        shuffle_sites(&mut point_set, seed);
+    }
+    report_timing("deduplication", start.elapsed(), options.show_timing);
+    // The indices refer to all unique sites, so that they do not change if sites are dropped.
+    let site_indices: HashMap<Point2D, usize> = if options.include_index {
+        let sites = if options.input_order {
+            bounded_point_set.ordered_point_set()
+        } else {
+            bounded_point_set.sorted_point_set()
+        };
+        sites
+            .into_iter()
+            .enumerate()
+            .map(|(index, site)| (site, index))
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
+    // A single site or collinear sites cannot be triangulated, so their cells
+    // are computed directly as the strips between the perpendicular bisectors.
+    if are_collinear(&point_set) {
+        let start = Instant::now();
+        let power_sites: Vec<(Point2D, f64)> = point_set.iter().map(|&site| (site, 0.0)).collect();
+        let mut unbounded_cells = compute_power_cells(&power_sites, &diagramm_bounds);
+        report_timing("diagramm construction", start.elapsed(), options.show_timing);
+        let start = Instant::now();
+        for (voronoi_cell, site) in unbounded_cells.iter_mut().zip(&point_set) {
+            voronoi_cell.label = point_labels.get(site).cloned();
+            voronoi_cell.properties = point_properties.get(site).cloned();
+            voronoi_cell.index = site_indices.get(site).copied();
+        }
+        report_timing("cell iteration", start.elapsed(), options.show_timing);
+        let report = RelaxationReport {
+            bounding_box: Some(diagramm_bounds),
+            ..RelaxationReport::default()
+        };
+        return Ok((unbounded_cells, clipping_bound, report));
+    }
+
+    let start = Instant::now();
+    let sites: Vec<voronoice::Point> = point_set.iter().map(|&point| point.into()).collect();
+    // The relaxation of voronoice does not apply the bound, cannot weight the centroids
+    // and moves all sites.
+    let (voronoi_digramm, report) = match (options.lloyd_tolerance, &options.lloyd_density) {
+        (None, None) if fixed_points.is_empty() && options.hull_bound.is_none() => (
+            build_voronoi(sites.clone(), &diagramm_bounds, options.lloyd_iterations)?,
+            RelaxationReport {
+                iterations: options.lloyd_iterations,
+                ..RelaxationReport::default()
+            },
+        ),
+        _ => {
+            let fixed: Vec<bool> = point_set
+                .iter()
+                .map(|site| fixed_points.contains(site))
+                .collect();
+            let (relaxed_sites, report) =
+                relax_sites(sites.clone(), &fixed, &bound, &diagramm_bounds, options)?;
+            (build_voronoi(relaxed_sites, &diagramm_bounds, 0)?, report)
+        },
+    };
+    let report = RelaxationReport {
+        bounding_box: Some(diagramm_bounds),
+        ..report
+    };
+    report_timing("diagramm construction", start.elapsed(), options.show_timing);
+
+    let start = Instant::now();
+    let mut unbounded_cells = Vec::new();
+    for cell in voronoi_digramm.iter_cells() {
+        let mut voronoi_cell = BoundedVoronoiCell::new(
+            voronoi_point_to_array(cell.site_position()),
+            cell.iter_vertices().map(voronoi_point_to_array).collect(),
         );
-        // Creates intersections between bounding polygon and the voronoi cell
-        // and selects the intersection that actually contains the original point.
-        let mut bounded_cell = None;
-        let geo_site = geo::Point::new(self.site[0], self.site[1]);
-        for intersection in cell_polygon.intersection(&centered_bound) {
-            if intersection.contains(&geo_site) {
-                bounded_cell = Some(
-                    intersection
-                        .coords_iter()
-                        .map(|coordinate| [coordinate.x, coordinate.y])
-                        .collect(),
-                );
-                break;
+        // The cell order matches the site order, so the original site can be
+        // retrieved if the sites have been moved by relaxation.
+        if options.lloyd_iterations > 0 {
+            voronoi_cell.original_site = Some(voronoi_point_to_array(&sites[cell.site()]));
+        }
+        voronoi_cell.label = point_labels.get(&point_set[cell.site()]).cloned();
+        voronoi_cell.properties = point_properties.get(&point_set[cell.site()]).cloned();
+        voronoi_cell.index = site_indices.get(&point_set[cell.site()]).copied();
+        // Sites that would not produce a bounded cell have already been removed
+        // or result in an error, so the site indices match the cell indices.
+        voronoi_cell.neighbors = cell.iter_neighbors().collect();
+        unbounded_cells.push(voronoi_cell);
+    }
+    report_timing("cell iteration", start.elapsed(), options.show_timing);
+
+    Ok((unbounded_cells, clipping_bound, report))
+}
+
+/// Shuffles the sites with the Fisher-Yates algorithm driven by a SplitMix64
+/// random number generator, so that the order only depends on the seed.
+///
+/// # Parameters
+///
+/// * `sites` - the sites to shuffle
+/// * `seed` - the seed of the random number generator
+fn shuffle_sites(sites: &mut [Point2D], seed: u64) {
+    let mut state = seed;
+    for index in (1..sites.len()).rev() {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut random = state;
+        random = (random ^ (random >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        random = (random ^ (random >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        random ^= random >> 31;
+        sites.swap(index, (random % (index as u64 + 1)) as usize);
+    }
+}
+
+/// Builds the unbounded voronoi diagramm of the sites within the specified bounding box.
+///
+/// # Parameters
+///
+/// * `sites` - the sites of the diagramm
+/// * `diagramm_bounds` - the bounding box of the diagramm
+/// * `lloyd_iterations` - the number of Lloyd relaxation iterations applied by `voronoice`
+fn build_voronoi(
+    sites: Vec<voronoice::Point>,
+    diagramm_bounds: &Bounds,
+    lloyd_iterations: usize,
+) -> Result<voronoice::Voronoi, VoronoiError> {
+    VoronoiBuilder::default()
+        .set_sites(sites)
+        .set_bounding_box(BoundingBox::new(
+            voronoice::Point {
+                x: diagramm_bounds.centre_x(),
+                y: diagramm_bounds.centre_y(),
+            },
+            diagramm_bounds.diff_x(),
+            diagramm_bounds.diff_y(),
+        ))
+        .set_lloyd_relaxation_iterations(lloyd_iterations)
+        .build()
+        .ok_or(VoronoiError::BuilderFailed)
+}
+
+/// Moves the sites to the centroids of their bounded cells, which are weighted by the
+/// Lloyd density if any, until no site moves further than the Lloyd tolerance or the
+/// number of Lloyd iterations has been reached and returns the relaxed sites in the
+/// same order together with a report of the relaxation.
+/// Fixed sites and sites whose cell does not intersect the bound are not moved.
+///
+/// # Parameters
+///
+/// * `sites` - the sites to relax
+/// * `fixed` - the flags marking the fixed sites in the same order as the sites
+/// * `bound` - the bounding regions
+/// * `diagramm_bounds` - the bounding box of the diagramm
+/// * `options` - the options controlling the relaxation
+fn relax_sites(
+    mut sites: Vec<voronoice::Point>,
+    fixed: &[bool],
+    bound: &MultiPolygon,
+    diagramm_bounds: &Bounds,
+    options: &VoronoiOptions,
+) -> Result<(Vec<voronoice::Point>, RelaxationReport), VoronoiError> {
+    let tolerance = options.lloyd_tolerance.unwrap_or(0.0);
+    let density = options.lloyd_density.as_ref();
+    let mut report = RelaxationReport::default();
+    while report.iterations < options.lloyd_iterations {
+        let voronoi_digramm = build_voronoi(sites.clone(), diagramm_bounds, 0)?;
+        let mut max_displacement: f64 = 0.0;
+        let mut relaxed_sites = Vec::with_capacity(sites.len());
+        for cell in voronoi_digramm.iter_cells() {
+            let site = voronoi_point_to_array(cell.site_position());
+            if fixed[cell.site()] {
+                relaxed_sites.push(voronoice::Point {
+                    x: site[0],
+                    y: site[1],
+                });
+                continue;
             }
+            let unbounded_cell = BoundedVoronoiCell::new(
+                site,
+                cell.iter_vertices().map(voronoi_point_to_array).collect(),
+            );
+            // The convex hull is applied as it is instead of centered around the site.
+            let bounded_cell = match options.hull_bound {
+                Some(_) => unbounded_cell.clip_to(bound.clone(), None),
+                None => unbounded_cell.apply_multi_polygon_bound(bound),
+            };
+            let centroid = match bounded_cell {
+                Ok(bounded_cell) => {
+                    let polygon = bounded_cell.multi_polygon();
+                    density
+                        .and_then(|density| weighted_centroid(polygon, density))
+                        .or_else(|| {
+                            polygon
+                                .centroid()
+                                .map(|centroid| [centroid.x(), centroid.y()])
+                        })
+                        .unwrap_or(site)
+                },
+                Err(VoronoiError::NoIntersection { .. }) => site,
+                Err(error) => return Err(error),
+            };
+            max_displacement =
+                max_displacement.max((centroid[0] - site[0]).hypot(centroid[1] - site[1]));
+            relaxed_sites.push(voronoice::Point {
+                x: centroid[0],
+                y: centroid[1],
+            });
+        }
+        sites = relaxed_sites;
+        report.iterations += 1;
+        report.max_displacement = Some(max_displacement);
+        if max_displacement < tolerance {
+            break;
         }
+    }
+    Ok((sites, report))
+}
 
-        match bounded_cell {
-            Some(cell_points) => Ok(BoundedVoronoiCell {
-                site: self.site,
-                cell: cell_points,
-            }),
-            None => Err("No intersection could be found between the bound and the voronoi cell."),
+/// Returns the centroid of the polygon weighted by the density or [`None`]
+/// if the polygon does not contain any density.
+/// The polygon is triangulated and each triangle is divided into congruent
+/// sub-triangles, whose density is evaluated at their centroids.
+///
+/// # Parameters
+///
+/// * `polygon` - the polygon to compute the centroid of
+/// * `density` - the density weighting the centroid
+fn weighted_centroid(polygon: &MultiPolygon, density: &LloydDensity) -> Option<[f64; 2]> {
+    let (mut mass, mut moment_x, mut moment_y) = (0.0, 0.0, 0.0);
+    let n = DENSITY_SUBDIVISIONS;
+    for triangle in polygon.iter().flat_map(|piece| piece.earcut_triangles()) {
+        let [a, b, c] = triangle.to_array();
+        let area = triangle.unsigned_area() / (n * n) as f64;
+        // The sub-triangles pointing in the direction of a and the ones pointing away
+        // have their centroids at the barycentric offsets 1 / 3 and 2 / 3 of a grid cell.
+        let mut add = |i: usize, j: usize| {
+            let (s, t) = (i as f64 / (3 * n) as f64, j as f64 / (3 * n) as f64);
+            let location = [
+                a.x + s * (b.x - a.x) + t * (c.x - a.x),
+                a.y + s * (b.y - a.y) + t * (c.y - a.y),
+            ];
+            let weight = density.evaluate(location) * area;
+            mass += weight;
+            moment_x += weight * location[0];
+            moment_y += weight * location[1];
+        };
+        for i in 0..n {
+            for j in 0..(n - i) {
+                add(3 * i + 1, 3 * j + 1);
+                if i + j + 1 < n {
+                    add(3 * i + 2, 3 * j + 2);
+                }
+            }
         }
     }
+    (mass > 0.0).then(|| [moment_x / mass, moment_y / mass])
+}
+
+/// Returns `true` if the unique sites lie on a single line,
+/// which includes the case of less than 3 sites.
+///
+/// # Parameters
+///
+/// * `sites` - the unique sites to check
+fn are_collinear(sites: &[Point2D]) -> bool {
+    match sites {
+        [first, second, others @ ..] => others.iter().all(|other| {
+            (second.x() - first.x()) * (other.y() - first.y())
+                == (second.y() - first.y()) * (other.x() - first.x())
+        }),
+        _ => true,
+    }
+}
+
+/// A report of the degenerate cells found in the Voronoi diagramm.
+#[derive(Clone, CopyGetters, Debug, Getters, PartialEq)]
+pub struct DegenerateReport {
+    /// The number of cells that are not degenerate.
+    #[getset(get_copy = "pub")]
+    kept: usize,
+    /// The indices of the degenerate cells in the diagramm.
+    #[getset(get = "pub")]
+    indices: Vec<usize>,
+    /// The sites of the degenerate cells in the same order as the indices.
+    #[getset(get = "pub")]
+    sites: Vec<[f64; 2]>,
+}
+
+/// Returns a report of all cells with fewer than 3 distinct vertices or
+/// an area below the specified minimum, e.g. slivers created by clipping.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells
+/// * `min_area` - the area below which a cell is considered degenerate
+pub fn find_degenerate_cells(cells: &[BoundedVoronoiCell], min_area: f64) -> DegenerateReport {
+    let indices: Vec<usize> = cells
+        .iter()
+        .enumerate()
+        .filter(|(_, cell)| cell.is_degenerate(min_area))
+        .map(|(index, _)| index)
+        .collect();
+    DegenerateReport {
+        kept: cells.len() - indices.len(),
+        sites: indices.iter().map(|&index| cells[index].site()).collect(),
+        indices,
+    }
+}
+
+/// Removes all degenerate cells as defined by [`find_degenerate_cells`] and returns
+/// the remaining cells together with a report of the removed cells.
+/// The neighbors of the remaining cells are updated to their new indices
+/// and no longer contain the removed cells.
+///
+/// # Parameters
+///
+/// * `cells` - the Voronoi cells
+/// * `min_area` - the area below which a cell is considered degenerate
+pub fn drop_degenerate_cells(
+    cells: Vec<BoundedVoronoiCell>,
+    min_area: f64,
+) -> (Vec<BoundedVoronoiCell>, DegenerateReport) {
+    let report = find_degenerate_cells(&cells, min_area);
+    // Maps the old cell indices to the indices of the remaining cells.
+    let mut cell_indices = Vec::with_capacity(cells.len());
+    let mut cell_count = 0;
+    for index in 0..cells.len() {
+        if report.indices.binary_search(&index).is_ok() {
+            cell_indices.push(None);
+        } else {
+            cell_indices.push(Some(cell_count));
+            cell_count += 1;
+        }
+    }
+    let remaining_cells = cells
+        .into_iter()
+        .zip(cell_indices.iter())
+        .filter(|(_, new_index)| new_index.is_some())
+        .map(|(mut cell, _)| {
+            cell.neighbors = cell
+                .neighbors
+                .iter()
+                .filter_map(|&neighbor| cell_indices.get(neighbor).copied().flatten())
+                .collect();
+            cell
+        })
+        .collect();
+    (remaining_cells, report)
+}
+
+/// Computes the Delaunay triangulation of the input point set.
+/// The triangles are returned as index triples into the
+/// deduplicated point set as returned by [`BoundedPointSet::sorted_point_set`].
+/// The bounding geometry is not taken into account.
+///
+/// # Parameters
+///
+/// * `bounded_point_set` - the input point set
+pub fn compute_delaunay<T: Borrow<BoundedPointSet>>(
+    bounded_point_set: T,
+) -> Result<Vec<[usize; 3]>, VoronoiError> {
+    Ok(delaunay_triangles(&unclipped_voronoi(bounded_point_set.borrow())?))
+}
+
+/// Builds the Voronoi diagramm of the sorted point set without clipping its cells.
+/// Fails if the point set is empty or cannot be triangulated, e.g. if all points are collinear.
+///
+/// # Parameters
+///
+/// * `bounded_point_set` - the input point set
+fn unclipped_voronoi(
+    bounded_point_set: &BoundedPointSet,
+) -> Result<voronoice::Voronoi, VoronoiError> {
+    let sites: Vec<voronoice::Point> = bounded_point_set
+        .sorted_point_set()
+        .into_iter()
+        .map(|point| point.into())
+        .collect();
+    if sites.is_empty() {
+        return Err(VoronoiError::EmptyPointSet);
+    }
+    // Sites must not be removed, so that the indices match the sorted point set.
+    VoronoiBuilder::default()
+        .set_sites(sites)
+        .set_clip_behavior(ClipBehavior::None)
+        .build()
+        .ok_or(VoronoiError::BuilderFailed)
+}
+
+/// Returns the Delaunay triangles of the Voronoi diagramm as index triples into its sites.
+///
+/// # Parameters
+///
+/// * `voronoi_digramm` - the Voronoi diagramm
+fn delaunay_triangles(voronoi_digramm: &voronoice::Voronoi) -> Vec<[usize; 3]> {
+    voronoi_digramm
+        .triangulation()
+        .triangles
+        .chunks_exact(3)
+        .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+        .collect()
+}
+
+/// Computes the vertices of the Voronoi diagramm of the input point set, which are
+/// the circumcenters of the Delaunay triangles as returned by [`compute_delaunay`].
+/// Cocircular points result in several triangles with the same circumcenter, so
+/// vertices closer than a fraction of `1e-9` of the extent of the point set are merged.
+/// The vertices are sorted by their x- and then y-coordinate.
+/// If `within_bound` is set, only vertices within or on the bound centered around one
+/// of the sites of their triangle are kept, i.e. vertices whose Voronoi edges have
+/// not been clipped away completely. Otherwise all vertices are kept including vertices
+/// far away from the sites, which belong to nearly collinear triangles.
+/// Fails if the point set cannot be triangulated or the bound is invalid.
+///
+/// # Parameters
+///
+/// * `bounded_point_set` - the input point set and bounding geometry
+/// * `within_bound` - if only the vertices within the bound should be kept
+pub fn voronoi_vertices<T: Borrow<BoundedPointSet>>(
+    bounded_point_set: T,
+    within_bound: bool,
+) -> Result<Vec<[f64; 2]>, VoronoiError> {
+    let bounded_point_set = bounded_point_set.borrow();
+    let bound = if within_bound {
+        Some(bounded_point_set.bounding_multi_polygon()?)
+    } else {
+        None
+    };
+    let voronoi_digramm = unclipped_voronoi(bounded_point_set)?;
+    let sites = voronoi_digramm.sites();
+    let mut vertices = Vec::new();
+    // The first vertices of the diagramm are the circumcenters in the order of the triangles.
+    for (triangle, vertex) in delaunay_triangles(&voronoi_digramm)
+        .into_iter()
+        .zip(voronoi_digramm.vertices())
+    {
+        let is_kept = match &bound {
+            Some(bound) => triangle.iter().try_fold(false, |is_within, &site| {
+                Ok::<_, VoronoiError>(
+                    is_within
+                        || center_polygon(bound, sites[site].x, sites[site].y)?
+                            .intersects(&geo::Point::new(vertex.x, vertex.y)),
+                )
+            })?,
+            None => true,
+        };
+        if is_kept {
+            vertices.push(voronoi_point_to_array(vertex));
+        }
+    }
+    let extent = Bounds::from_point_set(bounded_point_set.point_set())
+        .map_or(0.0, |bounds| bounds.diff_x().max(bounds.diff_y()));
+    vertices.sort_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+    vertices.dedup_by(|a, b| (a[0] - b[0]).hypot(a[1] - b[1]) <= VERTEX_TOLERANCE * extent);
+    Ok(vertices)
+}
+
+/// An edge of the Delaunay triangulation as pair of its end points.
+pub type DelaunayEdge = ([f64; 2], [f64; 2]);
+
+/// Returns the unique edges of the Delaunay triangulation of the input point set
+/// as pairs of points, so that edges shared by two triangles are only contained once.
+/// The edges connect exactly the sites whose Voronoi cells are neighbors before
+/// the bound is applied. Each edge starts at the smaller point in the order of
+/// [`BoundedPointSet::sorted_point_set`] and the edges are sorted by their points.
+/// The bounding geometry is not taken into account.
+///
+/// # Parameters
+///
+/// * `bounded_point_set` - the input point set
+pub fn delaunay_edges<T: Borrow<BoundedPointSet>>(
+    bounded_point_set: T,
+) -> Result<Vec<DelaunayEdge>, VoronoiError> {
+    let bounded_point_set = bounded_point_set.borrow();
+    let triangles = compute_delaunay(bounded_point_set)?;
+    let sites = bounded_point_set.sorted_point_set();
+    let edges: BTreeSet<(usize, usize)> = triangles
+        .iter()
+        .flat_map(|triangle| {
+            [
+                (triangle[0], triangle[1]),
+                (triangle[1], triangle[2]),
+                (triangle[2], triangle[0]),
+            ]
+        })
+        .map(|(a, b)| (a.min(b), a.max(b)))
+        .collect();
+    Ok(edges
+        .into_iter()
+        .map(|(a, b)| ([sites[a].x(), sites[a].y()], [sites[b].x(), sites[b].y()]))
+        .collect())
+}
+
+/// Computes an approximation of the polygon-bound, multiplicatively weighted
+/// Voronoi diagramm of the input point set.
+/// A point belongs to the cell of the site with the smallest distance
+/// divided by the site's weight, so heavier sites claim more area.
+/// As the cell edges of this diagramm are circular arcs, the cells are
+/// approximated by sampling a regular grid spanning the point set and the bound.
+/// Each grid square is assigned to the site closest to the square's centre
+/// and the squares of each site are merged into the cell polygon before the
+/// bound is applied.
+/// Neighbors are sites owning adjacent grid squares.
+/// Only the outer ring of a cell is kept, so a light site enclosed by
+/// the cell of a heavier site does not cut a hole into that cell.
+///
+/// # Parameters
+///
+/// * `bounded_point_set` - the input point set, weights and bounding geometry
+/// * `resolution` - the number of grid squares along the longer side of the sampled area
+/// * `options` - the options controlling the application of the bound
+pub fn compute_weighted_voronoi<T: Borrow<BoundedPointSet>, U: Borrow<VoronoiOptions>>(
+    bounded_point_set: T,
+    resolution: usize,
+    options: U,
+) -> Result<Vec<BoundedVoronoiCell>, VoronoiError> {
+    let bounded_point_set: &BoundedPointSet = bounded_point_set.borrow();
+    if resolution == 0 {
+        return Err(VoronoiError::InvalidResolution);
+    }
+
+    let bound = bounded_point_set.bounding_multi_polygon()?;
+    let bound_bounds = Bounds::from_multi_polygon(&bound).ok_or(VoronoiError::InvalidPolygon)?;
+    let point_set = bounded_point_set.point_set();
+    let bound_point_set = Bounds::from_point_set(&point_set).ok_or(VoronoiError::EmptyPointSet)?;
+    let diagramm_bounds = diagramm_bounds(bound_point_set, bound_bounds);
+    let point_labels = bounded_point_set.point_labels()?;
+    let point_weights = bounded_point_set.point_weights()?;
+    let sites = bounded_point_set.sorted_point_set();
+
+    let unbounded_cells =
+        compute_raster_cells(&sites, &point_labels, &diagramm_bounds, resolution, |x, y| {
+            closest_weighted_site(&sites, &point_weights, x, y)
+        })?;
+
+    apply_bound_to_cells(unbounded_cells, &bound, options.borrow())
+}
+
+/// Computes an approximation of the polygon-bound Voronoi diagramm of the input
+/// point set under the Manhattan distance (L1 metric).
+/// A point belongs to the cell of the site with the smallest sum of the absolute
+/// coordinate differences, which results in cell edges consisting of axis-aligned
+/// and diagonal segments instead of perpendicular bisectors.
+/// The cells are approximated like the cells of [`compute_weighted_voronoi`]
+/// by sampling a regular grid spanning the point set and the bound, so the cell
+/// edges are accurate up to the size of a single grid square.
+///
+/// # Parameters
+///
+/// * `bounded_point_set` - the input point set and bounding geometry
+/// * `resolution` - the number of grid squares along the longer side of the sampled area
+/// * `options` - the options controlling the application of the bound
+pub fn compute_l1_voronoi<T: Borrow<BoundedPointSet>, U: Borrow<VoronoiOptions>>(
+    bounded_point_set: T,
+    resolution: usize,
+    options: U,
+) -> Result<Vec<BoundedVoronoiCell>, VoronoiError> {
+    let bounded_point_set: &BoundedPointSet = bounded_point_set.borrow();
+    if resolution == 0 {
+        return Err(VoronoiError::InvalidResolution);
+    }
+
+    let bound = bounded_point_set.bounding_multi_polygon()?;
+    let bound_bounds = Bounds::from_multi_polygon(&bound).ok_or(VoronoiError::InvalidPolygon)?;
+    let point_set = bounded_point_set.point_set();
+    let bound_point_set = Bounds::from_point_set(&point_set).ok_or(VoronoiError::EmptyPointSet)?;
+    let diagramm_bounds = diagramm_bounds(bound_point_set, bound_bounds);
+    let point_labels = bounded_point_set.point_labels()?;
+    let sites = bounded_point_set.sorted_point_set();
+
+    let unbounded_cells =
+        compute_raster_cells(&sites, &point_labels, &diagramm_bounds, resolution, |x, y| {
+            closest_l1_site(&sites, x, y)
+        })?;
+
+    apply_bound_to_cells(unbounded_cells, &bound, options.borrow())
+}
+
+/// Approximates the unbounded cells by sampling a regular grid spanning the
+/// diagramm bounds, assigning each grid square to the site closest to the
+/// square's centre and merging the squares of each site into its cell polygon.
+/// Neighbors are sites owning adjacent grid squares.
+/// Only the outer ring of a cell is kept.
+///
+/// # Parameters
+///
+/// * `sites` - the sorted, unique sites
+/// * `point_labels` - the labels of the sites
+/// * `diagramm_bounds` - the bounds of the sampled area
+/// * `resolution` - the number of grid squares along the longer side of the sampled area
+/// * `closest_site` - returns the index of the site closest to the specified coordinates
+fn compute_raster_cells<F: Fn(f64, f64) -> usize>(
+    sites: &[Point2D],
+    point_labels: &HashMap<Point2D, String>,
+    diagramm_bounds: &Bounds,
+    resolution: usize,
+    closest_site: F,
+) -> Result<Vec<BoundedVoronoiCell>, VoronoiError> {
+    // The sampled area matches the bounding box of the ordinary Voronoi diagramm.
+    let width = diagramm_bounds.diff_x();
+    let height = diagramm_bounds.diff_y();
+    let step = width.max(height) / resolution as f64;
+    let columns = (width / step).ceil().max(1.0) as usize;
+    let rows = (height / step).ceil().max(1.0) as usize;
+    let min_x = diagramm_bounds.centre_x() - columns as f64 * step / 2.0;
+    let min_y = diagramm_bounds.centre_y() - rows as f64 * step / 2.0;
+
+    let owners: Vec<usize> = (0..rows)
+        .flat_map(|row| (0..columns).map(move |column| (row, column)))
+        .map(|(row, column)| {
+            let x = min_x + (column as f64 + 0.5) * step;
+            let y = min_y + (row as f64 + 0.5) * step;
+            closest_site(x, y)
+        })
+        .collect();
+
+    // An ordered set keeps the neighbors in ascending order independent of hashing.
+    let mut neighbors = vec![BTreeSet::new(); sites.len()];
+    for row in 0..rows {
+        for column in 0..columns {
+            let owner = owners[row * columns + column];
+            let mut adjacent = Vec::with_capacity(2);
+            if column + 1 < columns {
+                adjacent.push(owners[row * columns + column + 1]);
+            }
+            if row + 1 < rows {
+                adjacent.push(owners[(row + 1) * columns + column]);
+            }
+            for other in adjacent {
+                if other != owner {
+                    neighbors[owner].insert(other);
+                    neighbors[other].insert(owner);
+                }
+            }
+        }
+    }
+
+    // Merges the consecutive squares of each row into rectangles.
+    let mut site_rectangles = vec![Vec::new(); sites.len()];
+    for row in 0..rows {
+        let mut run_start = 0;
+        for column in 1..=columns {
+            let owner = owners[row * columns + run_start];
+            if column == columns || owners[row * columns + column] != owner {
+                site_rectangles[owner].push(geo::Rect::new(
+                    geo::coord! { x: min_x + run_start as f64 * step, y: min_y + row as f64 * step },
+                    geo::coord! { x: min_x + column as f64 * step, y: min_y + (row + 1) as f64 * step },
+                ));
+                run_start = column;
+            }
+        }
+    }
+
+    let mut unbounded_cells = Vec::with_capacity(sites.len());
+    for (index, (site, rectangles)) in sites.iter().zip(site_rectangles).enumerate() {
+        let site_point = geo::Point::new(site.x(), site.y());
+        let sampled_cell = rectangles
+            .into_iter()
+            .fold(geo::MultiPolygon::new(Vec::new()), |cell, rectangle| {
+                cell.union(&geo::MultiPolygon::new(vec![rectangle.to_polygon()]))
+            });
+        // The site itself might lie on the edge of or outside its sampled squares,
+        // so the closest part of the sampled cell is selected.
+        let cell_polygon = sampled_cell
+            .into_iter()
+            .min_by(|a, b| {
+                a.euclidean_distance(&site_point)
+                    .total_cmp(&b.euclidean_distance(&site_point))
+            })
+            .ok_or(VoronoiError::NoIntersection {
+                site: [site.x(), site.y()],
+            })?;
+        let mut voronoi_cell = BoundedVoronoiCell::new(
+            [site.x(), site.y()],
+            cell_polygon
+                .exterior()
+                .coords_iter()
+                .map(|coordinate| [coordinate.x, coordinate.y])
+                .collect(),
+        );
+        voronoi_cell.label = point_labels.get(site).cloned();
+        voronoi_cell.neighbors = neighbors[index].iter().copied().collect();
+        voronoi_cell.neighbors.sort_unstable();
+        unbounded_cells.push(voronoi_cell);
+    }
+
+    Ok(unbounded_cells)
+}
+
+/// Returns the index of the site with the smallest weighted distance to the coordinates.
+///
+/// # Parameters
+///
+/// * `sites` - the sites to compare
+/// * `point_weights` - the weights of the sites
+/// * `x` - the x-coordinate
+/// * `y` - the y-coordinate
+fn closest_weighted_site(
+    sites: &[Point2D],
+    point_weights: &HashMap<Point2D, f64>,
+    x: f64,
+    y: f64,
+) -> usize {
+    sites
+        .iter()
+        .map(|site| {
+            let distance = (site.x() - x).hypot(site.y() - y);
+            distance / point_weights.get(site).copied().unwrap_or(1.0)
+        })
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(index, _)| index)
+        .unwrap_or_default()
+}
+
+/// Returns the index of the site with the smallest Manhattan distance to the coordinates.
+///
+/// # Parameters
+///
+/// * `sites` - the sites to compare
+/// * `x` - the x-coordinate
+/// * `y` - the y-coordinate
+fn closest_l1_site(sites: &[Point2D], x: f64, y: f64) -> usize {
+    sites
+        .iter()
+        .map(|site| (site.x() - x).abs() + (site.y() - y).abs())
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(index, _)| index)
+        .unwrap_or_default()
+}
+
+/// Computes the polygon-bound power diagramm (Laguerre diagramm) of the sites.
+/// A point belongs to the cell of the site with the smallest power distance,
+/// which is the squared distance to the site minus the squared radius of the site.
+/// Each site is lifted onto the paraboloid `z = x² + y² - r²`, so that the lower
+/// convex hull of the lifted sites projects onto the regular triangulation, which is
+/// dual to the power diagramm. The cell of a site is computed by clipping a box
+/// spanning the sites and the bound with the half-planes bounded by the radical axes
+/// to the sites adjacent to it in the lower hull.
+/// Sites whose cell is empty are omitted. In contrast to the ordinary Voronoi diagramm
+/// a site might not lie within its own cell, in which case the bound cannot be applied.
+/// If sites are duplicated, the radius of the first occurrence is used.
+///
+/// # Parameters
+///
+/// * `sites` - the sites of the diagramm
+/// * `radii` - the radii of the sites in the same order as the sites
+/// * `bound` - the polygon to apply as bound
+/// * `options` - the options controlling the application of the bound
+pub fn compute_power_diagram<T: Borrow<Polygon>, U: Borrow<VoronoiOptions>>(
+    sites: &[[f64; 2]],
+    radii: &[f64],
+    bound: T,
+    options: U,
+) -> Result<Vec<BoundedVoronoiCell>, VoronoiError> {
+    let bound = MultiPolygon::new(vec![bound.borrow().clone()]);
+    if sites.len() != radii.len() {
+        return Err(VoronoiError::RadiusCountMismatch {
+            radii: radii.len(),
+            points: sites.len(),
+        });
+    }
+    let mut unique_sites = HashSet::new();
+    let mut power_sites = Vec::new();
+    for (site, &radius) in sites.iter().zip(radii.iter()) {
+        if !radius.is_finite() || radius < 0.0 {
+            return Err(VoronoiError::InvalidRadius { radius });
+        }
+        if let Some(point) = Point2D::new(site[0], site[1]) {
+            if unique_sites.insert(point) {
+                power_sites.push((point, radius));
+            }
+        }
+    }
+
+    let bound_bounds = Bounds::from_multi_polygon(&bound).ok_or(VoronoiError::InvalidPolygon)?;
+    let bound_point_set =
+        Bounds::from_point_set(&unique_sites).ok_or(VoronoiError::EmptyPointSet)?;
+    let diagramm_bounds = diagramm_bounds(bound_point_set, bound_bounds);
+    let unbounded_cells = compute_power_cells(&power_sites, &diagramm_bounds);
+
+    apply_bound_to_cells(unbounded_cells, &bound, options.borrow())
+}
+
+/// Computes the unbounded power diagramm cells of the sites with their radii
+/// within the specified bounding box.
+/// The cell of each site is clipped by the radical axes to the sites adjacent to it
+/// in the lower convex hull of the sites lifted onto the paraboloid, which form the
+/// regular triangulation dual to the power diagramm. Sites that are not lifted onto
+/// the lower hull have an empty cell.
+/// Sites whose cell is empty are omitted and the neighbors refer to the
+/// indices of the remaining cells.
+///
+/// # Parameters
+///
+/// * `power_sites` - the unique sites paired with their radii
+/// * `diagramm_bounds` - the bounding box of the diagramm
+fn compute_power_cells(
+    power_sites: &[(Point2D, f64)],
+    diagramm_bounds: &Bounds,
+) -> Vec<BoundedVoronoiCell> {
+    let bounding_box: Vec<([f64; 2], Option<usize>)> = vec![
+        ([diagramm_bounds.min_x(), diagramm_bounds.min_y()], None),
+        ([diagramm_bounds.min_x(), diagramm_bounds.max_y()], None),
+        ([diagramm_bounds.max_x(), diagramm_bounds.max_y()], None),
+        ([diagramm_bounds.max_x(), diagramm_bounds.min_y()], None),
+    ];
+
+    // The sites are centered before lifting to reduce the rounding errors of the hull.
+    let site_count = power_sites.len() as f64;
+    let centre_x = power_sites.iter().map(|(site, _)| site.x()).sum::<f64>() / site_count;
+    let centre_y = power_sites.iter().map(|(site, _)| site.y()).sum::<f64>() / site_count;
+    let lifted_sites: Vec<[f64; 3]> = power_sites
+        .iter()
+        .map(|&(site, radius)| {
+            let (x, y) = (site.x() - centre_x, site.y() - centre_y);
+            [x, y, x * x + y * y - radius * radius]
+        })
+        .collect();
+    let hull_neighbors = lower_hull_neighbors(&lifted_sites);
+
+    let mut power_cells = Vec::with_capacity(power_sites.len());
+    for (index, &(site, radius)) in power_sites.iter().enumerate() {
+        // Only the sites adjacent in the lower hull bound the cell, while all sites
+        // are used if the lifted sites do not span a volume, e.g. if they are collinear.
+        let (mut cell, other_indices): (Vec<([f64; 2], Option<usize>)>, Vec<usize>) =
+            match &hull_neighbors {
+                Some(hull_neighbors) if hull_neighbors[index].is_empty() => {
+                    (Vec::new(), Vec::new())
+                },
+                Some(hull_neighbors) => (bounding_box.clone(), hull_neighbors[index].clone()),
+                None => (
+                    bounding_box.clone(),
+                    (0..power_sites.len())
+                        .filter(|&other_index| other_index != index)
+                        .collect(),
+                ),
+            };
+        for other_index in other_indices {
+            if cell.is_empty() {
+                break;
+            }
+            let (other_site, other_radius) = power_sites[other_index];
+            // The cell is the half-plane normal · x <= offset bounded by the radical axis.
+            let normal = [
+                2.0 * (other_site.x() - site.x()),
+                2.0 * (other_site.y() - site.y()),
+            ];
+            let offset = other_site.x().powi(2) + other_site.y().powi(2)
+                - site.x().powi(2)
+                - site.y().powi(2)
+                + radius.powi(2)
+                - other_radius.powi(2);
+            cell = clip_half_plane(&cell, normal, offset, other_index);
+        }
+        power_cells.push(cell);
+    }
+
+    // Maps the site indices to the indices of the non-empty cells.
+    let mut cell_indices = Vec::with_capacity(power_cells.len());
+    let mut cell_count = 0;
+    for cell in &power_cells {
+        if cell.is_empty() {
+            cell_indices.push(None);
+        } else {
+            cell_indices.push(Some(cell_count));
+            cell_count += 1;
+        }
+    }
+
+    let mut unbounded_cells = Vec::with_capacity(cell_count);
+    for (cell, &(site, _)) in power_cells.iter().zip(power_sites.iter()) {
+        if cell.is_empty() {
+            continue;
+        }
+        let mut voronoi_cell = BoundedVoronoiCell::new(
+            [site.x(), site.y()],
+            cell.iter().map(|&(vertex, _)| vertex).collect(),
+        );
+        // The site indices are mapped in ascending order, so the neighbors stay sorted.
+        voronoi_cell.neighbors = half_plane_neighbors(cell)
+            .into_iter()
+            .filter_map(|other_index| cell_indices[other_index])
+            .collect();
+        unbounded_cells.push(voronoi_cell);
+    }
+
+    unbounded_cells
+}
+
+/// Returns the sorted, unique indices of the sites that produced an edge of
+/// non-zero length of the polygon clipped by [`clip_half_plane`].
+///
+/// # Parameters
+///
+/// * `cell` - the clipped polygon with the site index of each edge
+pub(crate) fn half_plane_neighbors(cell: &[([f64; 2], Option<usize>)]) -> Vec<usize> {
+    let mut neighbors: Vec<usize> = cell
+        .iter()
+        .enumerate()
+        .filter(|&(vertex_index, &(vertex, _))| vertex != cell[(vertex_index + 1) % cell.len()].0)
+        .filter_map(|(_, &(_, edge))| edge)
+        .collect();
+    neighbors.sort_unstable();
+    neighbors.dedup();
+    neighbors
+}
+
+/// Computes the polygon-bound farthest-point Voronoi diagramm of the sites.
+/// A point belongs to the cell of the site that is farthest away from it, so the
+/// cell of a site lies on the opposite side of the point set and never contains
+/// the site itself unless it is the only site.
+/// Only the vertices of the convex hull of the sites have a non-empty cell, so
+/// the cells are computed by clipping a box spanning the sites and the bound with
+/// the half-planes bounded by the perpendicular bisectors to all other hull vertices.
+/// As the cells do not contain their site, the bound is not centered around the
+/// sites, but is intersected with each cell as it is, keeping all pieces of the
+/// intersection.
+/// The cells are ordered by their site, first by the x- and then by the y-coordinate.
+/// Sites in the interior of the convex hull or on its edges as well as sites whose
+/// cell does not intersect the bound result in an empty cell polygon.
+/// The neighbors refer to the cells sharing an edge before the bound is applied.
+///
+/// # Parameters
+///
+/// * `sites` - the sites of the diagramm
+/// * `bound` - the polygon to apply as bound
+pub fn compute_farthest_voronoi<T: Borrow<Polygon>>(
+    sites: &[[f64; 2]],
+    bound: T,
+) -> Result<Vec<BoundedVoronoiCell>, VoronoiError> {
+    let bound = MultiPolygon::new(vec![bound.borrow().clone()]);
+    let unique_sites: HashSet<Point2D> = Point2D::from_coordinates(sites.iter().copied())
+        .into_iter()
+        .collect();
+    let mut sorted_sites: Vec<Point2D> = unique_sites.iter().copied().collect();
+    sorted_sites.sort();
+
+    let bound_bounds = Bounds::from_multi_polygon(&bound).ok_or(VoronoiError::InvalidPolygon)?;
+    let bound_point_set =
+        Bounds::from_point_set(&unique_sites).ok_or(VoronoiError::EmptyPointSet)?;
+    let diagramm_bounds = diagramm_bounds(bound_point_set, bound_bounds);
+    let bounding_box: Vec<([f64; 2], Option<usize>)> = vec![
+        ([diagramm_bounds.min_x(), diagramm_bounds.min_y()], None),
+        ([diagramm_bounds.min_x(), diagramm_bounds.max_y()], None),
+        ([diagramm_bounds.max_x(), diagramm_bounds.max_y()], None),
+        ([diagramm_bounds.max_x(), diagramm_bounds.min_y()], None),
+    ];
+    let hull: HashSet<Point2D> = sorted_sites
+        .iter()
+        .map(|site| geo::Point::new(site.x(), site.y()))
+        .collect::<geo::MultiPoint>()
+        .convex_hull()
+        .exterior()
+        .coords()
+        .filter_map(|coordinate| Point2D::new(coordinate.x, coordinate.y))
+        .collect();
+    let hull_indices: Vec<usize> = (0..sorted_sites.len())
+        .filter(|&index| hull.contains(&sorted_sites[index]))
+        .collect();
+
+    let mut cells = Vec::with_capacity(sorted_sites.len());
+    for (index, site) in sorted_sites.iter().enumerate() {
+        let mut cell = Vec::new();
+        if hull.contains(site) {
+            cell = bounding_box.clone();
+            for &other_index in &hull_indices {
+                if other_index == index || cell.is_empty() {
+                    continue;
+                }
+                // The cell is the half-plane normal · x <= offset, where the other
+                // site is at least as close as the site itself.
+                let other_site = sorted_sites[other_index];
+                let normal = [
+                    2.0 * (site.x() - other_site.x()),
+                    2.0 * (site.y() - other_site.y()),
+                ];
+                let offset = site.x().powi(2) + site.y().powi(2)
+                    - other_site.x().powi(2)
+                    - other_site.y().powi(2);
+                cell = clip_half_plane(&cell, normal, offset, other_index);
+            }
+        }
+        let mut voronoi_cell = BoundedVoronoiCell::new(
+            [site.x(), site.y()],
+            cell.iter().map(|&(vertex, _)| vertex).collect(),
+        );
+        voronoi_cell.neighbors = half_plane_neighbors(&cell);
+        if !cell.is_empty() {
+            let cell_polygon = voronoi_cell.as_polygon().clone();
+            voronoi_cell = voronoi_cell
+                .clone()
+                .clip(cell_polygon, bound.clone(), |_| true)
+                .unwrap_or(BoundedVoronoiCell {
+                    cell: Vec::new(),
+                    polygons: PolygonCache::default(),
+                    ..voronoi_cell
+                });
+        }
+        cells.push(voronoi_cell);
+    }
+    Ok(cells)
+}
+
+/// Clips a convex polygon with the half-plane `normal · x <= offset`.
+/// Each vertex is paired with the index of the site that produced the edge
+/// from this vertex to the next one or [`None`] if the edge is part of the box.
+/// Returns an empty polygon if the polygon lies completely outside of the half-plane.
+///
+/// # Parameters
+///
+/// * `polygon` - the convex polygon to clip
+/// * `normal` - the normal of the half-plane pointing outwards
+/// * `offset` - the offset of the half-plane
+/// * `edge` - the site index to assign to the edge created by the clipping
+pub(crate) fn clip_half_plane(
+    polygon: &[([f64; 2], Option<usize>)],
+    normal: [f64; 2],
+    offset: f64,
+    edge: usize,
+) -> Vec<([f64; 2], Option<usize>)> {
+    let distance = |point: [f64; 2]| normal[0] * point[0] + normal[1] * point[1] - offset;
+    let mut clipped = Vec::with_capacity(polygon.len() + 1);
+    for (index, &(current, current_edge)) in polygon.iter().enumerate() {
+        let next = polygon[(index + 1) % polygon.len()].0;
+        let current_distance = distance(current);
+        let next_distance = distance(next);
+        let crossing = || {
+            let t = current_distance / (current_distance - next_distance);
+            [
+                current[0] + t * (next[0] - current[0]),
+                current[1] + t * (next[1] - current[1]),
+            ]
+        };
+        if current_distance <= 0.0 {
+            clipped.push((current, current_edge));
+            if next_distance > 0.0 {
+                clipped.push((crossing(), Some(edge)));
+            }
+        } else if next_distance <= 0.0 {
+            clipped.push((crossing(), current_edge));
+        }
+    }
+    if clipped.len() < 3 {
+        Vec::new()
+    } else {
+        clipped
+    }
+}
+
+/// Returns the bounds of the area the unbounded Voronoi diagramm is computed for.
+/// The area contains the point set, the bound itself and the bound centered around
+/// each site and is enlarged by a margin, so every site lies strictly inside of it
+/// regardless of how asymmetric the point set and the bound are.
+///
+/// # Parameters
+///
+/// * `point_set_bounds` - the bounds of the point set
+/// * `bound_bounds` - the bounds of the bounding polygon
+fn diagramm_bounds(point_set_bounds: Bounds, bound_bounds: Bounds) -> Bounds {
+    let bounds = point_set_bounds
+        .expand(bound_bounds.diff_x() / 2.0, bound_bounds.diff_y() / 2.0)
+        .union(bound_bounds);
+    let margin = DIAGRAMM_MARGIN * bounds.diff_x().max(bounds.diff_y());
+    bounds.expand(margin, margin)
+}
+
+/// Applies the bound to each cell with the threads requested in the options
+/// while preserving the cell order.
+/// Fails on the first cell the bound cannot be applied to.
+///
+/// # Parameters
+///
+/// * `cells` - the unbounded cells
+/// * `bound` - the bounding regions
+/// * `options` - the options controlling the computation
+fn apply_bound_to_cells(
+    cells: Vec<BoundedVoronoiCell>,
+    bound: &MultiPolygon,
+    options: &VoronoiOptions,
+) -> Result<Vec<BoundedVoronoiCell>, VoronoiError> {
+    let threads = ClippingThreads::new(options)?;
+    #[cfg(feature = "progress")]
+    let progress = progress_bar(cells.len(), options.show_progress);
+    let bounded_cells = threads.apply_bound(cells, bound, options, || {
+        #[cfg(feature = "progress")]
+        progress.inc(1);
+    });
+    #[cfg(feature = "progress")]
+    progress.finish_and_clear();
+    bounded_cells.into_iter().collect()
+}
+
+/// The threads the bound is applied to the cells with.
+#[cfg(feature = "rayon")]
+enum ClippingThreads {
+    /// The cells are clipped one after another in the current thread.
+    Sequential,
+    /// The cells are clipped in parallel in the current thread pool.
+    Current,
+    /// The cells are clipped in parallel in a local thread pool.
+    Local(rayon::ThreadPool),
+}
+
+/// The threads the bound is applied to the cells with, which is always
+/// the current thread without the `rayon` feature.
+#[cfg(not(feature = "rayon"))]
+struct ClippingThreads;
+
+impl ClippingThreads {
+    /// Returns the threads for the number of threads set in the options
+    /// and builds a local thread pool if necessary.
+    ///
+    /// # Parameters
+    ///
+    /// * `options` - the options controlling the computation
+    #[cfg(feature = "rayon")]
+    fn new(options: &VoronoiOptions) -> Result<Self, VoronoiError> {
+        match options.threads {
+            Some(1) => Ok(Self::Sequential),
+            Some(threads) => rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .map(Self::Local)
+                .map_err(|_| VoronoiError::ThreadPoolFailed),
+            None => Ok(Self::Current),
+        }
+    }
+
+    /// Returns the current thread, as the number of threads set in the options
+    /// only has an effect with the `rayon` feature.
+    ///
+    /// # Parameters
+    ///
+    /// * `options` - the options controlling the computation
+    #[cfg(not(feature = "rayon"))]
+    fn new(_options: &VoronoiOptions) -> Result<Self, VoronoiError> {
+        Ok(Self)
+    }
+
+    /// Applies the bound to each cell while preserving the cell order and returns
+    /// the result for each cell.
+    ///
+    /// # Parameters
+    ///
+    /// * `cells` - the unbounded cells
+    /// * `bound` - the bounding regions
+    /// * `options` - the options controlling the computation
+    /// * `on_cell` - called once for each cell, e.g. to advance a progress bar
+    fn apply_bound<F: Fn() + Sync>(
+        &self,
+        cells: Vec<BoundedVoronoiCell>,
+        bound: &MultiPolygon,
+        options: &VoronoiOptions,
+        on_cell: F,
+    ) -> Vec<Result<BoundedVoronoiCell, VoronoiError>> {
+        let apply = |cell| {
+            on_cell();
+            apply_bound_to_cell(cell, bound, options)
+        };
+        #[cfg(feature = "rayon")]
+        match self {
+            Self::Sequential => cells.into_iter().map(apply).collect(),
+            Self::Current => cells.into_par_iter().map(apply).collect(),
+            Self::Local(pool) => pool.install(|| cells.into_par_iter().map(apply).collect()),
+        }
+        #[cfg(not(feature = "rayon"))]
+        cells.into_iter().map(apply).collect()
+    }
+}
+
+/// Returns a progress bar for the specified number of cells, which is hidden
+/// unless requested, the number of cells exceeds [`PROGRESS_THRESHOLD`]
+/// and stderr is a terminal.
+///
+/// # Parameters
+///
+/// * `length` - the number of cells
+/// * `show_progress` - if a progress bar has been requested
+#[cfg(feature = "progress")]
+fn progress_bar(length: usize, show_progress: bool) -> indicatif::ProgressBar {
+    use std::io::IsTerminal;
+
+    if show_progress && length > PROGRESS_THRESHOLD && std::io::stderr().is_terminal() {
+        indicatif::ProgressBar::new(length as u64)
+    } else {
+        indicatif::ProgressBar::hidden()
+    }
+}
+
+/// Applies the bound to a single cell and stores its area and unbounded polygon
+/// as well as closes its rings if requested.
+///
+/// # Parameters
+///
+/// * `cell` - the unbounded cell
+/// * `bound` - the bounding regions
+/// * `options` - the options controlling the computation
+fn apply_bound_to_cell(
+    cell: BoundedVoronoiCell,
+    bound: &MultiPolygon,
+    options: &VoronoiOptions,
+) -> Result<BoundedVoronoiCell, VoronoiError> {
+    let unbounded_cell = options.include_unbounded.then(|| cell.cell.clone());
+    let empty_cell =
+        (options.site_placement == SitePlacement::KeepEmpty).then(|| BoundedVoronoiCell {
+            cell: Vec::new(),
+            cells: Vec::new(),
+            holes: Vec::new(),
+            polygons: PolygonCache::default(),
+            ..cell.clone()
+        });
+    let clipped_cell = match (options.hull_bound, options.snap_precision) {
+        // The convex hull is applied as it is instead of centered around the site.
+        (Some(_), precision) => cell.clip_to(bound.clone(), precision),
+        (None, Some(precision)) => cell.apply_snapped_multi_polygon_bound(bound, precision),
+        (None, None) => cell.apply_multi_polygon_bound(bound),
+    };
+    let mut bounded_cell = match (clipped_cell, empty_cell) {
+        (Err(VoronoiError::NoIntersection { .. }), Some(empty_cell)) => empty_cell,
+        (clipped_cell, _) => clipped_cell?,
+    };
+    bounded_cell.unbounded_cell = unbounded_cell;
+    if options.include_area {
+        bounded_cell = bounded_cell.with_area();
+    }
+    Ok(if options.close_rings {
+        bounded_cell.with_closed_rings()
+    } else {
+        bounded_cell
+    })
+}
+
+/// Shrinks the bound inwards by the specified distance by offsetting each edge of its
+/// rings towards the inside of the bound, so that holes grow accordingly.
+/// Convex corners stay sharp, while reflex corners are mitered instead of rounded,
+/// which keeps all locations at least the distance away from the boundary.
+/// If the distance exceeds the width of a part of the bound, edges collapse and
+/// the offset rings intersect themselves or each other, e.g. at a narrow concavity,
+/// which cannot be resolved into a valid bound and results in an error.
+/// Non-positive or non-finite distances return the bound unchanged.
+///
+/// # Parameters
+///
+/// * `bound` - the bounding regions
+/// * `distance` - the distance the bound is shrunk inwards by
+pub fn inset_bound<T: Borrow<MultiPolygon>>(
+    bound: T,
+    distance: f64,
+) -> Result<MultiPolygon, VoronoiError> {
+    let bound = bound.borrow();
+    if !distance.is_finite() || distance <= 0.0 {
+        return Ok(bound.clone());
+    }
+    // The inside of the bound lies left of each edge of consistently oriented rings.
+    let inset: MultiPolygon = bound
+        .orient(Direction::Default)
+        .iter()
+        .map(|polygon| {
+            Polygon::new(
+                inset_ring(polygon.exterior(), distance),
+                polygon
+                    .interiors()
+                    .iter()
+                    .map(|hole| inset_ring(hole, distance))
+                    .collect(),
+            )
+        })
+        .collect();
+    let rings: Vec<&LineString> = inset
+        .iter()
+        .flat_map(|polygon| std::iter::once(polygon.exterior()).chain(polygon.interiors()))
+        .collect();
+    let is_invalid = rings
+        .iter()
+        .any(|ring| ring.0.is_empty() || find_self_intersection(*ring).is_some())
+        || rings
+            .iter()
+            .enumerate()
+            .any(|(i, ring)| rings[i + 1..].iter().any(|other| ring.intersects(*other)));
+    if is_invalid {
+        Err(VoronoiError::InvalidInset { distance })
+    } else {
+        Ok(inset)
+    }
+}
+
+/// Offsets each edge of the ring to its left by the distance and returns the ring
+/// connecting the intersections of the offset edges or an empty ring if an edge
+/// has collapsed, i.e. its direction has been reversed.
+///
+/// # Parameters
+///
+/// * `ring` - the closed ring to offset
+/// * `distance` - the offset distance
+fn inset_ring(ring: &LineString, distance: f64) -> LineString {
+    let edges: Vec<geo::Line> = ring.lines().filter(|line| line.start != line.end).collect();
+    let offset_edges: Vec<geo::Line> = edges
+        .iter()
+        .map(|edge| {
+            let length = edge.euclidean_length();
+            let normal = geo::coord! {
+                x: -edge.dy() / length * distance,
+                y: edge.dx() / length * distance,
+            };
+            geo::Line::new(edge.start + normal, edge.end + normal)
+        })
+        .collect();
+    let vertices: Vec<geo::Coord> = (0..offset_edges.len())
+        .map(|i| {
+            let previous = offset_edges[(i + offset_edges.len() - 1) % offset_edges.len()];
+            line_intersection(previous, offset_edges[i]).unwrap_or(offset_edges[i].start)
+        })
+        .collect();
+    let has_collapsed = edges.iter().enumerate().any(|(i, edge)| {
+        let offset_edge = vertices[(i + 1) % vertices.len()] - vertices[i];
+        edge.dx() * offset_edge.x + edge.dy() * offset_edge.y < 0.0
+    });
+    if has_collapsed {
+        LineString::new(Vec::new())
+    } else {
+        LineString::from(vertices)
+    }
+}
+
+/// Returns the intersection of the infinite lines through the segments
+/// or [`None`] if they are parallel.
+///
+/// # Parameters
+///
+/// * `a` - the first segment
+/// * `b` - the second segment
+fn line_intersection(a: geo::Line, b: geo::Line) -> Option<geo::Coord> {
+    let denominator = a.dx() * b.dy() - a.dy() * b.dx();
+    if denominator.abs() <= f64::EPSILON * a.euclidean_length() * b.euclidean_length() {
+        return None;
+    }
+    let t = ((b.start.x - a.start.x) * b.dy() - (b.start.y - a.start.y) * b.dx()) / denominator;
+    Some(a.start + (a.end - a.start) * t)
+}
+
+/// Applies the site placement behaviour to the point set and returns the
+/// remaining sites.
+///
+/// # Parameters
+///
+/// * `point_set` - the sites to check
+/// * `bound` - the bounding regions
+/// * `site_placement` - the behaviour for sites outside of the bound
+fn place_sites<T: Borrow<MultiPolygon>>(
+    point_set: HashSet<Point2D>,
+    bound: T,
+    site_placement: SitePlacement,
+) -> Result<HashSet<Point2D>, VoronoiError> {
+    if matches!(site_placement, SitePlacement::Keep | SitePlacement::KeepEmpty) {
+        return Ok(point_set);
+    }
+    let bound: &MultiPolygon = bound.borrow();
+    let mut placed_sites = HashSet::new();
+    for site in point_set {
+        if is_site_within_bound(site, bound)? {
+            placed_sites.insert(site);
+        } else if site_placement == SitePlacement::Error {
+            return Err(VoronoiError::SiteOutsideBound {
+                site: [site.x(), site.y()],
+            });
+        }
+    }
+    if placed_sites.is_empty() {
+        Err(VoronoiError::NoSiteWithinBound)
+    } else {
+        Ok(placed_sites)
+    }
+}
+
+/// Returns `true` if the site lies within or on the border of the bound centered around it.
+///
+/// # Parameters
+///
+/// * `site` - the site to check
+/// * `bound` - the bounding regions
+fn is_site_within_bound<T: Borrow<MultiPolygon>>(
+    site: Point2D,
+    bound: T,
+) -> Result<bool, VoronoiError> {
+    let centered_bound = center_polygon(bound.borrow(), site.x(), site.y())?;
+    Ok(centered_bound.intersects(&geo::Point::new(site.x(), site.y())))
+}
+
+/// Centers the polygon or multi-polygon including its interiors around the
+/// specified coordinates based on the centre of its bounding rectangle.
+///
+/// # Parameters
+///
+/// * `polygon` - the input polygon to center
+/// * `x` - the x-coordinate of the new center
+/// * `y` - the y-coordinate of the new center
+pub(crate) fn center_polygon<G>(polygon: &G, x: f64, y: f64) -> Result<G, VoronoiError>
+where
+    G: BoundingRect<f64, Output = Option<geo::Rect>> + Translate<f64>,
+{
+    let centre = polygon
+        .bounding_rect()
+        .ok_or(VoronoiError::InvalidPolygon)?
+        .center();
+    Ok(polygon.translate(x - centre.x, y - centre.y))
+}
+
+#[derive(Clone, CopyGetters, Debug, Deserialize, Getters, Serialize)]
+/// A finite Voronoi diagramm cell.
+pub struct BoundedVoronoiCell {
+    /// The original point or the relaxed point if Lloyd relaxation has been applied.
+    #[getset(get_copy = "pub")]
+    site: [f64; 2],
+    /// The original point if Lloyd relaxation has been applied.
+    #[getset(get_copy = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    original_site: Option<[f64; 2]>,
+    /// The label of the original point if labels have been specified.
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    /// The properties of the original point if it has been specified with properties.
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    properties: Option<PointProperties>,
+    /// The index of the site in the unique, ordered point set if it has been requested.
+    /// It is optional like the area, so that it is only serialized if it has been
+    /// requested with [`VoronoiOptions::include_index`].
+    #[getset(get_copy = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    index: Option<usize>,
+    /// The cell polygon.
+    #[getset(get = "pub")]
+    cell: Vec<[f64; 2]>,
+    /// All pieces of the cell polygon starting with the piece containing the site
+    /// if the bound splits the cell into multiple pieces.
+    #[serde(default, skip_serializing_if = "is_single_piece")]
+    cells: Vec<Vec<[f64; 2]>>,
+    /// The interior rings of all pieces of the cell polygon, which result from
+    /// holes of the bound that lie within the cell.
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    holes: Vec<Vec<[f64; 2]>>,
+    /// The indices of the neighboring cells, which share a Voronoi edge
+    /// with this cell before the bound is applied.
+    #[getset(get = "pub")]
+    #[serde(default)]
+    neighbors: Vec<usize>,
+    /// The area of the cell polygon if it has been requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    area: Option<f64>,
+    /// The cell polygon before the bound has been applied if it has been requested.
+    #[getset(get = "pub")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unbounded_cell: Option<Vec<[f64; 2]>>,
+    /// The polygons built from the rings of the cell on first use.
+    #[serde(skip)]
+    polygons: PolygonCache,
+}
+
+/// A cell serialized in the same structure as [`BoundedVoronoiCell`], but with each
+/// coordinate represented as [`Coordinate`] object instead of an array.
+#[derive(Serialize)]
+pub(crate) struct CoordinateObjectCell<'a> {
+    /// The original point or the relaxed point if Lloyd relaxation has been applied.
+    site: Coordinate,
+    /// The original point if Lloyd relaxation has been applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    original_site: Option<Coordinate>,
+    /// The label of the original point if labels have been specified.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<&'a String>,
+    /// The properties of the original point if it has been specified with properties.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    properties: Option<&'a PointProperties>,
+    /// The index of the site in the unique, ordered point set if it has been requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<usize>,
+    /// The cell polygon.
+    cell: Vec<Coordinate>,
+    /// All pieces of the cell polygon if the bound splits the cell into multiple pieces.
+    #[serde(skip_serializing_if = "is_single_piece")]
+    cells: Vec<Vec<Coordinate>>,
+    /// The interior rings of all pieces of the cell polygon.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    holes: Vec<Vec<Coordinate>>,
+    /// The indices of the neighboring cells.
+    neighbors: &'a [usize],
+    /// The area of the cell polygon if it has been requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    area: Option<f64>,
+    /// The cell polygon before the bound has been applied if it has been requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unbounded_cell: Option<Vec<Coordinate>>,
+}
+
+/// The polygons of a cell, which are built lazily from its rings
+/// and have to be reset whenever the rings change.
+#[derive(Clone, Debug, Default)]
+struct PolygonCache {
+    /// The cell polygon with the holes that lie within it.
+    polygon: OnceLock<Polygon>,
+    /// All pieces of the cell polygon with the holes that lie within them.
+    multi_polygon: OnceLock<MultiPolygon>,
+}
+
+impl From<&BoundedVoronoiCell> for Polygon {
+    fn from(cell: &BoundedVoronoiCell) -> Self {
+        cell.to_polygon()
+    }
+}
+
+/// Helper function to convert a [`Point`](voronoice::Point) to
+/// a two dimensional array.
+fn voronoi_point_to_array(point: &voronoice::Point) -> [f64; 2] {
+    [point.x, point.y]
+}
+
+impl BoundedVoronoiCell {
+    /// Creates a new Voronoi cell.
+    ///
+    /// # Parameters
+    ///
+    /// * `site` - the original point
+    /// * `cell` - the points of the cell polygon
+    pub fn new(site: [f64; 2], cell: Vec<[f64; 2]>) -> Self {
+        Self {
+            site,
+            original_site: None,
+            label: None,
+            properties: None,
+            index: None,
+            cell,
+            cells: Vec::new(),
+            holes: Vec::new(),
+            neighbors: Vec::new(),
+            area: None,
+            unbounded_cell: None,
+            polygons: PolygonCache::default(),
+        }
+    }
+
+    /// Creates a new Voronoi cell from all pieces of the cell polygon starting with
+    /// the piece containing the site.
+    ///
+    /// # Parameters
+    ///
+    /// * `site` - the original point
+    /// * `pieces` - the exterior rings of all pieces of the cell polygon
+    /// * `holes` - the interior rings of all pieces of the cell polygon
+    /// * `neighbors` - the indices of the neighboring cells
+    pub(crate) fn from_pieces(
+        site: [f64; 2],
+        pieces: Vec<Vec<[f64; 2]>>,
+        holes: Vec<Vec<[f64; 2]>>,
+        neighbors: Vec<usize>,
+    ) -> Self {
+        Self {
+            cell: pieces.first().cloned().unwrap_or_default(),
+            cells: pieces,
+            holes,
+            neighbors,
+            ..Self::new(site, Vec::new())
+        }
+    }
+
+    /// Returns the rings of all pieces of the cell polygon starting with the
+    /// piece containing the site.
+    /// Unless the bound splits the cell, this is only the cell polygon itself.
+    pub fn cells(&self) -> Vec<Vec<[f64; 2]>> {
+        if self.cells.is_empty() {
+            vec![self.cell.clone()]
+        } else {
+            self.cells.clone()
+        }
+    }
+
+    /// Returns a view of the cell, which is serialized in the same structure as the cell,
+    /// but with each coordinate represented as [`Coordinate`] object instead of an array.
+    pub(crate) fn coordinate_objects(&self) -> CoordinateObjectCell<'_> {
+        CoordinateObjectCell {
+            site: self.site.into(),
+            original_site: self.original_site.map(Coordinate::from),
+            label: self.label.as_ref(),
+            properties: self.properties.as_ref(),
+            index: self.index,
+            cell: coordinate_ring(&self.cell),
+            cells: self
+                .cells
+                .iter()
+                .map(|piece| coordinate_ring(piece))
+                .collect(),
+            holes: self
+                .holes
+                .iter()
+                .map(|hole| coordinate_ring(hole))
+                .collect(),
+            neighbors: &self.neighbors,
+            area: self.area,
+            unbounded_cell: self.unbounded_cell.as_deref().map(coordinate_ring),
+        }
+    }
+
+    /// Returns the area of all pieces of the cell polygon.
+    /// The area is calculated with the shoelace formula, which also handles
+    /// concave polygons correctly.
+    pub fn area(&self) -> f64 {
+        self.multi_polygon().unsigned_area()
+    }
+
+    /// Returns the Polsby-Popper compactness `4 * pi * area / perimeter^2` of the cell
+    /// polygon, which is 1 for a circle and approaches 0 for elongated or frayed cells,
+    /// e.g. about 0.785 for a square.
+    /// Returns NaN for a cell without a perimeter.
+    pub fn compactness(&self) -> f64 {
+        let perimeter = self.perimeter();
+        if perimeter > 0.0 {
+            4.0 * std::f64::consts::PI * self.area() / perimeter.powi(2)
+        } else {
+            f64::NAN
+        }
+    }
+
+    /// Returns the perimeter of all pieces of the cell polygon.
+    /// Edges along the bound including the holes are included and the closing
+    /// edge is taken into account even if the ring is not closed explicitly.
+    pub fn perimeter(&self) -> f64 {
+        self.multi_polygon()
+            .iter()
+            .flat_map(|piece| std::iter::once(piece.exterior()).chain(piece.interiors()))
+            .map(|ring| ring.euclidean_length())
+            .sum()
+    }
+
+    /// Returns the number of vertices of the exterior rings of all pieces of the cell
+    /// polygon. The first vertex repeated at the end to close a ring is not counted.
+    pub fn vertex_count(&self) -> usize {
+        let ring_vertex_count = |ring: &Vec<[f64; 2]>| match (ring.first(), ring.last()) {
+            (Some(first), Some(last)) if ring.len() > 1 && first == last => ring.len() - 1,
+            _ => ring.len(),
+        };
+        if self.cells.is_empty() {
+            ring_vertex_count(&self.cell)
+        } else {
+            self.cells.iter().map(ring_vertex_count).sum()
+        }
+    }
+
+    /// Returns the centroid of all pieces of the cell polygon or [`None`]
+    /// if the cell is degenerate and does not cover any area.
+    pub fn centroid(&self) -> Option<[f64; 2]> {
+        let polygon = self.multi_polygon();
+        if polygon.unsigned_area() > 0.0 {
+            polygon
+                .centroid()
+                .map(|centroid| [centroid.x(), centroid.y()])
+        } else {
+            None
+        }
+    }
+
+    /// Consumes the cell and returns it with its area stored for serialization.
+    pub fn with_area(self) -> Self {
+        Self {
+            area: Some(self.area()),
+            ..self
+        }
+    }
+
+    /// Consumes the cell and returns it with all coordinates transformed by the
+    /// specified function, which fails on the first coordinate that cannot be transformed.
+    /// The neighbors and a stored area are kept unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// * `transform` - the transformation applied to each coordinate
+    pub fn try_map_coords<E, F: Fn([f64; 2]) -> Result<[f64; 2], E>>(
+        self,
+        transform: F,
+    ) -> Result<Self, E> {
+        let map_ring = |ring: Vec<[f64; 2]>| -> Result<Vec<[f64; 2]>, E> {
+            ring.into_iter().map(&transform).collect()
+        };
+        Ok(Self {
+            site: transform(self.site)?,
+            original_site: self.original_site.map(&transform).transpose()?,
+            cell: map_ring(self.cell)?,
+            cells: self
+                .cells
+                .into_iter()
+                .map(map_ring)
+                .collect::<Result<_, _>>()?,
+            holes: self
+                .holes
+                .into_iter()
+                .map(map_ring)
+                .collect::<Result<_, _>>()?,
+            unbounded_cell: self.unbounded_cell.map(map_ring).transpose()?,
+            polygons: PolygonCache::default(),
+            ..self
+        })
+    }
+
+    /// Consumes the cell and returns it with all coordinates including the sites
+    /// rounded to the specified number of decimal places.
+    /// Rounding may collapse small cells into a degenerate shape, which can be
+    /// detected with [`BoundedVoronoiCell::is_degenerate`].
+    /// The neighbors and a stored area are kept unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// * `decimals` - the number of decimal places to round to
+    pub fn with_rounded_coords(self, decimals: u32) -> Self {
+        let Ok(cell) = self.try_map_coords(|[x, y]| {
+            Ok::<_, Infallible>([
+                round_to_decimals(x, decimals),
+                round_to_decimals(y, decimals),
+            ])
+        });
+        cell
+    }
+
+    /// Consumes the cell and returns it with the first vertex of each ring
+    /// repeated at its end, unless the ring is already closed.
+    pub fn with_closed_rings(mut self) -> Self {
+        close_ring(&mut self.cell);
+        self.cells.iter_mut().for_each(close_ring);
+        self.holes.iter_mut().for_each(close_ring);
+        if let Some(unbounded_cell) = self.unbounded_cell.as_mut() {
+            close_ring(unbounded_cell);
+        }
+        self.polygons = PolygonCache::default();
+        self
+    }
+
+    /// Subdivides all edges of the cell including the holes and the unbounded cell,
+    /// so that no segment is longer than the specified length, e.g. for smoother
+    /// rendering or styling based on the arc length. Edges that are already short
+    /// enough and non-positive lengths leave the cell unchanged.
+    /// The neighbors and a stored area are kept unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// * `max_segment_len` - the maximum length of a segment
+    pub fn densify(&mut self, max_segment_len: f64) {
+        if max_segment_len.is_nan() || max_segment_len <= 0.0 {
+            return;
+        }
+        densify_ring(&mut self.cell, max_segment_len);
+        self.cells
+            .iter_mut()
+            .for_each(|ring| densify_ring(ring, max_segment_len));
+        self.holes
+            .iter_mut()
+            .for_each(|ring| densify_ring(ring, max_segment_len));
+        if let Some(unbounded_cell) = self.unbounded_cell.as_mut() {
+            densify_ring(unbounded_cell, max_segment_len);
+        }
+        self.polygons = PolygonCache::default();
+    }
+
+    /// Removes near-collinear vertices from all rings of the cell including the holes
+    /// and the unbounded cell with the Douglas-Peucker algorithm, e.g. for cells clipped
+    /// to detailed bounds. Vertices deviating less than the tolerance from the
+    /// simplified edges are removed, while rings that would collapse are kept.
+    /// If the simplification would move the site outside of its cell, the cell
+    /// polygon and the holes are left unchanged. A stored area is updated and
+    /// non-positive tolerances leave the cell unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// * `epsilon` - the maximum distance of a removed vertex from the simplified edges
+    pub fn simplify(&mut self, epsilon: f64) {
+        if epsilon.is_nan() || epsilon <= 0.0 {
+            return;
+        }
+        let site = geo::Point::new(self.site[0], self.site[1]);
+        let contains_site = self.as_polygon().intersects(&site);
+        let (cell, cells, holes) = (self.cell.clone(), self.cells.clone(), self.holes.clone());
+        simplify_ring(&mut self.cell, epsilon);
+        self.cells
+            .iter_mut()
+            .for_each(|ring| simplify_ring(ring, epsilon));
+        self.holes
+            .iter_mut()
+            .for_each(|ring| simplify_ring(ring, epsilon));
+        self.polygons = PolygonCache::default();
+        if contains_site && !self.as_polygon().intersects(&site) {
+            self.cell = cell;
+            self.cells = cells;
+            self.holes = holes;
+            self.polygons = PolygonCache::default();
+        }
+        if let Some(unbounded_cell) = self.unbounded_cell.as_mut() {
+            simplify_ring(unbounded_cell, epsilon);
+        }
+        if self.area.is_some() {
+            self.area = Some(self.area());
+        }
+    }
+
+    /// Returns the cell as [`Polygon`] including the holes that lie within it.
+    /// The polygon is built on first use and cached until the rings of the cell change.
+    pub fn as_polygon(&self) -> &Polygon {
+        self.polygons
+            .polygon
+            .get_or_init(|| self.piece_to_polygon(&self.cell))
+    }
+
+    /// Converts the cell to a [`Polygon`] with a closed exterior ring and the holes
+    /// that lie within it as interiors, so that `geo` algorithms can be run on the cell.
+    /// If the bound splits the cell, only the piece containing the site is converted,
+    /// see [`BoundedVoronoiCell::multi_polygon`] for all pieces.
+    /// This is equivalent to [`BoundedVoronoiCell::as_polygon`] and the [`From`]
+    /// conversion.
+    pub fn to_polygon(&self) -> Polygon {
+        self.as_polygon().clone()
+    }
+
+    /// Converts a piece of the cell to a [`Polygon`] with the holes that lie
+    /// within the piece as interiors.
+    ///
+    /// # Parameters
+    ///
+    /// * `piece` - the exterior ring of the piece
+    fn piece_to_polygon<T: Borrow<Vec<[f64; 2]>>>(&self, piece: T) -> Polygon {
+        let exterior = ring_to_polygon(piece);
+        if self.holes.is_empty() {
+            return exterior;
+        }
+        let interiors = self
+            .holes
+            .iter()
+            .map(ring_to_polygon)
+            .filter(|hole| exterior.contains(hole))
+            .map(|hole| hole.exterior().clone())
+            .collect();
+        Polygon::new(exterior.exterior().clone(), interiors)
+    }
+
+    /// Returns `true` if the point lies within any piece of the cell.
+    /// Points exactly on the border of the cell are not contained,
+    /// so a point on the shared edge of two cells is contained by neither.
+    ///
+    /// # Parameters
+    ///
+    /// * `point` - the point to check
+    pub fn contains(&self, point: [f64; 2]) -> bool {
+        self.multi_polygon()
+            .contains(&geo::Point::new(point[0], point[1]))
+    }
+
+    /// Returns `true` if all pieces of the cell polygon together have fewer than
+    /// 3 distinct vertices or if their area is below the specified minimum.
+    ///
+    /// # Parameters
+    ///
+    /// * `min_area` - the area below which the cell is considered degenerate
+    pub fn is_degenerate(&self, min_area: f64) -> bool {
+        let mut vertices: Vec<[f64; 2]> = self.cells().into_iter().flatten().collect();
+        vertices.sort_by(|a, b| a[0].total_cmp(&b[0]).then(a[1].total_cmp(&b[1])));
+        vertices.dedup();
+        vertices.len() < 3 || self.area() < min_area
+    }
+
+    /// Returns all pieces of the cell as [`MultiPolygon`], which is built on first use
+    /// and cached until the rings of the cell change.
+    pub fn multi_polygon(&self) -> &MultiPolygon {
+        self.polygons.multi_polygon.get_or_init(|| {
+            if self.cells.is_empty() {
+                MultiPolygon::new(vec![self.as_polygon().clone()])
+            } else {
+                self.cells
+                    .iter()
+                    .map(|piece| self.piece_to_polygon(piece))
+                    .collect()
+            }
+        })
+    }
+
+    /// Consumes the cell and returns the cell with the specified bound applied.
+    /// If the bound splits the cell into multiple pieces, the piece containing
+    /// the site is used as cell polygon, while all pieces are kept as
+    /// [`cells`](BoundedVoronoiCell::cells).
+    ///
+    /// # Parameters
+    ///
+    /// * `bound` - the polygon to apply as bound
+    pub fn apply_bound<T: Borrow<Polygon>>(self, bound: T) -> Result<Self, VoronoiError> {
+        self.apply_multi_polygon_bound(MultiPolygon::new(vec![bound.borrow().clone()]))
+    }
+
+    /// Consumes the cell and returns the cell with the specified bound consisting
+    /// of several regions applied.
+    /// The regions are centered around the site as a whole and the piece
+    /// containing the site is used as cell polygon.
+    /// A site on the border of the bound, e.g. on a vertex or an edge of a bound
+    /// whose bounding rectangle is centered on its border, is considered to be
+    /// contained by the pieces it touches.
+    ///
+    /// # Parameters
+    ///
+    /// * `bound` - the regions to apply as bound
+    pub fn apply_multi_polygon_bound<T: Borrow<MultiPolygon>>(
+        self,
+        bound: T,
+    ) -> Result<Self, VoronoiError> {
+        let centered_bound = center_polygon(bound.borrow(), self.site[0], self.site[1])?;
+        self.clip_to(centered_bound, None)
+    }
+
+    /// Consumes the cell and returns the cell with the specified bound applied
+    /// after snapping the coordinates of the cell and the bound to a grid.
+    /// The site is snapped as well and may lie on the border of its piece,
+    /// so that floating point errors cannot exclude it from the cell.
+    /// Only the input of the intersection is snapped, so the vertices of the snapped
+    /// cell and bound are kept in the resulting cell polygon, while new vertices where
+    /// their edges cross are computed by the intersection and generally do not lie
+    /// on the grid.
+    /// The site is kept unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// * `bound` - the polygon to apply as bound
+    /// * `precision` - the spacing of the grid the coordinates are snapped to
+    pub fn apply_snapped_bound<T: Borrow<Polygon>>(
+        self,
+        bound: T,
+        precision: f64,
+    ) -> Result<Self, VoronoiError> {
+        self.apply_snapped_multi_polygon_bound(
+            MultiPolygon::new(vec![bound.borrow().clone()]),
+            precision,
+        )
+    }
+
+    /// Consumes the cell and returns the cell with the specified bound consisting
+    /// of several regions applied after snapping the coordinates to a grid.
+    /// See [`BoundedVoronoiCell::apply_snapped_bound`] for details.
+    ///
+    /// # Parameters
+    ///
+    /// * `bound` - the regions to apply as bound
+    /// * `precision` - the spacing of the grid the coordinates are snapped to
+    pub fn apply_snapped_multi_polygon_bound<T: Borrow<MultiPolygon>>(
+        self,
+        bound: T,
+        precision: f64,
+    ) -> Result<Self, VoronoiError> {
+        let centered_bound = center_polygon(bound.borrow(), self.site[0], self.site[1])?;
+        self.clip_to(centered_bound, Some(precision))
+    }
+
+    /// Consumes the cell and returns the cell clipped to the bound as it is without
+    /// centering it around the site, optionally after snapping the coordinates to a grid
+    /// as described for [`BoundedVoronoiCell::apply_snapped_bound`].
+    ///
+    /// # Parameters
+    ///
+    /// * `bound` - the regions to clip the cell to
+    /// * `precision` - the spacing of the grid the coordinates are snapped to if any
+    pub(crate) fn clip_to(
+        self,
+        bound: MultiPolygon,
+        precision: Option<f64>,
+    ) -> Result<Self, VoronoiError> {
+        match precision {
+            Some(precision) => {
+                let snap = |coordinate: geo::Coord| geo::Coord {
+                    x: snap_to_grid(coordinate.x, precision),
+                    y: snap_to_grid(coordinate.y, precision),
+                };
+                let cell_polygon = self.as_polygon().map_coords(snap);
+                let geo_site = geo::Point::new(
+                    snap_to_grid(self.site[0], precision),
+                    snap_to_grid(self.site[1], precision),
+                );
+                self.clip(cell_polygon, bound.map_coords(snap), |piece| piece.intersects(&geo_site))
+            },
+            None => {
+                let cell_polygon = self.to_polygon();
+                let geo_site = geo::Point::new(self.site[0], self.site[1]);
+                // Only a site on the border of the bound is contained by the pieces it
+                // touches, while a site on the border of its own cell requires snapping.
+                let on_bound_border = !bound.contains(&geo_site) && bound.intersects(&geo_site);
+                self.clip(cell_polygon, bound, |piece| {
+                    piece.contains(&geo_site) || (on_bound_border && piece.intersects(&geo_site))
+                })
+            },
+        }
+    }
+
+    /// Consumes the cell and returns the intersection of the cell polygon and the bound.
+    /// The intersection that contains the site is moved to the front.
+    /// The interiors of all intersections are kept as holes of the cell.
+    /// All pieces are oriented counter-clockwise and the holes clockwise
+    /// regardless of the input winding.
+    ///
+    /// # Parameters
+    ///
+    /// * `cell_polygon` - the cell polygon to clip
+    /// * `centered_bound` - the bound centered around the site or the bound as it is
+    /// * `contains_site` - returns `true` if an intersection contains the site
+    fn clip<F: Fn(&Polygon) -> bool>(
+        self,
+        cell_polygon: Polygon,
+        centered_bound: MultiPolygon,
+        contains_site: F,
+    ) -> Result<Self, VoronoiError> {
+        // Creates intersections between bounding polygon and the voronoi cell
+        // and moves the intersection that actually contains the original point to the front.
+        let mut pieces: Vec<Vec<[f64; 2]>> = Vec::new();
+        let mut holes: Vec<Vec<[f64; 2]>> = Vec::new();
+        let mut site_found = false;
+        let cell_polygon = MultiPolygon::new(vec![cell_polygon.orient(Direction::Default)]);
+        for intersection in cell_polygon
+            .intersection(&centered_bound)
+            .orient(Direction::Default)
+        {
+            let piece = line_string_to_ring(intersection.exterior());
+            holes.extend(intersection.interiors().iter().map(line_string_to_ring));
+            if !site_found && contains_site(&intersection) {
+                site_found = true;
+                pieces.insert(0, piece);
+            } else {
+                pieces.push(piece);
+            }
+        }
+
+        if site_found {
+            Ok(BoundedVoronoiCell {
+                cell: pieces[0].clone(),
+                cells: pieces,
+                holes,
+                polygons: PolygonCache::default(),
+                ..self
+            })
+        } else {
+            Err(VoronoiError::NoIntersection { site: self.site })
+        }
+    }
+}
+
+/// Rounds the value to the specified number of decimal places.
+/// Values that cannot be scaled without overflow are returned unchanged
+/// and negative zeros are converted to positive zeros.
+///
+/// # Parameters
+///
+/// * `value` - the value to round
+/// * `decimals` - the number of decimal places to round to
+pub fn round_to_decimals(value: f64, decimals: u32) -> f64 {
+    let factor = 10f64.powi(decimals.min(i32::MAX as u32) as i32);
+    let rounded = (value * factor).round() / factor;
+    if rounded.is_finite() {
+        rounded + 0.0
+    } else {
+        value
+    }
+}
+
+/// Rounds the value to the closest multiple of the precision.
+///
+/// # Parameters
+///
+/// * `value` - the value to round
+/// * `precision` - the spacing of the grid
+fn snap_to_grid(value: f64, precision: f64) -> f64 {
+    (value / precision).round() * precision
+}
+
+/// Repeats the first vertex of the ring at its end, unless the ring is empty
+/// or already closed.
+///
+/// # Parameters
+///
+/// * `ring` - the ring to close
+fn close_ring(ring: &mut Vec<[f64; 2]>) {
+    if let (Some(&first), Some(&last)) = (ring.first(), ring.last()) {
+        if first != last {
+            ring.push(first);
+        }
+    }
+}
+
+/// Subdivides all edges of the ring including the closing edge, so that no segment
+/// is longer than the specified length. An open ring stays open.
+///
+/// # Parameters
+///
+/// * `ring` - the ring to densify
+/// * `max_segment_len` - the positive maximum length of a segment
+fn densify_ring(ring: &mut Vec<[f64; 2]>, max_segment_len: f64) {
+    let is_open = ring.len() > 1 && ring.first() != ring.last();
+    let mut closed_ring = ring.clone();
+    close_ring(&mut closed_ring);
+    let line_string: LineString = closed_ring.into();
+    *ring = line_string
+        .densify(max_segment_len)
+        .into_iter()
+        .map(|coordinate| [coordinate.x, coordinate.y])
+        .collect();
+    if is_open {
+        ring.pop();
+    }
+}
+
+/// Simplifies the ring with the Douglas-Peucker algorithm including the closing edge.
+/// An open ring stays open and rings that would have less than 3 distinct points
+/// are kept unchanged.
+///
+/// # Parameters
+///
+/// * `ring` - the ring to simplify
+/// * `epsilon` - the positive maximum distance of a removed vertex from the simplified edges
+fn simplify_ring(ring: &mut Vec<[f64; 2]>, epsilon: f64) {
+    let is_open = ring.len() > 1 && ring.first() != ring.last();
+    let mut closed_ring = ring.clone();
+    close_ring(&mut closed_ring);
+    let line_string: LineString = closed_ring.into();
+    let mut simplified: Vec<[f64; 2]> = line_string
+        .simplify(&epsilon)
+        .into_iter()
+        .map(|coordinate| [coordinate.x, coordinate.y])
+        .collect();
+    if simplified.len() < 4 {
+        return;
+    }
+    if is_open {
+        simplified.pop();
+    }
+    *ring = simplified;
+}
+
+/// Returns `true` if the cell consists of at most a single piece,
+/// which is already stored as cell polygon.
+///
+/// # Parameters
+///
+/// * `cells` - the pieces of the cell
+fn is_single_piece<T>(cells: &[Vec<T>]) -> bool {
+    cells.len() <= 1
+}
+
+/// Converts a [`LineString`] to a ring of points.
+///
+/// # Parameters
+///
+/// * `line_string` - the line string to convert
+fn line_string_to_ring(line_string: &LineString) -> Vec<[f64; 2]> {
+    line_string
+        .coords_iter()
+        .map(|coordinate| [coordinate.x, coordinate.y])
+        .collect()
+}
+
+/// Converts a ring of points to a [`Polygon`] without interiors.
+///
+/// # Parameters
+///
+/// * `ring` - the points of the ring
+fn ring_to_polygon<T: Borrow<Vec<[f64; 2]>>>(ring: T) -> Polygon {
+    Polygon::new(
+        LineString::from(
+            ring.borrow()
+                .iter()
+                .map(|point| (point[0], point[1]))
+                .collect::<Vec<(f64, f64)>>(),
+        ),
+        Vec::new(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::{assert_abs_diff_eq, assert_ulps_eq};
+    use geo::Winding;
+
+    use super::*;
+    use crate::input::CircleBound;
+
+    /// Returns a bounded point set with a square bound of the specified size.
+    fn square_bounded_point_set(point_set: Vec<[f64; 2]>, size: f64) -> BoundedPointSet {
+        let half = size / 2.0;
+        BoundedPointSet::new(
+            point_set,
+            vec![
+                [-half, -half],
+                [half, -half],
+                [half, half],
+                [-half, half],
+                [-half, -half],
+            ],
+        )
+    }
+
+    /// Returns a square grid of points with the specified number of points per side and spacing.
+    fn grid_point_set(points_per_side: usize, spacing: f64) -> Vec<[f64; 2]> {
+        (0..points_per_side)
+            .flat_map(|i| {
+                (0..points_per_side).map(move |j| [i as f64 * spacing, j as f64 * spacing])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compute_voronoi_no_lloyd() {
+        let point_set = grid_point_set(3, 10.0);
+        let bps = square_bounded_point_set(point_set.clone(), 2.0);
+        let cells =
+            compute_voronoi_with_options(&bps, VoronoiOptions::default().lloyd_iterations(0))
+                .unwrap();
+        assert_eq!(cells.len(), point_set.len());
+        for cell in cells {
+            assert!(point_set.contains(&cell.site()));
+            assert!(cell.original_site().is_none());
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_lloyd() {
+        let point_set = vec![
+            [0.0, 0.0],
+            [1.0, 0.5],
+            [10.0, 0.0],
+            [0.0, 10.0],
+            [10.0, 10.0],
+            [5.0, 6.0],
+        ];
+        let bps = square_bounded_point_set(point_set.clone(), 2.0);
+        let cells =
+            compute_voronoi_with_options(&bps, VoronoiOptions::default().lloyd_iterations(5))
+                .unwrap();
+        assert_eq!(cells.len(), point_set.len());
+        let mut moved = false;
+        for cell in cells {
+            let original_site = cell.original_site().unwrap();
+            assert!(point_set.contains(&original_site));
+            moved |= original_site != cell.site();
+        }
+        assert!(moved);
+    }
+
+    #[test]
+    fn test_compute_voronoi_neighbors() {
+        let point_set = vec![
+            [0.0, 0.0],
+            [10.0, 1.0],
+            [-10.0, -1.0],
+            [1.0, -10.0],
+            [-1.0, 10.0],
+        ];
+        let bps = square_bounded_point_set(point_set.clone(), 2.0);
+        let cells = compute_voronoi(&bps).unwrap();
+        assert_eq!(cells.len(), point_set.len());
+        for (index, cell) in cells.iter().enumerate() {
+            assert!(!cell.neighbors().contains(&index));
+            for &neighbor in cell.neighbors() {
+                assert!(cells[neighbor].neighbors().contains(&index));
+            }
+            // The central site is adjacent to all other sites.
+            if cell.site() == [0.0, 0.0] {
+                let mut neighbors = cell.neighbors().clone();
+                neighbors.sort();
+                let expected: Vec<usize> = (0..cells.len()).filter(|&i| i != index).collect();
+                assert_eq!(neighbors, expected);
+            }
+        }
+    }
+
+    /// Returns a bounded point set with an L-shaped bound, which does not contain
+    /// the centre of its bounding rectangle.
+    fn l_shaped_bounded_point_set(point_set: Vec<[f64; 2]>) -> BoundedPointSet {
+        BoundedPointSet::new(
+            point_set,
+            vec![
+                [0.0, 0.0],
+                [2.0, 0.0],
+                [2.0, 0.5],
+                [0.5, 0.5],
+                [0.5, 2.0],
+                [0.0, 2.0],
+                [0.0, 0.0],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_is_site_within_bound() {
+        let site = Point2D::new(5.0, 5.0).unwrap();
+        let square = square_bounded_point_set(vec![], 2.0)
+            .bounding_multi_polygon()
+            .unwrap();
+        let l_shape = l_shaped_bounded_point_set(vec![])
+            .bounding_multi_polygon()
+            .unwrap();
+        assert!(is_site_within_bound(site, square).unwrap());
+        assert!(!is_site_within_bound(site, l_shape).unwrap());
+    }
+
+    #[test]
+    fn test_compute_voronoi_site_on_bound_edge() {
+        // The centre of the bounding rectangle of the triangle lies on its hypotenuse.
+        let bps = BoundedPointSet::new(
+            grid_point_set(3, 10.0),
+            vec![[0.0, 0.0], [2.0, 0.0], [0.0, 2.0], [0.0, 0.0]],
+        );
+        let options = VoronoiOptions::default().site_placement(SitePlacement::Error);
+        let cells = compute_voronoi_with_options(&bps, options).unwrap();
+        assert_eq!(cells.len(), 9);
+        for cell in cells {
+            assert_ulps_eq!(cell.area(), 2.0);
+            assert!(cell
+                .as_polygon()
+                .intersects(&geo::Point::new(cell.site()[0], cell.site()[1])));
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_site_on_bound_vertex() {
+        // The centre of the bounding rectangle is the vertex of the notch.
+        let bps = BoundedPointSet::new(
+            grid_point_set(3, 10.0),
+            vec![
+                [-1.0, -1.0],
+                [1.0, -1.0],
+                [0.0, 0.0],
+                [1.0, 1.0],
+                [-1.0, 1.0],
+                [-1.0, -1.0],
+            ],
+        );
+        let options = VoronoiOptions::default().site_placement(SitePlacement::Error);
+        let cells = compute_voronoi_with_options(&bps, options).unwrap();
+        assert_eq!(cells.len(), 9);
+        assert!(cells.iter().all(|cell| cell.cell().contains(&cell.site())));
+        assert_ulps_eq!(cells[0].area(), 3.0);
+        let cell = BoundedVoronoiCell::new(
+            [0.0, 0.0],
+            vec![[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]],
+        )
+        .apply_bound(bps.bounding_polygon().unwrap())
+        .unwrap();
+        assert_eq!(cell.cells().len(), 1);
+        assert_ulps_eq!(cell.area(), 3.0);
+    }
+
+    #[test]
+    fn test_compute_voronoi_site_placement_keep() {
+        let bps = square_bounded_point_set(grid_point_set(3, 10.0), 2.0);
+        let options = VoronoiOptions::default().site_placement(SitePlacement::Keep);
+        assert_eq!(compute_voronoi_with_options(&bps, options).unwrap().len(), 9);
+        let bps = l_shaped_bounded_point_set(grid_point_set(3, 10.0));
+        let options = VoronoiOptions::default().site_placement(SitePlacement::Keep);
+        assert!(matches!(
+            compute_voronoi_with_options(&bps, options).unwrap_err(),
+            VoronoiError::NoIntersection { .. }
+        ));
+    }
+
+    #[test]
+    fn test_compute_voronoi_site_placement_keep_empty() {
+        let bps = l_shaped_bounded_point_set(grid_point_set(3, 10.0));
+        let options = VoronoiOptions::default().site_placement(SitePlacement::KeepEmpty);
+        let cells = compute_voronoi_with_options(&bps, options).unwrap();
+        assert_eq!(cells.len(), 9);
+        assert!(cells.iter().all(|cell| cell.cell().is_empty()));
+    }
+
+    #[test]
+    fn test_apply_bound_to_cells_keep_empty() {
+        let bound = square_bounded_point_set(vec![], 2.0)
+            .bounding_multi_polygon()
+            .unwrap();
+        let in_bound = BoundedVoronoiCell::new(
+            [0.5, 0.5],
+            vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.0, 0.0]],
+        );
+        // The site lies outside of its cell, so no piece of the bounded cell contains it.
+        let out_of_bound = BoundedVoronoiCell::new(
+            [5.0, 0.5],
+            vec![[1.0, 0.0], [2.0, 0.0], [2.0, 1.0], [1.0, 1.0], [1.0, 0.0]],
+        );
+        let cells = vec![in_bound, out_of_bound];
+        let options = VoronoiOptions::default().site_placement(SitePlacement::KeepEmpty);
+        let bounded_cells = apply_bound_to_cells(cells.clone(), &bound, &options).unwrap();
+        assert_eq!(bounded_cells.len(), 2);
+        assert!(!bounded_cells[0].cell().is_empty());
+        assert!(bounded_cells[1].cell().is_empty());
+        assert_eq!(bounded_cells[1].site(), [5.0, 0.5]);
+        let options = VoronoiOptions::default().site_placement(SitePlacement::Keep);
+        assert_eq!(
+            apply_bound_to_cells(cells, &bound, &options).unwrap_err(),
+            VoronoiError::NoIntersection { site: [5.0, 0.5] }
+        );
+    }
+
+    #[test]
+    fn test_compute_voronoi_site_placement_drop() {
+        let bps = square_bounded_point_set(grid_point_set(3, 10.0), 2.0);
+        let options = VoronoiOptions::default().site_placement(SitePlacement::Drop);
+        assert_eq!(compute_voronoi_with_options(&bps, options).unwrap().len(), 9);
+        let bps = l_shaped_bounded_point_set(grid_point_set(3, 10.0));
+        let options = VoronoiOptions::default().site_placement(SitePlacement::Drop);
+        assert_eq!(
+            compute_voronoi_with_options(&bps, options).unwrap_err(),
+            VoronoiError::NoSiteWithinBound
+        );
+    }
+
+    #[test]
+    fn test_compute_voronoi_site_placement_error() {
+        let bps = square_bounded_point_set(grid_point_set(3, 10.0), 2.0);
+        let options = VoronoiOptions::default().site_placement(SitePlacement::Error);
+        assert_eq!(compute_voronoi_with_options(&bps, options).unwrap().len(), 9);
+        let bps = l_shaped_bounded_point_set(grid_point_set(3, 10.0));
+        let options = VoronoiOptions::default().site_placement(SitePlacement::Error);
+        assert!(matches!(
+            compute_voronoi_with_options(&bps, options).unwrap_err(),
+            VoronoiError::SiteOutsideBound { .. }
+        ));
+    }
+
+    #[test]
+    fn test_compute_voronoi_labels() {
+        let point_set = grid_point_set(3, 10.0);
+        let labels: Vec<String> = point_set
+            .iter()
+            .map(|point| format!("{}_{}", point[0], point[1]))
+            .collect();
+        let bps = square_bounded_point_set(point_set.clone(), 2.0).with_labels(labels);
+        let options = VoronoiOptions::default().lloyd_iterations(2);
+        let cells = compute_voronoi_with_options(&bps, options).unwrap();
+        assert_eq!(cells.len(), point_set.len());
+        for cell in cells {
+            let original_site = cell.original_site().unwrap();
+            assert_eq!(
+                cell.label().as_ref().unwrap(),
+                &format!("{}_{}", original_site[0], original_site[1])
+            );
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_no_labels() {
+        let bps = square_bounded_point_set(grid_point_set(3, 10.0), 2.0);
+        for cell in compute_voronoi(&bps).unwrap() {
+            assert!(cell.label().is_none());
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_point_properties() {
+        let json = r#"{
+            "points": [[15.0, 5.0], {"coord": [5.0, 5.0], "properties": {"name": "a"}}],
+            "bbox": [-1.0, -1.0, 1.0, 1.0]
+        }"#;
+        let bps: BoundedPointSet = serde_json::from_str(json).unwrap();
+        for cells in [
+            compute_voronoi(&bps).unwrap(),
+            compute_voronoi_iter(&bps)
+                .collect::<Result<_, _>>()
+                .unwrap(),
+        ] {
+            assert_eq!(cells[0].site(), [5.0, 5.0]);
+            assert_eq!(cells[0].properties().as_ref().unwrap()["name"], "a");
+            assert!(cells[1].properties().is_none());
+            let serialized = serde_json::to_value(&cells[0]).unwrap();
+            assert_eq!(serialized["properties"], serde_json::json!({"name": "a"}));
+            assert!(serde_json::to_value(&cells[1])
+                .unwrap()
+                .get("properties")
+                .is_none());
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_asymmetric_bounding_box() {
+        // The sites are clustered in one corner of a large bound that is far off-center.
+        let point_set = grid_point_set(3, 1.0)
+            .into_iter()
+            .map(|[x, y]| [x + 100.0, y + 100.0])
+            .collect();
+        let bps = BoundedPointSet::new(
+            point_set,
+            vec![
+                [-50.0, -10.0],
+                [-10.0, -10.0],
+                [-10.0, 30.0],
+                [-50.0, 30.0],
+                [-50.0, -10.0],
+            ],
+        );
+        let cells = compute_voronoi(&bps).unwrap();
+        assert_eq!(cells.len(), 9);
+        for cell in &cells {
+            let site = cell.site();
+            assert!(cell
+                .as_polygon()
+                .contains(&geo::Point::new(site[0], site[1])));
+        }
+        let centre = cells
+            .iter()
+            .find(|cell| cell.site() == [101.0, 101.0])
+            .unwrap();
+        assert_ulps_eq!(centre.area(), 1.0);
+    }
+
+    #[test]
+    fn test_diagramm_bounds() {
+        let point_set_bounds =
+            Bounds::from_point_set(HashSet::from([Point2D::new(0.0, 0.0).unwrap()])).unwrap();
+        let bound_bounds = Bounds::from_polygon(
+            square_bounded_point_set(vec![], 2.0)
+                .bounding_polygon()
+                .unwrap()
+                .translate(10.0, 0.0),
+        )
+        .unwrap();
+        let bounds = diagramm_bounds(point_set_bounds, bound_bounds);
+        // The union reaches from -1 to 11 and from -1 to 1 with a margin of 1.2.
+        assert_ulps_eq!(bounds.min_x(), -2.2);
+        assert_ulps_eq!(bounds.max_x(), 12.2);
+        assert_ulps_eq!(bounds.min_y(), -2.2);
+        assert_ulps_eq!(bounds.max_y(), 2.2);
+    }
+
+    #[test]
+    fn test_compute_voronoi_deterministic_order() {
+        let bps = square_bounded_point_set(
+            vec![
+                [5.0, 0.0],
+                [0.0, 5.0],
+                [0.0, 0.0],
+                [5.0, 5.0],
+                [2.5, 2.5],
+                [0.0, 2.5],
+            ],
+            4.0,
+        );
+        let first = serde_json::to_string(&compute_voronoi(&bps).unwrap()).unwrap();
+        let second = serde_json::to_string(&compute_voronoi(&bps).unwrap()).unwrap();
+        assert_eq!(first, second);
+        let sites: Vec<[f64; 2]> = compute_voronoi(&bps)
+            .unwrap()
+            .iter()
+            .map(|cell| cell.site())
+            .collect();
+        assert_eq!(
+            sites,
+            vec![
+                [0.0, 0.0],
+                [0.0, 2.5],
+                [0.0, 5.0],
+                [2.5, 2.5],
+                [5.0, 0.0],
+                [5.0, 5.0]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_compute_voronoi_deterministic_lloyd() {
+        let bps = square_bounded_point_set(
+            vec![
+                [5.0, 0.0],
+                [0.0, 5.0],
+                [0.0, 0.0],
+                [5.0, 5.0],
+                [2.5, 2.5],
+                [0.5, 2.5],
+            ],
+            4.0,
+        );
+        for options in [
+            VoronoiOptions::default().lloyd_iterations(5),
+            VoronoiOptions::default().lloyd_until(1e-6, 100),
+        ] {
+            let first =
+                serde_json::to_string(&compute_voronoi_with_options(&bps, &options).unwrap())
+                    .unwrap();
+            let second =
+                serde_json::to_string(&compute_voronoi_with_options(&bps, &options).unwrap())
+                    .unwrap();
+            assert_eq!(first, second);
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_seed() {
+        let bps = square_bounded_point_set(grid_point_set(4, 1.0), 8.0);
+        let run = |options: VoronoiOptions| {
+            serde_json::to_string(&compute_voronoi_with_options(&bps, &options).unwrap()).unwrap()
+        };
+        let seeded = VoronoiOptions::default()
+            .lloyd_iterations(2)
+            .include_index(true);
+        assert_eq!(run(seeded.clone().seed(1)), run(seeded.clone().seed(1)));
+        assert_ne!(run(seeded.clone().seed(1)), run(seeded.clone().seed(2)));
+        // The seed only changes the order of the cells, which keep their indices.
+        let mut shuffled = compute_voronoi_with_options(&bps, &seeded.clone().seed(1)).unwrap();
+        shuffled.sort_by_key(|cell| cell.index());
+        let sorted = compute_voronoi_with_options(&bps, &seeded).unwrap();
+        assert_eq!(shuffled.len(), sorted.len());
+        for (shuffled_cell, sorted_cell) in shuffled.iter().zip(&sorted) {
+            assert_eq!(shuffled_cell.index(), sorted_cell.index());
+            assert_ulps_eq!(shuffled_cell.area(), sorted_cell.area(), epsilon = 1e-6);
+        }
+        assert_eq!(
+            compute_voronoi_with_options(&bps, seeded.seed(1).input_order(true)).unwrap_err(),
+            VoronoiError::ConflictingSiteOrder
+        );
+    }
+
+    #[test]
+    fn test_compute_weighted_voronoi_neighbors_sorted() {
+        let bps = square_bounded_point_set(grid_point_set(3, 1.0), 4.0);
+        for cell in compute_weighted_voronoi(&bps, 50, VoronoiOptions::default()).unwrap() {
+            assert!(cell.neighbors().windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_sort_by() {
+        let bps = BoundedPointSet::new(
+            vec![[0.1, 0.1], [0.8, 0.2], [0.5, 0.9], [0.2, 0.6], [0.45, 0.4]],
+            vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+        );
+        let unsorted = compute_voronoi(&bps).unwrap();
+        let cells =
+            compute_voronoi_with_options(&bps, VoronoiOptions::default().sort_by(SortKey::Area))
+                .unwrap();
+        assert_eq!(cells.len(), unsorted.len());
+        assert!(cells
+            .windows(2)
+            .all(|pair| pair[0].area() >= pair[1].area()));
+        // The neighbors still refer to the same sites.
+        let neighbor_sites = |cells: &[BoundedVoronoiCell], site: [f64; 2]| {
+            let cell = cells.iter().find(|cell| cell.site() == site).unwrap();
+            let mut sites: Vec<Point2D> = cell
+                .neighbors()
+                .iter()
+                .filter_map(|&neighbor| {
+                    Point2D::new(cells[neighbor].site()[0], cells[neighbor].site()[1])
+                })
+                .collect();
+            sites.sort();
+            sites
+        };
+        for cell in &unsorted {
+            assert_eq!(neighbor_sites(&cells, cell.site()), neighbor_sites(&unsorted, cell.site()));
+        }
+        let cells = compute_voronoi_with_options(
+            &bps,
+            VoronoiOptions::default().sort_by(SortKey::AreaAscending),
+        )
+        .unwrap();
+        assert!(cells
+            .windows(2)
+            .all(|pair| pair[0].area() <= pair[1].area()));
+        let cells =
+            compute_voronoi_with_options(&bps, VoronoiOptions::default().sort_by(SortKey::SiteY))
+                .unwrap();
+        let sites: Vec<[f64; 2]> = cells.iter().map(|cell| cell.site()).collect();
+        assert_eq!(sites, vec![[0.1, 0.1], [0.8, 0.2], [0.45, 0.4], [0.2, 0.6], [0.5, 0.9]]);
+    }
+
+    #[test]
+    fn test_compute_voronoi_input_order() {
+        let point_set = vec![[1.0, 1.0], [0.0, 1.0], [1.0, 0.0], [0.0, 1.0], [0.0, 0.0]];
+        let bps = square_bounded_point_set(point_set, 2.0);
+        let sorted_cells = compute_voronoi(&bps).unwrap();
+        let cells = compute_voronoi_with_options(&bps, VoronoiOptions::default().input_order(true))
+            .unwrap();
+        let sites: Vec<[f64; 2]> = cells.iter().map(BoundedVoronoiCell::site).collect();
+        assert_eq!(sites, vec![[1.0, 1.0], [0.0, 1.0], [1.0, 0.0], [0.0, 0.0]]);
+        // The neighbors refer to the same sites regardless of the order.
+        for cell in &cells {
+            let sorted_cell = sorted_cells
+                .iter()
+                .find(|sorted_cell| sorted_cell.site() == cell.site())
+                .unwrap();
+            let mut neighbor_sites: Vec<[f64; 2]> = cell
+                .neighbors()
+                .iter()
+                .map(|&neighbor| cells[neighbor].site())
+                .collect();
+            let mut sorted_neighbor_sites: Vec<[f64; 2]> = sorted_cell
+                .neighbors()
+                .iter()
+                .map(|&neighbor| sorted_cells[neighbor].site())
+                .collect();
+            neighbor_sites.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            sorted_neighbor_sites.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            assert_eq!(neighbor_sites, sorted_neighbor_sites);
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_lloyd_until_converged() {
+        let point_set = vec![[0.0, 0.0], [0.5, 0.2], [0.1, 0.6], [0.9, 0.9], [0.3, 0.3]];
+        let bps = square_bounded_point_set(point_set.clone(), 4.0);
+        let options = VoronoiOptions::default().lloyd_until(1e-6, 1000);
+        let (cells, report) = compute_voronoi_with_report(&bps, options).unwrap();
+        assert!(report.iterations() > 1);
+        assert!(report.iterations() < 1000);
+        assert!(report.max_displacement().unwrap() < 1e-6);
+        assert_eq!(cells.len(), point_set.len());
+        for cell in cells {
+            assert!(point_set.contains(&cell.original_site().unwrap()));
+            // The sites are the centroids of their bounded cells.
+            let centroid = cell.multi_polygon().centroid().unwrap();
+            assert_abs_diff_eq!(centroid.x(), cell.site()[0], epsilon = 1e-5);
+            assert_abs_diff_eq!(centroid.y(), cell.site()[1], epsilon = 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_hull_bound() {
+        let point_set = vec![
+            [0.0, 0.0],
+            [3.0, 0.5],
+            [1.0, 2.0],
+            [2.5, 3.0],
+            [1.5, 1.0],
+            [0.2, 2.5],
+        ];
+        // No bound has been specified.
+        let bps = BoundedPointSet::new(point_set.clone(), Vec::new());
+        for (margin, lloyd_iterations) in [(0.0, 0), (0.5, 0), (0.0, 3)] {
+            let options = VoronoiOptions::default()
+                .hull_bound(margin)
+                .lloyd_iterations(lloyd_iterations);
+            let cells = compute_voronoi_with_options(&bps, options).unwrap();
+            assert_eq!(cells.len(), point_set.len());
+            // The cells tile the hull without gaps or overlaps.
+            let hull = bps.convex_hull(margin).unwrap();
+            let coverage =
+                crate::statistics::coverage(&cells, MultiPolygon::new(vec![hull.clone()]));
+            assert_abs_diff_eq!(coverage.ratio(), 1.0, epsilon = 1e-9);
+            assert_abs_diff_eq!(
+                crate::statistics::union_cells(&cells).unsigned_area(),
+                hull.unsigned_area(),
+                epsilon = 1e-9
+            );
+        }
+        let collinear = BoundedPointSet::new(vec![[0.0, 0.0], [1.0, 1.0], [2.0, 2.0]], Vec::new());
+        assert_eq!(
+            compute_voronoi_with_options(&collinear, VoronoiOptions::default().hull_bound(0.0))
+                .unwrap_err(),
+            VoronoiError::DegenerateHull
+        );
+    }
+
+    #[test]
+    fn test_compute_voronoi_threads() {
+        let point_set: Vec<[f64; 2]> = (0..200)
+            .map(|index| {
+                let index = index as f64;
+                [
+                    (index * 0.37).sin() * 10.0,
+                    (index * 0.73).cos() * 10.0 + index * 0.01,
+                ]
+            })
+            .collect();
+        let bps = square_bounded_point_set(point_set, 4.0);
+        let options = VoronoiOptions::default()
+            .include_area(true)
+            .lloyd_iterations(2);
+        let sequential = compute_voronoi_with_options(&bps, options.clone().threads(1)).unwrap();
+        let sequential = serde_json::to_value(sequential).unwrap();
+        for other_options in [options.clone(), options.threads(3)] {
+            let cells = compute_voronoi_with_options(&bps, other_options).unwrap();
+            assert_eq!(serde_json::to_value(cells).unwrap(), sequential);
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_groups() {
+        let groups = vec![
+            vec![[0.0, 0.0], [0.5, 0.2], [0.1, 0.6]],
+            vec![[0.2, 0.2], [0.8, 0.3], [0.4, 0.9], [0.9, 0.9]],
+        ];
+        let bound = vec![
+            [-2.0, -2.0],
+            [2.0, -2.0],
+            [2.0, 2.0],
+            [-2.0, 2.0],
+            [-2.0, -2.0],
+        ];
+        let input = MultiGroupInput::new(groups.clone(), bound.clone());
+        let options = VoronoiOptions::default().include_area(true);
+        let tessellations = compute_voronoi_groups(&input, &options).unwrap();
+        assert_eq!(tessellations.len(), groups.len());
+        // Each group is tessellated on its own against the shared bound.
+        for (cells, group) in tessellations.iter().zip(groups) {
+            assert_eq!(cells.len(), group.len());
+            let expected =
+                compute_voronoi_with_options(BoundedPointSet::new(group, bound.clone()), &options)
+                    .unwrap();
+            assert_eq!(
+                serde_json::to_value(cells).unwrap(),
+                serde_json::to_value(expected).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_lloyd_fixed() {
+        let point_set = vec![[0.0, 0.0], [0.5, 0.2], [0.1, 0.6], [0.9, 0.9], [0.3, 0.3]];
+        // The corner site is fixed, while the other sites relax around it.
+        let bps = square_bounded_point_set(point_set.clone(), 4.0)
+            .with_fixed(vec![true, false, false, false, false]);
+        let cells =
+            compute_voronoi_with_options(&bps, VoronoiOptions::default().lloyd_iterations(5))
+                .unwrap();
+        assert_eq!(cells.len(), point_set.len());
+        for cell in &cells {
+            if cell.original_site() == Some([0.0, 0.0]) {
+                assert_eq!(cell.site(), [0.0, 0.0]);
+            } else {
+                assert_ne!(Some(cell.site()), cell.original_site());
+            }
+        }
+        let bps = bps.with_fixed(vec![true]);
+        assert_eq!(
+            compute_voronoi(&bps).unwrap_err(),
+            VoronoiError::FixedCountMismatch {
+                fixed: 1,
+                points: 5
+            }
+        );
+    }
+
+    #[test]
+    fn test_compute_voronoi_report_bounding_box() {
+        let contains = |bounding_box: Bounds, [x, y]: [f64; 2]| {
+            bounding_box.min_x() < x
+                && x < bounding_box.max_x()
+                && bounding_box.min_y() < y
+                && y < bounding_box.max_y()
+        };
+        // The bound is small and far away from the asymmetric point set.
+        let bps = BoundedPointSet::new(
+            vec![[0.0, 0.0], [10.0, 1.0], [3.0, 7.0], [-2.0, 4.0]],
+            vec![[20.0, 20.0], [21.0, 20.0], [21.0, 21.0], [20.0, 21.0]],
+        );
+        let (cells, report) = compute_voronoi_with_report(&bps, VoronoiOptions::default()).unwrap();
+        let bounding_box = report.bounding_box().unwrap();
+        assert!(cells.iter().all(|cell| contains(bounding_box, cell.site())));
+        assert!(contains(bounding_box, [20.5, 20.5]));
+        // Collinear sites report the box their cells have been clipped to as well.
+        let bps = square_bounded_point_set(vec![[0.0, 0.0], [1.0, 1.0], [3.0, 3.0]], 1.0);
+        let (cells, report) = compute_voronoi_with_report(&bps, VoronoiOptions::default()).unwrap();
+        let bounding_box = report.bounding_box().unwrap();
+        assert!(cells.iter().all(|cell| contains(bounding_box, cell.site())));
+    }
+
+    #[test]
+    fn test_compute_voronoi_lloyd_until_max_iterations() {
+        let point_set = vec![[0.0, 0.0], [0.5, 0.2], [0.1, 0.6], [0.9, 0.9], [0.3, 0.3]];
+        let bps = square_bounded_point_set(point_set, 4.0);
+        let options = VoronoiOptions::default().lloyd_until(0.0, 3);
+        let (_, report) = compute_voronoi_with_report(&bps, options).unwrap();
+        assert_eq!(report.iterations(), 3);
+        assert!(report.max_displacement().unwrap() > 0.0);
+        let options = VoronoiOptions::default()
+            .lloyd_until(0.0, 3)
+            .lloyd_iterations(2);
+        let (_, report) = compute_voronoi_with_report(&bps, options).unwrap();
+        assert_eq!(report.iterations(), 2);
+        assert_eq!(report.max_displacement(), None);
+    }
+
+    #[test]
+    fn test_compute_voronoi_lloyd_weighted() {
+        let bps = square_bounded_point_set(grid_point_set(3, 1.0), 4.0);
+        let mean_x = |cells: &[BoundedVoronoiCell]| {
+            cells.iter().map(|cell| cell.site()[0]).sum::<f64>() / cells.len() as f64
+        };
+        let uniform =
+            compute_voronoi_with_options(&bps, VoronoiOptions::default().lloyd_until(0.0, 5))
+                .unwrap();
+        // A density concentrated on the right pulls the sites to the right.
+        let weighted = compute_voronoi_with_options(
+            &bps,
+            VoronoiOptions::default()
+                .lloyd_until(0.0, 5)
+                .lloyd_weighted(|location| (4.0 * location[0]).exp()),
+        )
+        .unwrap();
+        assert_abs_diff_eq!(mean_x(&uniform), 1.0, epsilon = 1e-9);
+        assert!(mean_x(&weighted) > mean_x(&uniform) + 0.2);
+        for (weighted_cell, uniform_cell) in weighted.iter().zip(&uniform) {
+            assert_eq!(weighted_cell.original_site(), uniform_cell.original_site());
+            assert!(weighted_cell.site()[0] > uniform_cell.site()[0]);
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_lloyd_weighted_constant() {
+        // A constant density results in the plain centroids.
+        let point_set = vec![[0.0, 0.0], [0.5, 0.2], [0.1, 0.6], [0.9, 0.9], [0.3, 0.3]];
+        let bps = square_bounded_point_set(point_set, 4.0);
+        let (uniform, _) =
+            compute_voronoi_with_report(&bps, VoronoiOptions::default().lloyd_until(0.0, 3))
+                .unwrap();
+        let (weighted, report) = compute_voronoi_with_report(
+            &bps,
+            VoronoiOptions::default()
+                .lloyd_iterations(3)
+                .lloyd_weighted(|_| 2.0),
+        )
+        .unwrap();
+        assert_eq!(report.iterations(), 3);
+        for (weighted_cell, uniform_cell) in weighted.iter().zip(&uniform) {
+            assert_abs_diff_eq!(weighted_cell.site()[0], uniform_cell.site()[0], epsilon = 1e-9);
+            assert_abs_diff_eq!(weighted_cell.site()[1], uniform_cell.site()[1], epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_bbox_matches_ring() {
+        let ring = square_bounded_point_set(grid_point_set(3, 2.0), 3.0);
+        let json = serde_json::json!({
+            "points": grid_point_set(3, 2.0),
+            "bbox": [-1.5, -1.5, 1.5, 1.5],
+        });
+        let bbox: BoundedPointSet = serde_json::from_value(json).unwrap();
+        let ring_cells = serde_json::to_value(compute_voronoi(&ring).unwrap()).unwrap();
+        let bbox_cells = serde_json::to_value(compute_voronoi(&bbox).unwrap()).unwrap();
+        assert_eq!(ring_cells, bbox_cells);
+    }
+
+    #[test]
+    fn test_compute_voronoi_circle_bound() {
+        let bps = BoundedPointSet::with_circle(
+            vec![[1.0, 0.0], [0.0, 1.0], [-1.0, 0.0], [0.0, -1.0]],
+            CircleBound::new([0.0, 0.0], 2.0),
+        );
+        let cells = compute_voronoi(&bps).unwrap();
+        assert_eq!(cells.len(), 4);
+        let area = cells[0].area();
+        assert!(area > 0.0);
+        for cell in &cells {
+            assert_abs_diff_eq!(cell.area(), area, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_hole_within_cell() {
+        let bps = BoundedPointSet::new(
+            vec![[-2.0, 0.0], [2.0, 0.0]],
+            vec![[-4.0, -4.0], [4.0, -4.0], [4.0, 4.0], [-4.0, 4.0]],
+        )
+        .with_holes(vec![vec![[0.5, 0.5], [1.5, 0.5], [1.5, 1.5], [0.5, 1.5]]]);
+        let cells = compute_voronoi(&bps).unwrap();
+        assert_eq!(cells.len(), 2);
+        for cell in &cells {
+            // The hole is centered together with the bound, so it lies within each cell.
+            assert_eq!(cell.holes().len(), 1);
+            assert_eq!(cell.as_polygon().interiors().len(), 1);
+            assert!(cell.as_polygon().interiors()[0].is_cw());
+            assert_ulps_eq!(cell.area(), 47.0);
+            assert_ulps_eq!(cell.perimeter(), 32.0);
+            let hole_centre = [cell.site()[0] + 1.0, cell.site()[1] + 1.0];
+            assert!(!cell.contains(hole_centre));
+            assert!(cell.contains([cell.site()[0] - 1.0, cell.site()[1] + 1.0]));
+        }
+        let serialized = serde_json::to_value(&cells[1]).unwrap();
+        assert_eq!(serialized["holes"].as_array().unwrap().len(), 1);
+        assert_eq!(serialized["holes"][0].as_array().unwrap().len(), 5);
+        let deserialized: BoundedVoronoiCell = serde_json::from_value(serialized).unwrap();
+        assert_ulps_eq!(deserialized.area(), 47.0);
+        // Cells without holes do not serialize the key.
+        let cells = compute_voronoi(BoundedPointSet::new(
+            vec![[-2.0, 0.0], [2.0, 0.0]],
+            vec![[-4.0, -4.0], [4.0, -4.0], [4.0, 4.0], [-4.0, 4.0]],
+        ))
+        .unwrap();
+        assert!(serde_json::to_value(&cells[0])
+            .unwrap()
+            .get("holes")
+            .is_none());
+    }
+
+    #[test]
+    fn test_compute_voronoi_bound_winding() {
+        let counter_clockwise = vec![[-2.0, -2.0], [2.0, -2.0], [0.0, 2.0], [-2.0, -2.0]];
+        let clockwise: Vec<[f64; 2]> = counter_clockwise.iter().rev().copied().collect();
+        let counter_clockwise_cells =
+            compute_voronoi(BoundedPointSet::new(grid_point_set(3, 1.0), counter_clockwise))
+                .unwrap();
+        let clockwise_cells =
+            compute_voronoi(BoundedPointSet::new(grid_point_set(3, 1.0), clockwise)).unwrap();
+        assert_eq!(
+            serde_json::to_value(&counter_clockwise_cells).unwrap(),
+            serde_json::to_value(&clockwise_cells).unwrap()
+        );
+        assert!(counter_clockwise_cells
+            .iter()
+            .all(|cell| cell.as_polygon().exterior().is_ccw()));
+    }
+
+    #[test]
+    fn test_apply_bound_clockwise_cell() {
+        let cell = BoundedVoronoiCell::new(
+            [0.5, 0.5],
+            vec![[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0], [0.0, 0.0]],
+        );
+        let bound = Polygon::new(
+            LineString::from(vec![(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]),
+            Vec::new(),
+        );
+        let bounded_cell = cell.apply_bound(bound).unwrap();
+        assert!(bounded_cell.as_polygon().exterior().is_ccw());
+        assert_ulps_eq!(bounded_cell.area(), 1.0);
+    }
+
+    #[test]
+    fn test_is_degenerate() {
+        let square = BoundedVoronoiCell::new(
+            [0.5, 0.5],
+            vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.0, 0.0]],
+        );
+        assert!(!square.is_degenerate(1e-9));
+        assert!(square.is_degenerate(2.0));
+        let segment = BoundedVoronoiCell::new([0.5, 0.0], vec![[0.0, 0.0], [1.0, 0.0], [0.0, 0.0]]);
+        assert!(segment.is_degenerate(0.0));
+        assert!(BoundedVoronoiCell::new([0.0, 0.0], Vec::new()).is_degenerate(0.0));
+    }
+
+    #[test]
+    fn test_is_degenerate_split_cell() {
+        // The first piece alone is a segment, but the second piece has an area.
+        let mut cell =
+            BoundedVoronoiCell::new([0.5, 0.5], vec![[0.0, 0.0], [1.0, 0.0], [0.0, 0.0]]);
+        cell.cells = vec![
+            vec![[0.0, 0.0], [1.0, 0.0], [0.0, 0.0]],
+            vec![[2.0, 0.0], [3.0, 0.0], [3.0, 1.0], [2.0, 0.0]],
+        ];
+        assert!(!cell.is_degenerate(0.1));
+        assert!(cell.is_degenerate(1.0));
+    }
+
+    #[test]
+    fn test_drop_degenerate_cells_sliver() {
+        let bound = Polygon::new(
+            LineString::from(vec![(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]),
+            Vec::new(),
+        );
+        // The cell only overlaps the bound in a thin sliver around the site.
+        let mut sliver = BoundedVoronoiCell::new(
+            [0.0, 0.0],
+            vec![
+                [-5.0, -1e-9],
+                [5.0, -1e-9],
+                [5.0, 1e-9],
+                [-5.0, 1e-9],
+                [-5.0, -1e-9],
+            ],
+        )
+        .apply_bound(&bound)
+        .unwrap();
+        sliver.neighbors = vec![1];
+        assert_abs_diff_eq!(sliver.area(), 4e-9, epsilon = 1e-12);
+        let mut first = BoundedVoronoiCell::new(
+            [0.0, -2.0],
+            vec![
+                [-1.0, -3.0],
+                [1.0, -3.0],
+                [1.0, -1.0],
+                [-1.0, -1.0],
+                [-1.0, -3.0],
+            ],
+        );
+        first.neighbors = vec![1, 2];
+        let mut last = BoundedVoronoiCell::new(
+            [0.0, 2.0],
+            vec![
+                [-1.0, 1.0],
+                [1.0, 1.0],
+                [1.0, 3.0],
+                [-1.0, 3.0],
+                [-1.0, 1.0],
+            ],
+        );
+        last.neighbors = vec![0, 1];
+        let cells = vec![first, sliver, last];
+
+        let report = find_degenerate_cells(&cells, 1e-6);
+        assert_eq!(report.kept(), 2);
+        assert_eq!(report.indices(), &vec![1]);
+        assert_eq!(report.sites(), &vec![[0.0, 0.0]]);
+        assert_eq!(find_degenerate_cells(&cells, 1e-10).indices(), &Vec::<usize>::new());
+
+        let (cells, dropped_report) = drop_degenerate_cells(cells, 1e-6);
+        assert_eq!(dropped_report, report);
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[0].site(), [0.0, -2.0]);
+        assert_eq!(cells[0].neighbors(), &vec![1]);
+        assert_eq!(cells[1].neighbors(), &vec![0]);
+    }
+
+    #[test]
+    fn test_compute_voronoi_show_progress() {
+        let bps = square_bounded_point_set(grid_point_set(3, 10.0), 2.0);
+        let cells = compute_voronoi(&bps).unwrap();
+        let cells_with_progress =
+            compute_voronoi_with_options(&bps, VoronoiOptions::default().show_progress(true))
+                .unwrap();
+        assert_eq!(
+            serde_json::to_value(cells).unwrap(),
+            serde_json::to_value(cells_with_progress).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_compute_delaunay() {
+        // A kite, whose Delaunay triangulation is split along the short diagonal.
+        let bps =
+            square_bounded_point_set(vec![[5.0, 0.0], [2.0, 1.0], [0.0, 0.0], [2.0, -1.0]], 1.0);
+        let mut triangles: Vec<[usize; 3]> = compute_delaunay(&bps)
+            .unwrap()
+            .into_iter()
+            .map(|mut triangle| {
+                triangle.sort();
+                triangle
+            })
+            .collect();
+        triangles.sort();
+        assert_eq!(triangles, vec![[0, 1, 2], [1, 2, 3]]);
+    }
+
+    #[test]
+    fn test_voronoi_vertices_symmetric() {
+        // The cocircular sites result in two triangles with the same circumcenter.
+        let diamond = vec![[1.0, 0.0], [0.0, 1.0], [-1.0, 0.0], [0.0, -1.0]];
+        let vertices =
+            voronoi_vertices(square_bounded_point_set(diamond.clone(), 0.5), false).unwrap();
+        assert_eq!(vertices.len(), 1);
+        assert!(vertices[0][0].abs() < 1e-12 && vertices[0][1].abs() < 1e-12);
+        // The bound centered around each site does not reach the central vertex.
+        assert!(voronoi_vertices(square_bounded_point_set(diamond.clone(), 0.5), true)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            voronoi_vertices(square_bounded_point_set(diamond, 2.0), true).unwrap(),
+            vertices
+        );
+    }
+
+    #[test]
+    fn test_voronoi_vertices_grid() {
+        let bps = square_bounded_point_set(
+            vec![[0.0, 0.0], [2.0, 0.0], [1.0, 1.0], [0.0, 2.0], [2.0, 2.0]],
+            10.0,
+        );
+        let vertices = voronoi_vertices(&bps, true).unwrap();
+        let expected = [[0.0, 1.0], [1.0, 0.0], [1.0, 2.0], [2.0, 1.0]];
+        assert_eq!(vertices.len(), expected.len());
+        for (vertex, expected) in vertices.iter().zip(expected) {
+            assert!((vertex[0] - expected[0]).abs() < 1e-12);
+            assert!((vertex[1] - expected[1]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_delaunay_edges_square_with_center() {
+        // The center is connected to each corner and the corners along the sides of the square.
+        let bps = square_bounded_point_set(
+            vec![[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0], [1.0, 1.0]],
+            4.0,
+        );
+        let edges = delaunay_edges(&bps).unwrap();
+        assert_eq!(
+            edges,
+            vec![
+                ([0.0, 0.0], [0.0, 2.0]),
+                ([0.0, 0.0], [1.0, 1.0]),
+                ([0.0, 0.0], [2.0, 0.0]),
+                ([0.0, 2.0], [1.0, 1.0]),
+                ([0.0, 2.0], [2.0, 2.0]),
+                ([1.0, 1.0], [2.0, 0.0]),
+                ([1.0, 1.0], [2.0, 2.0]),
+                ([2.0, 0.0], [2.0, 2.0]),
+            ]
+        );
+        // The edges connect exactly the neighboring cells.
+        let cells = compute_voronoi(&bps).unwrap();
+        let neighbor_count: usize = cells.iter().map(|cell| cell.neighbors().len()).sum();
+        assert_eq!(neighbor_count, 2 * edges.len());
+        for (a, b) in &edges {
+            let cell = cells.iter().find(|cell| cell.site() == *a).unwrap();
+            assert!(cell
+                .neighbors()
+                .iter()
+                .any(|&neighbor| cells[neighbor].site() == *b));
+        }
+    }
+
+    #[test]
+    fn test_compute_weighted_voronoi_heavier_site() {
+        let bps = square_bounded_point_set(vec![[-1.0, 0.0], [1.0, 0.0]], 8.0)
+            .with_weights(vec![2.0, 1.0]);
+        let cells = compute_weighted_voronoi(&bps, 200, VoronoiOptions::default()).unwrap();
+        assert_eq!(cells.len(), 2);
+        let heavy = cells
+            .iter()
+            .find(|cell| cell.site() == [-1.0, 0.0])
+            .unwrap();
+        let light = cells.iter().find(|cell| cell.site() == [1.0, 0.0]).unwrap();
+        assert!(heavy.area() > light.area());
+        assert_eq!(heavy.neighbors(), &vec![1]);
+        assert_eq!(light.neighbors(), &vec![0]);
+    }
+
+    #[test]
+    fn test_compute_l1_voronoi_staircase() {
+        let bps = square_bounded_point_set(vec![[0.0, 0.0], [2.0, 1.0]], 10.0);
+        let l1_cells = compute_l1_voronoi(&bps, 200, VoronoiOptions::default()).unwrap();
+        let euclidean_cells = compute_voronoi(&bps).unwrap();
+        assert_eq!(l1_cells.len(), 2);
+        assert_eq!(l1_cells[0].neighbors(), &vec![1]);
+        // Above the sites the L1 boundary is the vertical line x = 0.5, while the
+        // Euclidean bisector 2x + y = 2.5 leans towards the first site.
+        assert!(l1_cells[0].contains([0.4, 3.0]));
+        assert!(l1_cells[1].contains([0.6, 3.0]));
+        assert!(euclidean_cells[1].contains([0.4, 3.0]));
+        // Between the sites the L1 boundary is the diagonal x + y = 1.5.
+        assert!(l1_cells[0].contains([1.2, 0.1]));
+        assert!(l1_cells[1].contains([1.2, 0.5]));
+        // Below the sites the L1 boundary is the vertical line x = 1.5.
+        assert!(l1_cells[0].contains([1.4, -3.0]));
+        assert!(l1_cells[1].contains([1.6, -3.0]));
+    }
+
+    #[test]
+    fn test_compute_l1_voronoi_options() {
+        let bps = square_bounded_point_set(grid_point_set(3, 10.0), 2.0);
+        let options = VoronoiOptions::default().include_area(true);
+        for cell in compute_l1_voronoi(&bps, 100, options).unwrap() {
+            assert_ulps_eq!(cell.area.unwrap(), 4.0);
+        }
+    }
+
+    #[test]
+    fn test_compute_l1_voronoi_invalid_resolution() {
+        let bps = square_bounded_point_set(vec![[0.0, 0.0], [2.0, 1.0]], 10.0);
+        assert_eq!(
+            compute_l1_voronoi(bps, 0, VoronoiOptions::default()).unwrap_err(),
+            VoronoiError::InvalidResolution
+        );
+    }
+
+    #[test]
+    fn test_compute_weighted_voronoi_equal_weights() {
+        let bps = square_bounded_point_set(grid_point_set(3, 10.0), 2.0).with_weights(vec![3.0; 9]);
+        let cells = compute_weighted_voronoi(&bps, 100, VoronoiOptions::default()).unwrap();
+        assert_eq!(cells.len(), 9);
+        for cell in cells {
+            assert_ulps_eq!(cell.area(), 4.0);
+        }
+    }
+
+    #[test]
+    fn test_compute_weighted_voronoi_options() {
+        let bps = square_bounded_point_set(grid_point_set(3, 10.0), 2.0).with_weights(vec![3.0; 9]);
+        let options = VoronoiOptions::default().include_area(true);
+        for cell in compute_weighted_voronoi(&bps, 100, options).unwrap() {
+            assert_ulps_eq!(cell.area.unwrap(), 4.0);
+        }
+    }
+
+    #[test]
+    fn test_compute_weighted_voronoi_invalid_resolution() {
+        let bps = square_bounded_point_set(grid_point_set(3, 10.0), 2.0);
+        assert_eq!(
+            compute_weighted_voronoi(&bps, 0, VoronoiOptions::default()).unwrap_err(),
+            VoronoiError::InvalidResolution
+        );
+    }
+
+    #[test]
+    fn test_compute_power_diagram_equal_radii() {
+        let point_set = vec![
+            [0.3, 0.1],
+            [5.2, -0.4],
+            [10.1, 0.6],
+            [-0.2, 4.7],
+            [4.6, 5.3],
+            [9.8, 5.1],
+            [0.4, 10.2],
+            [5.1, 9.6],
+            [10.3, 10.4],
+        ];
+        let bps = square_bounded_point_set(point_set.clone(), 6.0);
+        let bound = bps.bounding_polygon().unwrap();
+        let voronoi_cells = compute_voronoi(&bps).unwrap();
+        let power_cells =
+            compute_power_diagram(&point_set, &[1.5; 9], &bound, VoronoiOptions::default())
+                .unwrap();
+        assert_eq!(power_cells.len(), voronoi_cells.len());
+        for power_cell in &power_cells {
+            let voronoi_cell = voronoi_cells
+                .iter()
+                .find(|cell| cell.site() == power_cell.site())
+                .unwrap();
+            assert_abs_diff_eq!(power_cell.area(), voronoi_cell.area(), epsilon = 1e-9);
+            let power_neighbors: HashSet<[u64; 2]> = power_cell
+                .neighbors()
+                .iter()
+                .map(|&neighbor| power_cells[neighbor].site().map(f64::to_bits))
+                .collect();
+            let voronoi_neighbors: HashSet<[u64; 2]> = voronoi_cell
+                .neighbors()
+                .iter()
+                .map(|&neighbor| voronoi_cells[neighbor].site().map(f64::to_bits))
+                .collect();
+            assert_eq!(power_neighbors, voronoi_neighbors);
+        }
+    }
+
+    #[test]
+    fn test_compute_power_diagram_radical_axis() {
+        let bps = square_bounded_point_set(vec![], 8.0);
+        let bound = bps.bounding_polygon().unwrap();
+        let cells = compute_power_diagram(
+            &[[-1.0, 0.0], [1.0, 0.0]],
+            &[2.0, 1.0],
+            &bound,
+            VoronoiOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(cells.len(), 2);
+        // The radical axis lies at x = 0.75 and the bounds centered around
+        // the sites reach from x = -5 to x = 3 and from x = -3 to x = 5.
+        assert_abs_diff_eq!(cells[0].area(), 5.75 * 8.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(cells[1].area(), 4.25 * 8.0, epsilon = 1e-9);
+        assert_eq!(cells[0].neighbors(), &vec![1]);
+        assert_eq!(cells[1].neighbors(), &vec![0]);
+    }
+
+    #[test]
+    fn test_compute_power_diagram_hidden_site() {
+        let bound = square_bounded_point_set(vec![], 4.0)
+            .bounding_polygon()
+            .unwrap();
+        // The radical axes of the large sites enclose no region closer to the centre.
+        let cells = compute_power_diagram(
+            &[[-1.0, 0.0], [1.0, 0.0], [0.0, 0.0], [0.0, 5.0]],
+            &[2.0, 2.0, 0.0, 0.0],
+            &bound,
+            VoronoiOptions::default(),
+        )
+        .unwrap();
+        let sites: Vec<[f64; 2]> = cells.iter().map(|cell| cell.site()).collect();
+        assert_eq!(sites, vec![[-1.0, 0.0], [1.0, 0.0], [0.0, 5.0]]);
+        assert_eq!(cells[0].neighbors(), &vec![1, 2]);
+        assert_eq!(cells[1].neighbors(), &vec![0, 2]);
+        assert_eq!(cells[2].neighbors(), &vec![0, 1]);
+    }
+
+    #[test]
+    fn test_compute_power_diagram_options() {
+        let bound = square_bounded_point_set(vec![], 2.0)
+            .bounding_polygon()
+            .unwrap();
+        let options = VoronoiOptions::default().include_area(true);
+        let cells =
+            compute_power_diagram(&grid_point_set(3, 10.0), &[0.0; 9], &bound, options).unwrap();
+        assert_eq!(cells.len(), 9);
+        for cell in cells {
+            assert_ulps_eq!(cell.area.unwrap(), 4.0);
+        }
+    }
+
+    #[test]
+    fn test_compute_farthest_voronoi_square() {
+        // The corners of a square and its centre, which lies inside the convex hull.
+        let sites = [
+            [1.0, 1.0],
+            [-1.0, 1.0],
+            [0.0, 0.0],
+            [-1.0, -1.0],
+            [1.0, -1.0],
+        ];
+        let bound = Polygon::new(
+            LineString::from(vec![(-2.0, -2.0), (2.0, -2.0), (2.0, 2.0), (-2.0, 2.0)]),
+            vec![],
+        );
+        let cells = compute_farthest_voronoi(&sites, &bound).unwrap();
+        let ordered_sites: Vec<[f64; 2]> = cells.iter().map(|cell| cell.site()).collect();
+        assert_eq!(
+            ordered_sites,
+            vec![
+                [-1.0, -1.0],
+                [-1.0, 1.0],
+                [0.0, 0.0],
+                [1.0, -1.0],
+                [1.0, 1.0]
+            ]
+        );
+        assert!(cells[2].cell().is_empty());
+        assert!(cells[2].neighbors().is_empty());
+        // Each corner owns the quadrant of the bound opposite to it, which is
+        // adjacent to the quadrants of the two neighboring corners.
+        for (index, opposite_quadrant, neighbors) in [
+            (0, [1.0, 1.0], vec![1, 3]),
+            (1, [1.0, -1.0], vec![0, 4]),
+            (3, [-1.0, 1.0], vec![0, 4]),
+            (4, [-1.0, -1.0], vec![1, 3]),
+        ] {
+            let cell = &cells[index];
+            assert_ulps_eq!(cell.area(), 4.0);
+            let centroid = cell.centroid().unwrap();
+            assert_ulps_eq!(centroid[0], opposite_quadrant[0]);
+            assert_ulps_eq!(centroid[1], opposite_quadrant[1]);
+            assert!(!cell.contains(cell.site()));
+            assert_eq!(cell.neighbors(), &neighbors);
+        }
+    }
+
+    #[test]
+    fn test_compute_farthest_voronoi_single_site() {
+        let bound = square_bounded_point_set(vec![], 2.0)
+            .bounding_polygon()
+            .unwrap();
+        let cells = compute_farthest_voronoi(&[[5.0, 5.0], [5.0, 5.0]], &bound).unwrap();
+        assert_eq!(cells.len(), 1);
+        assert_ulps_eq!(cells[0].area(), 4.0);
+        assert_eq!(
+            compute_farthest_voronoi(&[[f64::NAN, 0.0]], &bound).unwrap_err(),
+            VoronoiError::EmptyPointSet
+        );
+    }
+
+    #[test]
+    fn test_compute_power_diagram_invalid_radii() {
+        let bound = square_bounded_point_set(vec![], 8.0)
+            .bounding_polygon()
+            .unwrap();
+        assert_eq!(
+            compute_power_diagram(&[[0.0, 0.0]], &[1.0, 2.0], &bound, VoronoiOptions::default())
+                .unwrap_err(),
+            VoronoiError::RadiusCountMismatch {
+                radii: 2,
+                points: 1
+            }
+        );
+        assert_eq!(
+            compute_power_diagram(&[[0.0, 0.0]], &[-1.0], &bound, VoronoiOptions::default())
+                .unwrap_err(),
+            VoronoiError::InvalidRadius { radius: -1.0 }
+        );
+    }
+
+    #[test]
+    fn test_compute_delaunay_empty() {
+        let bps = square_bounded_point_set(vec![], 1.0);
+        assert_eq!(compute_delaunay(&bps).unwrap_err(), VoronoiError::EmptyPointSet);
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_vertex_count() {
+        let closed = BoundedVoronoiCell::new(
+            [0.0, 0.0],
+            vec![
+                [-1.0, -1.0],
+                [1.0, -1.0],
+                [1.0, 1.0],
+                [-1.0, 1.0],
+                [-1.0, -1.0],
+            ],
+        );
+        assert_eq!(closed.vertex_count(), 4);
+        let open = BoundedVoronoiCell::new([0.0, 0.0], vec![[-1.0, -1.0], [1.0, -1.0], [1.0, 1.0]]);
+        assert_eq!(open.vertex_count(), 3);
+        assert_eq!(BoundedVoronoiCell::new([0.0, 0.0], vec![[0.0, 0.0]]).vertex_count(), 1);
+        assert_eq!(BoundedVoronoiCell::new([0.0, 0.0], vec![]).vertex_count(), 0);
+        // The vertices of all pieces are counted.
+        let split = BoundedVoronoiCell {
+            cells: vec![
+                vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.0, 0.0]],
+                vec![[2.0, 0.0], [3.0, 0.0], [2.5, 1.0]],
+            ],
+            ..BoundedVoronoiCell::new([0.5, 0.5], Vec::new())
+        };
+        assert_eq!(split.vertex_count(), 7);
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_with_rounded_coords() {
+        let cell = BoundedVoronoiCell::new(
+            [0.123456, -0.004],
+            vec![
+                [0.0, 0.0],
+                [1.005001, 0.0],
+                [1.0, 0.99999],
+                [-0.00049, 1.23456789],
+            ],
+        )
+        .with_rounded_coords(2);
+        assert_eq!(cell.site(), [0.12, 0.0]);
+        assert!(cell.site()[1].is_sign_positive());
+        assert_eq!(cell.cell(), &vec![[0.0, 0.0], [1.01, 0.0], [1.0, 1.0], [0.0, 1.23]]);
+        assert!(!cell.is_degenerate(f64::MIN_POSITIVE));
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_with_rounded_coords_degenerate() {
+        // The cell is smaller than the rounding precision and collapses into a single point.
+        let cell = BoundedVoronoiCell::new(
+            [0.5, 0.5],
+            vec![
+                [0.501, 0.501],
+                [0.502, 0.501],
+                [0.502, 0.502],
+                [0.501, 0.502],
+            ],
+        );
+        assert!(!cell.is_degenerate(f64::MIN_POSITIVE));
+        let cell = cell.with_rounded_coords(2);
+        assert!(cell.is_degenerate(f64::MIN_POSITIVE));
+        assert_eq!(cell.cell(), &vec![[0.5, 0.5]; 4]);
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_densify() {
+        let rectangle = vec![[0.0, 0.0], [4.0, 0.0], [4.0, 1.0], [0.0, 1.0]];
+        let mut cell = BoundedVoronoiCell::new([2.0, 0.5], rectangle.clone());
+        // The long edges are split into 4 segments each including the closing edge,
+        // while the short edges are kept.
+        cell.densify(1.0);
+        assert_eq!(cell.vertex_count(), 10);
+        assert_eq!(cell.cell()[..3], [[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]]);
+        assert_eq!(cell.cell().last(), Some(&[0.0, 1.0]));
+        assert_ulps_eq!(cell.area(), 4.0);
+        assert_ulps_eq!(cell.perimeter(), 10.0);
+        // A closed ring stays closed.
+        let mut closed_cell =
+            BoundedVoronoiCell::new([2.0, 0.5], rectangle.clone()).with_closed_rings();
+        closed_cell.densify(1.0);
+        assert_eq!(closed_cell.vertex_count(), 10);
+        assert_eq!(closed_cell.cell().len(), 11);
+        // Lengths exceeding all edges and non-positive lengths are a no-op.
+        for max_segment_len in [4.0, 100.0, 0.0, -1.0, f64::NAN] {
+            let mut cell = BoundedVoronoiCell::new([2.0, 0.5], rectangle.clone());
+            cell.densify(max_segment_len);
+            assert_eq!(cell.cell(), &rectangle);
+        }
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_simplify() {
+        // The edges of the square carry redundant and nearly collinear vertices.
+        let square = vec![
+            [0.0, 0.0],
+            [1.0, 0.001],
+            [2.0, 0.0],
+            [3.0, 0.0005],
+            [4.0, 0.0],
+            [4.0, 2.0],
+            [4.0, 4.0],
+            [2.0, 4.001],
+            [0.0, 4.0],
+            [0.0, 2.0],
+        ];
+        let mut cell = BoundedVoronoiCell::new([2.0, 2.0], square.clone());
+        cell.area = Some(cell.area());
+        cell.simplify(0.01);
+        assert_eq!(cell.cell(), &vec![[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]]);
+        assert_abs_diff_eq!(cell.area(), 16.0, epsilon = 1e-2);
+        assert_eq!(cell.area, Some(cell.area()));
+        // A closed ring stays closed.
+        let mut closed_cell =
+            BoundedVoronoiCell::new([2.0, 2.0], square.clone()).with_closed_rings();
+        closed_cell.simplify(0.01);
+        assert_eq!(closed_cell.vertex_count(), 4);
+        assert_eq!(closed_cell.cell().len(), 5);
+        // Non-positive tolerances are a no-op.
+        for epsilon in [0.0, -1.0, f64::NAN] {
+            let mut cell = BoundedVoronoiCell::new([2.0, 2.0], square.clone());
+            cell.simplify(epsilon);
+            assert_eq!(cell.cell(), &square);
+        }
+        // Removing the narrow spike would move the site outside of its cell.
+        let spike = vec![
+            [0.0, 0.0],
+            [4.0, 0.0],
+            [4.0, 1.0],
+            [2.05, 1.0],
+            [2.0, 1.05],
+            [1.95, 1.0],
+            [0.0, 1.0],
+        ];
+        let mut cell = BoundedVoronoiCell::new([2.0, 1.03], spike.clone());
+        cell.simplify(0.1);
+        assert_eq!(cell.cell(), &spike);
+    }
+
+    #[test]
+    fn test_round_to_decimals() {
+        assert_eq!(round_to_decimals(1.23456, 2), 1.23);
+        assert_eq!(round_to_decimals(-1.235001, 2), -1.24);
+        assert_eq!(round_to_decimals(1e300, 100), 1e300);
+        assert_eq!(round_to_decimals(0.5, u32::MAX), 0.5);
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_centroid() {
+        let cell = BoundedVoronoiCell::new(
+            [0.0, 0.0],
+            vec![
+                [-1.0, -1.0],
+                [3.0, -1.0],
+                [3.0, 1.0],
+                [-1.0, 1.0],
+                [-1.0, -1.0],
+            ],
+        );
+        let centroid = cell.centroid().unwrap();
+        assert_ulps_eq!(centroid[0], 1.0);
+        assert_ulps_eq!(centroid[1], 0.0);
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_centroid_degenerate() {
+        let cell =
+            BoundedVoronoiCell::new([0.0, 0.0], vec![[-1.0, -1.0], [1.0, 1.0], [-1.0, -1.0]]);
+        assert!(cell.centroid().is_none());
+        let cell = BoundedVoronoiCell::new([0.0, 0.0], vec![]);
+        assert!(cell.centroid().is_none());
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_centroid_off_center() {
+        // The site lies close to the corner of its cell, so the bounded cell
+        // is cut off and its centroid shifts away from the site towards the cell.
+        let cell = BoundedVoronoiCell::new(
+            [1.0, 1.0],
+            vec![
+                [0.0, 0.0],
+                [10.0, 0.0],
+                [10.0, 10.0],
+                [0.0, 10.0],
+                [0.0, 0.0],
+            ],
+        );
+        let bps = square_bounded_point_set(vec![], 4.0);
+        let bounded_cell = cell.apply_bound(bps.bounding_polygon().unwrap()).unwrap();
+        let centroid = bounded_cell.centroid().unwrap();
+        assert_ulps_eq!(centroid[0], 1.5);
+        assert_ulps_eq!(centroid[1], 1.5);
+        assert_ne!(centroid, bounded_cell.site());
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_area_square() {
+        let cell = BoundedVoronoiCell::new(
+            [0.0, 0.0],
+            vec![
+                [-1.0, -1.0],
+                [1.0, -1.0],
+                [1.0, 1.0],
+                [-1.0, 1.0],
+                [-1.0, -1.0],
+            ],
+        );
+        assert_ulps_eq!(cell.area(), 4.0);
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_polygon_cache() {
+        let mut cell = BoundedVoronoiCell::new(
+            [2.0, 0.5],
+            vec![[0.0, 0.0], [4.0, 0.0], [4.0, 1.0], [0.0, 1.0]],
+        );
+        // The polygons are only built once.
+        assert!(std::ptr::eq(cell.as_polygon(), cell.as_polygon()));
+        assert!(std::ptr::eq(cell.multi_polygon(), cell.multi_polygon()));
+        assert_eq!(cell.as_polygon().exterior().0.len(), 5);
+        // Changing the rings rebuilds the polygons.
+        cell.densify(1.0);
+        assert_eq!(cell.as_polygon().exterior().0.len(), 11);
+        assert_eq!(cell.multi_polygon().0[0], *cell.as_polygon());
+        let cell = cell
+            .try_map_coords(|[x, y]| Ok::<_, Infallible>([2.0 * x, y]))
+            .unwrap();
+        assert_ulps_eq!(cell.area(), 8.0);
+        // The cache is not serialized.
+        let value = serde_json::to_value(&cell).unwrap();
+        assert!(value.get("polygons").is_none());
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_to_polygon() {
+        // The ring of the cell is not closed explicitly.
+        let cell = BoundedVoronoiCell::new(
+            [0.5, 0.5],
+            vec![
+                [0.0, 0.0],
+                [2.0, 0.0],
+                [2.0, 1.0],
+                [1.0, 1.0],
+                [1.0, 2.0],
+                [0.0, 2.0],
+            ],
+        );
+        let polygon = cell.to_polygon();
+        assert!(polygon.exterior().is_closed());
+        assert_eq!(polygon.exterior().0.len(), 7);
+        assert_ulps_eq!(polygon.unsigned_area(), cell.area());
+        let cell: BoundedVoronoiCell = serde_json::from_value(serde_json::json!({
+            "site": [0.0, 0.0],
+            "cell": [[-2.0, -2.0], [2.0, -2.0], [2.0, 2.0], [-2.0, 2.0]],
+            "holes": [[[0.5, 0.5], [1.5, 0.5], [1.5, 1.5], [0.5, 1.5]]]
+        }))
+        .unwrap();
+        let polygon = Polygon::from(&cell);
+        assert_eq!(polygon.interiors().len(), 1);
+        assert!(polygon.interiors()[0].is_closed());
+        assert_ulps_eq!(polygon.unsigned_area(), 15.0);
+        assert_ulps_eq!(polygon.unsigned_area(), cell.area());
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_perimeter_square() {
+        let cell = BoundedVoronoiCell::new(
+            [0.5, 0.5],
+            vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]],
+        );
+        assert_ulps_eq!(cell.perimeter(), 4.0);
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_compactness() {
+        let square = BoundedVoronoiCell::new(
+            [0.5, 0.5],
+            vec![[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]],
+        );
+        assert_ulps_eq!(square.compactness(), std::f64::consts::FRAC_PI_4);
+        let strip = BoundedVoronoiCell::new(
+            [0.5, 0.05],
+            vec![[0.0, 0.0], [1.0, 0.0], [1.0, 0.1], [0.0, 0.1]],
+        );
+        assert!(strip.compactness() < square.compactness());
+        assert!(BoundedVoronoiCell::new([0.0, 0.0], vec![])
+            .compactness()
+            .is_nan());
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_contains() {
+        let cell = BoundedVoronoiCell::new(
+            [0.5, 0.5],
+            vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.0, 0.0]],
+        );
+        assert!(cell.contains([0.5, 0.25]));
+        assert!(!cell.contains([1.0, 0.5]));
+        assert!(!cell.contains([0.0, 0.0]));
+        assert!(!cell.contains([1.5, 0.5]));
+        assert!(cell.as_polygon().contains(&geo::Point::new(0.5, 0.25)));
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_area_concave() {
+        // An L-shaped cell.
+        let cell = BoundedVoronoiCell::new(
+            [0.5, 0.5],
+            vec![
+                [0.0, 0.0],
+                [2.0, 0.0],
+                [2.0, 1.0],
+                [1.0, 1.0],
+                [1.0, 2.0],
+                [0.0, 2.0],
+            ],
+        );
+        assert_ulps_eq!(cell.area(), 3.0);
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_area_bounded() {
+        let cell = BoundedVoronoiCell::new(
+            [0.0, 0.0],
+            vec![
+                [-2.0, -2.0],
+                [2.0, -2.0],
+                [2.0, 2.0],
+                [-2.0, 2.0],
+                [-2.0, -2.0],
+            ],
+        );
+        let bps = square_bounded_point_set(vec![], 1.0);
+        let bounded_cell = cell.apply_bound(bps.bounding_polygon().unwrap()).unwrap();
+        assert_ulps_eq!(bounded_cell.area(), 1.0);
+    }
+
+    #[test]
+    fn test_compute_voronoi_include_index() {
+        let points = vec![
+            [10.0, 0.0],
+            [0.0, 10.0],
+            [0.0, 0.0],
+            [10.0, 10.0],
+            [0.0, 0.0],
+        ];
+        let bps = square_bounded_point_set(points, 2.0);
+        let cells = compute_voronoi(&bps).unwrap();
+        assert!(cells.iter().all(|cell| cell.index().is_none()));
+        assert!(serde_json::to_value(&cells[0])
+            .unwrap()
+            .get("index")
+            .is_none());
+        for options in [
+            VoronoiOptions::default(),
+            VoronoiOptions::default().input_order(true),
+        ] {
+            let sites = if options.input_order {
+                bps.ordered_point_set()
+            } else {
+                bps.sorted_point_set()
+            };
+            let cells = compute_voronoi_with_options(&bps, options.include_index(true)).unwrap();
+            assert_eq!(cells.len(), 4);
+            for cell in &cells {
+                let site = sites[cell.index().unwrap()];
+                assert_eq!(cell.site(), [site.x(), site.y()]);
+            }
+            assert_eq!(
+                serde_json::to_value(&cells[1]).unwrap()["index"],
+                serde_json::json!(cells[1].index().unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_include_area() {
+        let bps = square_bounded_point_set(grid_point_set(3, 10.0), 2.0);
+        let cells =
+            compute_voronoi_with_options(&bps, VoronoiOptions::default().include_area(true))
+                .unwrap();
+        assert_eq!(cells.len(), 9);
+        for cell in cells {
+            assert_ulps_eq!(cell.area.unwrap(), 4.0);
+            let serialized = serde_json::to_value(&cell).unwrap();
+            assert_ulps_eq!(serialized["area"].as_f64().unwrap(), 4.0);
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_exclude_area() {
+        let bps = square_bounded_point_set(grid_point_set(3, 10.0), 2.0);
+        let cells = compute_voronoi(&bps).unwrap();
+        assert_eq!(cells.len(), 9);
+        for cell in cells {
+            assert!(cell.area.is_none());
+            let serialized = serde_json::to_value(&cell).unwrap();
+            assert!(serialized.get("area").is_none());
+        }
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_apply_bound_split() {
+        // A diagonal strip through the site, which crosses the notch of the L-shaped bound.
+        let cell = BoundedVoronoiCell::new(
+            [1.8, 0.2],
+            vec![
+                [2.0, -0.1],
+                [2.2, -0.1],
+                [-0.1, 2.2],
+                [-0.1, 2.0],
+                [2.0, -0.1],
+            ],
+        );
+        // The bound is already centered around the site.
+        let bound: Polygon = Polygon::new(
+            LineString::from(vec![
+                (-0.4, -2.0),
+                (4.0, -2.0),
+                (4.0, 0.5),
+                (0.5, 0.5),
+                (0.5, 2.4),
+                (-0.4, 2.4),
+                (-0.4, -2.0),
+            ]),
+            vec![],
+        );
+        let unbounded_area = cell.area();
+        let bounded_cell = cell.apply_bound(bound).unwrap();
+        let pieces = bounded_cell.cells();
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(&pieces[0], bounded_cell.cell());
+        assert!(bounded_cell
+            .as_polygon()
+            .contains(&geo::Point::new(1.8, 0.2)));
+        assert!(bounded_cell.area() > bounded_cell.as_polygon().unsigned_area());
+        assert!(bounded_cell.area() < unbounded_area);
+        let serialized = serde_json::to_value(&bounded_cell).unwrap();
+        assert_eq!(serialized["cells"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_apply_bound_single_piece() {
+        let cell = BoundedVoronoiCell::new(
+            [0.0, 0.0],
+            vec![
+                [-2.0, -2.0],
+                [2.0, -2.0],
+                [2.0, 2.0],
+                [-2.0, 2.0],
+                [-2.0, -2.0],
+            ],
+        );
+        let bound = square_bounded_point_set(vec![], 2.0)
+            .bounding_polygon()
+            .unwrap();
+        let bounded_cell = cell.apply_bound(bound).unwrap();
+        assert_eq!(bounded_cell.cells(), vec![bounded_cell.cell().clone()]);
+        let serialized = serde_json::to_value(&bounded_cell).unwrap();
+        assert!(serialized.get("cells").is_none());
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_apply_snapped_bound() {
+        // The site is collinear with the left edge of the cell, so it lies
+        // on the border of its cell and cannot be found without snapping.
+        let cell = BoundedVoronoiCell::new(
+            [0.1, 0.2],
+            vec![[0.0, 0.0], [0.3, 0.6], [0.3, 1.0], [0.0, 1.0], [0.0, 0.0]],
+        );
+        let bound = square_bounded_point_set(vec![], 1.0)
+            .bounding_polygon()
+            .unwrap();
+        assert_eq!(
+            cell.clone().apply_bound(&bound).unwrap_err(),
+            VoronoiError::NoIntersection { site: [0.1, 0.2] }
+        );
+        let bounded_cell = cell.apply_snapped_bound(&bound, 1e-9).unwrap();
+        assert_eq!(bounded_cell.site(), [0.1, 0.2]);
+        assert!(bounded_cell.area() > 0.0);
+        for point in bounded_cell.cell() {
+            assert_abs_diff_eq!(point[0], snap_to_grid(point[0], 1e-9));
+            assert_abs_diff_eq!(point[1], snap_to_grid(point[1], 1e-9));
+        }
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_apply_snapped_bound_rounding_error() {
+        // The site lies on the left edge of the cell apart from a floating point
+        // error, so it lies outside of its cell and cannot be found without snapping.
+        let cell = BoundedVoronoiCell::new(
+            [0.1, 0.19999999999999998],
+            vec![[0.0, 0.0], [0.3, 0.6], [0.3, 1.0], [0.0, 1.0], [0.0, 0.0]],
+        );
+        let bound = square_bounded_point_set(vec![], 1.0)
+            .bounding_polygon()
+            .unwrap();
+        assert_eq!(
+            cell.clone().apply_bound(&bound).unwrap_err(),
+            VoronoiError::NoIntersection {
+                site: [0.1, 0.19999999999999998]
+            }
+        );
+        let bounded_cell = cell.apply_snapped_bound(&bound, 1e-9).unwrap();
+        assert_eq!(bounded_cell.site(), [0.1, 0.19999999999999998]);
+        assert!(bounded_cell.area() > 0.0);
+        for point in bounded_cell.cell() {
+            assert_abs_diff_eq!(point[0], snap_to_grid(point[0], 1e-9));
+            assert_abs_diff_eq!(point[1], snap_to_grid(point[1], 1e-9));
+        }
+    }
+
+    #[test]
+    fn test_voronoioptions_snap_precision() {
+        assert_eq!(VoronoiOptions::default().snap_precision, None);
+        assert_eq!(
+            VoronoiOptions::default()
+                .snap_precision(1e-6)
+                .snap_precision,
+            Some(1e-6)
+        );
+        assert_eq!(VoronoiOptions::default().snap_precision(0.0).snap_precision, None);
+        assert_eq!(
+            VoronoiOptions::default()
+                .snap_precision(f64::NAN)
+                .snap_precision,
+            None
+        );
+    }
+
+    #[test]
+    fn test_voronoioptions_inset() {
+        assert_eq!(VoronoiOptions::default().inset, None);
+        assert_eq!(VoronoiOptions::default().inset(0.5).inset, Some(0.5));
+        assert_eq!(VoronoiOptions::default().inset(0.0).inset, None);
+        assert_eq!(VoronoiOptions::default().inset(-1.0).inset, None);
+        assert_eq!(VoronoiOptions::default().inset(f64::INFINITY).inset, None);
+    }
+
+    #[test]
+    fn test_compute_voronoi_max_sites() {
+        // The duplicate site does not count towards the limit.
+        let mut point_set = grid_point_set(3, 10.0);
+        point_set.push(point_set[0]);
+        let bps = square_bounded_point_set(point_set, 2.0);
+        let options = VoronoiOptions::default().max_sites(9);
+        assert_eq!(compute_voronoi_with_options(&bps, &options).unwrap().len(), 9);
+        let options = VoronoiOptions::default().max_sites(8);
+        let expected_error = VoronoiError::TooManySites {
+            sites: 9,
+            max_sites: 8,
+        };
+        assert_eq!(compute_voronoi_with_options(&bps, &options).unwrap_err(), expected_error);
+        assert_eq!(
+            compute_voronoi_iter_with_options(&bps, options)
+                .next()
+                .unwrap()
+                .unwrap_err(),
+            expected_error
+        );
+    }
+
+    #[test]
+    fn test_compute_voronoi_merge_tolerance() {
+        let mut point_set = grid_point_set(3, 10.0);
+        point_set.push([point_set[4][0] + 1e-12, point_set[4][1]]);
+        let bps = square_bounded_point_set(point_set, 2.0);
+        assert_eq!(compute_voronoi(&bps).unwrap().len(), 10);
+        let options = VoronoiOptions::default().merge_tolerance(1e-9);
+        let cells = compute_voronoi_with_options(&bps, options).unwrap();
+        assert_eq!(cells.len(), 9);
+        assert_eq!(cells[4].site(), [10.0, 10.0]);
+        assert_ulps_eq!(cells[4].area(), 4.0);
+    }
+
+    #[test]
+    fn test_voronoioptions_merge_tolerance() {
+        assert_eq!(VoronoiOptions::default().merge_tolerance, None);
+        let options = VoronoiOptions::default().merge_tolerance(1e-9);
+        assert_eq!(options.merge_tolerance, Some(1e-9));
+        assert_eq!(
+            VoronoiOptions::default()
+                .merge_tolerance(0.0)
+                .merge_tolerance,
+            None
+        );
+        assert_eq!(
+            VoronoiOptions::default()
+                .merge_tolerance(f64::NAN)
+                .merge_tolerance,
+            None
+        );
+    }
+
+    #[test]
+    fn test_compute_voronoi_inset() {
+        let bps = square_bounded_point_set(grid_point_set(3, 10.0), 2.0);
+        let total_area = |options: VoronoiOptions| {
+            compute_voronoi_with_options(&bps, options)
+                .unwrap()
+                .iter()
+                .map(BoundedVoronoiCell::area)
+                .sum::<f64>()
+        };
+        assert!((total_area(VoronoiOptions::default()) - 36.0).abs() < 1e-9);
+        // Each 2 x 2 cell shrinks to 1 x 1.
+        assert!((total_area(VoronoiOptions::default().inset(0.5)) - 9.0).abs() < 1e-9);
+        assert!((total_area(VoronoiOptions::default().inset(-0.5)) - 36.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_inset_bound_concave() {
+        let bound = l_shaped_bounded_point_set(vec![[0.25, 0.25]])
+            .bounding_multi_polygon()
+            .unwrap();
+        let inset = inset_bound(&bound, 0.2).unwrap();
+        assert_eq!(inset.0.len(), 1);
+        assert!(inset.unsigned_area() < bound.unsigned_area());
+        assert!(bound.contains(&inset));
+        // The reflex corner is mitered.
+        for coordinate in inset.coords_iter() {
+            let distance = geo::Point::from(coordinate).euclidean_distance(bound.0[0].exterior());
+            assert!(distance >= 0.2 - 1e-9);
+        }
+        // The arms of the L shape are narrower than twice the inset.
+        assert_eq!(inset_bound(&bound, 0.3), Err(VoronoiError::InvalidInset { distance: 0.3 }));
+        assert_eq!(inset_bound(&bound, 0.0).unwrap(), bound);
+    }
+
+    #[test]
+    fn test_compute_voronoi_include_unbounded() {
+        let bps = square_bounded_point_set(grid_point_set(3, 10.0), 2.0);
+        let cells =
+            compute_voronoi_with_options(&bps, VoronoiOptions::default().include_unbounded(true))
+                .unwrap();
+        assert_eq!(cells.len(), 9);
+        for cell in cells {
+            let unbounded_cell = cell.unbounded_cell().clone().unwrap();
+            let unbounded_polygon = ring_to_polygon(&unbounded_cell);
+            assert!(unbounded_polygon.contains(cell.as_polygon()));
+            assert!(unbounded_polygon.unsigned_area() > cell.area());
+            let serialized = serde_json::to_value(&cell).unwrap();
+            assert!(serialized.get("unbounded_cell").is_some());
+        }
+        let cells = compute_voronoi(&bps).unwrap();
+        assert!(cells.iter().all(|cell| cell.unbounded_cell().is_none()));
+    }
+
+    #[test]
+    fn test_compute_voronoi_multi_polygon_bound() {
+        // Two disjoint squares, where the larger one contains the centre of the bound.
+        let bps = BoundedPointSet::with_multi_polygon(
+            vec![[1.0, 1.0], [2.0, 1.0], [4.5, 4.5]],
+            vec![
+                vec![[0.0, 0.0], [3.0, 0.0], [3.0, 3.0], [0.0, 3.0], [0.0, 0.0]],
+                vec![[4.0, 4.0], [5.0, 4.0], [5.0, 5.0], [4.0, 5.0], [4.0, 4.0]],
+            ],
+        );
+        let cells = compute_voronoi(bps).unwrap();
+        assert_eq!(cells.len(), 3);
+        for cell in &cells {
+            assert!(cell
+                .as_polygon()
+                .contains(&geo::Point::new(cell.site()[0], cell.site()[1])));
+        }
+        // The smaller square centered around the upper site lies completely within its cell.
+        let upper_cell = &cells[2];
+        assert_eq!(upper_cell.site(), [4.5, 4.5]);
+        assert_eq!(upper_cell.cells().len(), 2);
+        assert_abs_diff_eq!(
+            upper_cell.area() - upper_cell.as_polygon().unsigned_area(),
+            1.0,
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_compute_voronoi_close_rings() {
+        let options = VoronoiOptions::default()
+            .include_unbounded(true)
+            .close_rings(true);
+        let cells = compute_voronoi_with_options(
+            square_bounded_point_set(vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]], 2.0),
+            options,
+        )
+        .unwrap();
+        for cell in cells {
+            let unbounded_cell = cell.unbounded_cell().as_ref().unwrap();
+            assert_eq!(unbounded_cell.first(), unbounded_cell.last());
+            assert_eq!(cell.cell().first(), cell.cell().last());
+            // Already closed rings are not closed a second time.
+            assert_ne!(cell.cell()[0], cell.cell()[cell.cell().len() - 2]);
+        }
+    }
+
+    #[test]
+    fn test_close_ring() {
+        let mut ring = vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]];
+        close_ring(&mut ring);
+        assert_eq!(ring, vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]);
+        close_ring(&mut ring);
+        assert_eq!(ring.len(), 4);
+        let mut empty_ring = Vec::new();
+        close_ring(&mut empty_ring);
+        assert!(empty_ring.is_empty());
+    }
+
+    #[test]
+    fn test_compute_voronoi_iter() {
+        let bps =
+            square_bounded_point_set(vec![[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]], 2.0);
+        let options = VoronoiOptions::default().include_area(true);
+        let cells: Vec<BoundedVoronoiCell> =
+            compute_voronoi_iter_with_options(&bps, options.clone())
+                .collect::<Result<_, _>>()
+                .unwrap();
+        let expected_cells = compute_voronoi_with_options(&bps, options).unwrap();
+        assert_eq!(cells.len(), expected_cells.len());
+        for (cell, expected_cell) in cells.iter().zip(&expected_cells) {
+            assert_eq!(cell.site(), expected_cell.site());
+            assert_eq!(cell.cell(), expected_cell.cell());
+            assert_eq!(cell.area(), expected_cell.area());
+        }
+    }
+
+    #[test]
+    fn test_compute_voronoi_iter_error() {
+        let mut cells = compute_voronoi_iter(BoundedPointSet::new(vec![[0.0, 0.0]], vec![]));
+        assert_eq!(cells.next().unwrap().unwrap_err(), VoronoiError::TooFewBoundPoints);
+        assert!(cells.next().is_none());
+    }
+
+    #[test]
+    fn test_compute_voronoi_single_site() {
+        let bps =
+            square_bounded_point_set(vec![[5.0, 5.0]], 2.0).with_labels(vec!["single".into()]);
+        let cells = compute_voronoi(bps).unwrap();
+        assert_eq!(cells.len(), 1);
+        assert_eq!(cells[0].label().as_deref(), Some("single"));
+        assert!(cells[0].neighbors().is_empty());
+        assert_ulps_eq!(cells[0].area(), 4.0);
+        let bounds = Bounds::from_polygon(cells[0].as_polygon()).unwrap();
+        assert_ulps_eq!(bounds.min_x(), 4.0);
+        assert_ulps_eq!(bounds.max_x(), 6.0);
+        assert_ulps_eq!(bounds.min_y(), 4.0);
+        assert_ulps_eq!(bounds.max_y(), 6.0);
+    }
+
+    #[test]
+    fn test_compute_voronoi_identical_points() {
+        let bound = vec![[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]];
+        let bps = BoundedPointSet::new(vec![[1.0, 1.0]; 5], bound.clone());
+        let bound_polygon = MultiPolygon::new(vec![ring_to_polygon(bound)]);
+        let options = [
+            VoronoiOptions::default(),
+            VoronoiOptions::default().lloyd_until(1e-6, 10),
+            VoronoiOptions::default().lloyd_iterations(3),
+        ];
+        for options in options {
+            let cells = compute_voronoi_with_options(&bps, options).unwrap();
+            assert_eq!(cells.len(), 1);
+            assert_eq!(cells[0].site(), [1.0, 1.0]);
+            assert!(cells[0].neighbors().is_empty());
+            // The single cell is the whole bound.
+            let difference = cells[0].multi_polygon().xor(&bound_polygon);
+            assert_abs_diff_eq!(difference.unsigned_area(), 0.0, epsilon = 1e-12);
+        }
+        let cells: Vec<BoundedVoronoiCell> = compute_voronoi_iter(&bps)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(cells.len(), 1);
+    }
+
+    #[test]
+    fn test_compute_voronoi_collinear_sites() {
+        let bps = square_bounded_point_set(vec![[2.0, 2.0], [0.0, 0.0], [1.0, 1.0]], 4.0);
+        let cells = compute_voronoi(bps).unwrap();
+        assert_eq!(cells.len(), 3);
+        assert_eq!(cells[0].site(), [0.0, 0.0]);
+        assert_eq!(cells[1].site(), [1.0, 1.0]);
+        assert_eq!(cells[2].site(), [2.0, 2.0]);
+        assert_eq!(cells[1].neighbors(), &vec![0, 2]);
+        for cell in &cells {
+            assert!(cell
+                .as_polygon()
+                .contains(&geo::Point::new(cell.site()[0], cell.site()[1])));
+        }
+        // The middle cell is the diagonal strip between the bisectors of
+        // the neighboring sites within the centered bound.
+        assert_abs_diff_eq!(cells[1].area(), 16.0 - 2.0 * 0.5 * 3.0 * 3.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_are_collinear() {
+        let sites = |points: &[[f64; 2]]| -> Vec<Point2D> {
+            points
+                .iter()
+                .map(|point| Point2D::new(point[0], point[1]).unwrap())
+                .collect()
+        };
+        assert!(are_collinear(&[]));
+        assert!(are_collinear(&sites(&[[1.0, 2.0]])));
+        assert!(are_collinear(&sites(&[[0.0, 0.0], [1.0, 2.0], [2.0, 4.0]])));
+        assert!(!are_collinear(&sites(&[[0.0, 0.0], [1.0, 2.0], [2.0, 3.0]])));
+    }
+
+    #[test]
+    fn test_center_polygon() {
+        let poly: Polygon = Polygon::new(
+            LineString::from(vec![(0.0, 0.0), (4.0, 0.0), (4.0, 2.0), (0.0, 2.0), (0.0, 0.0)]),
+            vec![LineString::from(vec![
+                (1.0, 0.5),
+                (3.0, 0.5),
+                (3.0, 1.5),
+                (1.0, 0.5),
+            ])],
+        );
+        let centered = center_polygon(&poly, 10.0, -10.0).unwrap();
+        let bounds = Bounds::from_polygon(&centered).unwrap();
+        assert_ulps_eq!(bounds.centre_x(), 10.0);
+        assert_ulps_eq!(bounds.centre_y(), -10.0);
+        assert_eq!(centered.interiors().len(), 1);
+        let hole_start = centered.interiors()[0].0[0];
+        assert_ulps_eq!(hole_start.x, 9.0);
+        assert_ulps_eq!(hole_start.y, -10.5);
+    }
+
+    #[test]
+    fn test_boundedvoronoicell_apply_bound_hole() {
+        let cell = BoundedVoronoiCell::new(
+            [0.0, 0.0],
+            vec![
+                [-1.0, -1.0],
+                [1.0, -1.0],
+                [1.0, 1.0],
+                [-1.0, 1.0],
+                [-1.0, -1.0],
+            ],
+        );
+        // The hole overlaps the right edge of the cell.
+        let bound: Polygon = Polygon::new(
+            LineString::from(vec![
+                (-2.0, -2.0),
+                (2.0, -2.0),
+                (2.0, 2.0),
+                (-2.0, 2.0),
+                (-2.0, -2.0),
+            ]),
+            vec![LineString::from(vec![
+                (0.5, -0.5),
+                (1.5, -0.5),
+                (1.5, 0.5),
+                (0.5, 0.5),
+                (0.5, -0.5),
+            ])],
+        );
+        let bounded_cell = cell.apply_bound(bound).unwrap();
+        let bounded_polygon = bounded_cell.as_polygon();
+        assert_ulps_eq!(bounded_cell.area(), 3.5);
+        assert!(bounded_polygon.contains(&geo::Point::new(0.0, 0.0)));
+        assert!(!bounded_polygon.contains(&geo::Point::new(0.75, 0.0)));
+    }
 }
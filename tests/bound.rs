@@ -0,0 +1,104 @@
+//! Tests supplying the bound from a separate file with the command line tool.
+
+use std::{path::Path, process::Output};
+
+use serde_json::Value;
+
+mod common;
+
+/// The bound shared by the point sets as bare ring.
+const BOUND: &str = "[[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5], [-0.5, 0.5], [-0.5, -0.5]]";
+
+/// Writes the point set and the bound to a new directory and runs the command line tool
+/// on them, writing the result to stdout.
+///
+/// # Parameters
+///
+/// * `name` - the name of the test directory
+/// * `point_set` - the content of the point set file
+fn run_with_bound(name: &str, point_set: &str) -> Output {
+    let directory = common::fixture_dir(name);
+    let point_set_path = common::write_fixture(&directory, "point_set.json", point_set);
+    let bound_path = common::write_fixture(&directory, "bound.json", BOUND);
+    common::assert_success(
+        common::command()
+            .arg("--bound")
+            .arg(&bound_path)
+            .args(["--area", "--output-path", "-"])
+            .arg(&point_set_path)
+            .output()
+            .unwrap(),
+    )
+}
+
+/// Returns the total area of the cells written to stdout.
+///
+/// # Parameters
+///
+/// * `output` - the output of the command line tool
+fn total_area(output: &Output) -> f64 {
+    let cells: Value = serde_json::from_slice(&output.stdout).unwrap();
+    cells
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|cell| cell["area"].as_f64().unwrap())
+        .sum()
+}
+
+#[test]
+fn test_bound_file_merge() {
+    let output = run_with_bound("bound_file_merge", r#"{"points": [[0.0, 0.0], [10.0, 0.0]]}"#);
+    assert!(String::from_utf8(output.stderr.clone()).unwrap().is_empty());
+    // Each cell is covered completely by the unit square centered around its site.
+    assert!((total_area(&output) - 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_bound_file_conflict() {
+    let output = run_with_bound(
+        "bound_file_conflict",
+        r#"{"points": [[0.0, 0.0], [10.0, 0.0]], "bbox": [-2.0, -2.0, 2.0, 2.0]}"#,
+    );
+    let stderr = String::from_utf8(output.stderr.clone()).unwrap();
+    assert!(stderr.contains("is replaced by the bound specified with --bound"));
+    assert!((total_area(&output) - 2.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_bound_file_strict() {
+    let directory = common::fixture_dir("bound_file_strict");
+    let bound_path = common::write_fixture(&directory, "bound.json", BOUND);
+    let run_strict = |point_set: &str| {
+        let point_set_path = common::write_fixture(&directory, "point_set.json", point_set);
+        common::command()
+            .arg("--bound")
+            .arg(&bound_path)
+            .args(["--strict", "--output-path", "-"])
+            .arg(&point_set_path)
+            .output()
+            .unwrap()
+    };
+    let contained = run_strict(r#"{"points": [[0.0, 0.0], [0.5, 0.5]]}"#);
+    assert!(contained.status.success());
+    assert!(contained.stderr.is_empty());
+    let spilling_over = run_strict(r#"{"points": [[0.0, 0.0], [10.0, 0.0]]}"#);
+    assert!(!spilling_over.status.success());
+    assert!(String::from_utf8(spilling_over.stderr)
+        .unwrap()
+        .contains("SitesOutsideBound { sites: 2 }"));
+}
+
+#[test]
+fn test_bound_file_invalid() {
+    let output = common::command()
+        .arg("--bound")
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .arg("-")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("The bound file"));
+}
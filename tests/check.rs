@@ -0,0 +1,34 @@
+//! Tests the validation of point set files with the command line tool.
+
+use std::process::Output;
+
+mod common;
+
+/// Runs the command line tool with the `--check` flag on the point set read from stdin.
+///
+/// # Parameters
+///
+/// * `point_set` - the JSON point set file content
+fn check(point_set: &str) -> Output {
+    common::run_with_stdin(["--check", "-"], point_set.as_bytes())
+}
+
+#[test]
+fn test_check_valid() {
+    let output = check(
+        r#"{"points": [[0.0, 0.0], [1.0, 1.0], [0.0, 0.0]], "bbox": [-1.0, -1.0, 1.0, 1.0]}"#,
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("valid with 2 unique points and 1 duplicate points"));
+}
+
+#[test]
+fn test_check_self_intersecting_bound() {
+    let output = check(
+        r#"{"points": [[0.0, 0.0]], "bound": [[0.0, 0.0], [1.0, 1.0], [1.0, 0.0], [0.0, 1.0], [0.0, 0.0]]}"#,
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(!output.stderr.is_empty());
+}
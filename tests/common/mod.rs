@@ -0,0 +1,74 @@
+//! Helpers shared by the tests of the command line tool.
+
+// Each test file only uses some of the helpers.
+#![allow(dead_code)]
+
+use std::{
+    ffi::OsStr,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+};
+
+/// Returns a command running the command line tool.
+pub fn command() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_geo-bounded-voronoi"))
+}
+
+/// Returns the empty directory of the test with the specified name,
+/// so that files of previous runs cannot affect the test.
+///
+/// # Parameters
+///
+/// * `name` - the name of the test directory
+pub fn fixture_dir(name: &str) -> PathBuf {
+    let directory = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    if directory.exists() {
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+    std::fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+/// Writes the content to the file with the specified name in the directory
+/// and returns the path of the file.
+///
+/// # Parameters
+///
+/// * `directory` - the directory to write the file to
+/// * `file_name` - the name of the file
+/// * `content` - the content of the file
+pub fn write_fixture<T: AsRef<[u8]>>(directory: &Path, file_name: &str, content: T) -> PathBuf {
+    let path = directory.join(file_name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+/// Runs the command line tool with the specified arguments on the input
+/// passed via stdin and returns its output.
+///
+/// # Parameters
+///
+/// * `args` - the command line arguments
+/// * `input` - the input written to stdin
+pub fn run_with_stdin<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(args: I, input: &[u8]) -> Output {
+    let mut child = command()
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// Asserts that the command line tool succeeded and returns its output.
+///
+/// # Parameters
+///
+/// * `output` - the output of the command line tool
+pub fn assert_success(output: Output) -> Output {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    output
+}
@@ -0,0 +1,63 @@
+//! Tests reading and writing gzip-compressed point set and result files with the command line tool.
+
+use std::io::{Read, Write};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde_json::Value;
+
+mod common;
+
+/// The point set used as input.
+const POINT_SET: &str =
+    r#"{"points": [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]], "bbox": [-1.0, -1.0, 1.0, 1.0]}"#;
+
+/// Returns the gzip-compressed data.
+///
+/// # Parameters
+///
+/// * `data` - the data to compress
+fn compress(data: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(data).unwrap();
+    encoder.finish().unwrap()
+}
+
+/// Returns the decompressed data.
+///
+/// # Parameters
+///
+/// * `data` - the gzip-compressed data
+fn decompress(data: &[u8]) -> Vec<u8> {
+    let mut decompressed = Vec::new();
+    GzDecoder::new(data).read_to_end(&mut decompressed).unwrap();
+    decompressed
+}
+
+#[test]
+fn test_gzip_file_round_trip() {
+    let directory = common::fixture_dir("gzip_file_round_trip");
+    let input_path =
+        common::write_fixture(&directory, "point_set.json.gz", compress(POINT_SET.as_bytes()));
+    let output_path = directory.join("voronoi.json.gz");
+    common::assert_success(
+        common::command()
+            .arg("--output-path")
+            .arg(&output_path)
+            .arg(&input_path)
+            .output()
+            .unwrap(),
+    );
+    let cells: Value =
+        serde_json::from_slice(&decompress(&std::fs::read(&output_path).unwrap())).unwrap();
+    assert_eq!(cells.as_array().unwrap().len(), 3);
+}
+
+#[test]
+fn test_gzip_standard_streams() {
+    let output = common::assert_success(common::run_with_stdin(
+        ["--gzip", "-"],
+        &compress(POINT_SET.as_bytes()),
+    ));
+    let cells: Value = serde_json::from_slice(&decompress(&output.stdout)).unwrap();
+    assert_eq!(cells.as_array().unwrap().len(), 3);
+}
@@ -0,0 +1,35 @@
+//! Tests shuffling the points with a seed with the command line tool.
+
+mod common;
+
+/// The point set passed via stdin.
+const POINT_SET: &str = r#"{
+    "points": [[0, 0], [1, 0], [2, 0], [0, 1], [1, 1], [2, 1], [0, 2], [1, 2], [2, 2]],
+    "bbox": [-2, -2, 2, 2]
+}"#;
+
+/// Runs the command line tool with the specified arguments on the point set
+/// passed via stdin and returns the output written to stdout.
+///
+/// # Parameters
+///
+/// * `args` - the additional command line arguments
+fn run(args: &[&str]) -> Vec<u8> {
+    let args = args.iter().copied().chain(["-"]);
+    common::assert_success(common::run_with_stdin(args, POINT_SET.as_bytes())).stdout
+}
+
+#[test]
+fn test_seed() {
+    assert_eq!(run(&["--seed", "7"]), run(&["--seed", "7"]));
+    assert_ne!(run(&["--seed", "7"]), run(&["--seed", "8"]));
+    assert_eq!(run(&["--seed", "7", "--lloyd", "3"]), run(&["--seed", "7", "--lloyd", "3"]));
+}
+
+#[test]
+fn test_seed_input_order() {
+    let output =
+        common::run_with_stdin(["--seed", "7", "--input-order", "-"], POINT_SET.as_bytes());
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("input order"));
+}
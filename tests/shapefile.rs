@@ -0,0 +1,99 @@
+//! Tests writing the cells as shapefile with the command line tool.
+
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use shapefile::{
+    dbase::{FieldValue, Record},
+    Point, Polygon, PolygonRing,
+};
+
+mod common;
+
+/// A point set with five sites in a square bound with a hole.
+const POINT_SET: &str = r#"{
+    "points": [[-0.25, -0.25], [0.25, -0.25], [-0.25, 0.25], [0.25, 0.2], [0.05, 0.0]],
+    "bound": [[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5], [-0.5, 0.5], [-0.5, -0.5]],
+    "holes": [[[0.3, 0.3], [0.4, 0.3], [0.4, 0.4], [0.3, 0.4], [0.3, 0.3]]]
+}"#;
+
+/// Runs the command line tool on the point set file in the test directory.
+///
+/// # Parameters
+///
+/// * `directory` - the test directory containing the point set file
+/// * `output_file` - the name of the output file within the test directory
+/// * `args` - the additional command line arguments
+fn run(directory: &Path, output_file: &str, args: &[&str]) -> PathBuf {
+    let output_path = directory.join(output_file);
+    common::assert_success(
+        common::command()
+            .arg("-o")
+            .arg(&output_path)
+            .args(args)
+            .arg(directory.join("point_set.json"))
+            .output()
+            .unwrap(),
+    );
+    output_path
+}
+
+/// Returns the signed area of the ring, which is negative for clockwise rings.
+///
+/// # Parameters
+///
+/// * `ring` - the closed ring
+fn signed_area(ring: &[Point]) -> f64 {
+    ring.windows(2)
+        .map(|edge| edge[0].x * edge[1].y - edge[1].x * edge[0].y)
+        .sum::<f64>()
+        / 2.0
+}
+
+#[test]
+fn test_shapefile_read_back() {
+    let directory = common::fixture_dir("shapefile_read_back");
+    common::write_fixture(&directory, "point_set.json", POINT_SET);
+    let shp_path = run(&directory, "cells.shp", &["--format", "shapefile"]);
+    let json_path = run(&directory, "cells.json", &[]);
+    let cells: Vec<Value> = serde_json::from_slice(&std::fs::read(json_path).unwrap()).unwrap();
+
+    let records = shapefile::read_as::<_, Polygon, Record>(&shp_path).unwrap();
+    assert_eq!(records.len(), cells.len());
+    let mut hole_count = 0;
+    for (position, ((polygon, record), cell)) in records.iter().zip(&cells).enumerate() {
+        for ring in polygon.rings() {
+            let points = ring.points();
+            assert_eq!(points.first(), points.last());
+            // The exterior ring is clockwise and the holes are counter-clockwise.
+            match ring {
+                PolygonRing::Outer(_) => assert!(signed_area(points) < 0.0),
+                PolygonRing::Inner(_) => {
+                    assert!(signed_area(points) > 0.0);
+                    hole_count += 1;
+                },
+            }
+        }
+        let numeric = |name: &str| match record.get(name) {
+            Some(FieldValue::Numeric(Some(value))) => *value,
+            value => panic!("Unexpected value {:?} of field {}", value, name),
+        };
+        assert!((numeric("SITE_X") - cell["site"][0].as_f64().unwrap()).abs() < 1e-9);
+        assert!((numeric("SITE_Y") - cell["site"][1].as_f64().unwrap()).abs() < 1e-9);
+        assert_eq!(numeric("INDEX"), position as f64);
+    }
+    assert_eq!(hole_count, 1);
+
+    let shx = std::fs::read(shp_path.with_extension("shx")).unwrap();
+    assert_eq!((shx.len() - 100) / 8, cells.len());
+}
+
+#[test]
+fn test_shapefile_stdout() {
+    let output = common::command()
+        .args(["--format", "shapefile", "-o", "-", "-"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Shapefiles"));
+}
@@ -0,0 +1,94 @@
+//! Tests writing each cell to its own file with the command line tool.
+
+use std::{
+    path::{Path, PathBuf},
+    process::Output,
+};
+
+use serde_json::Value;
+
+mod common;
+
+/// A point set with four sites in a square bound.
+const POINT_SET: &str = r#"{
+    "points": [[-0.25, -0.25], [0.25, -0.25], [-0.25, 0.25], [0.25, 0.2]],
+    "bound": [[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5], [-0.5, 0.5], [-0.5, -0.5]]
+}"#;
+
+/// Writes the point set to a new directory and runs the command line tool on it,
+/// writing the cells to the `cells` subdirectory.
+///
+/// # Parameters
+///
+/// * `name` - the name of the test directory
+/// * `args` - the additional command line arguments
+fn run_split_output(name: &str, args: &[&str]) -> (PathBuf, Output) {
+    let directory = common::fixture_dir(name);
+    let point_set_path = common::write_fixture(&directory, "point_set.json", POINT_SET);
+    let split_output = directory.join("cells");
+    let output = common::command()
+        .arg("--split-output")
+        .arg(&split_output)
+        .args(args)
+        .arg(&point_set_path)
+        .output()
+        .unwrap();
+    (split_output, output)
+}
+
+/// Returns the sorted names of the files in the directory.
+///
+/// # Parameters
+///
+/// * `directory` - the directory to list
+fn file_names(directory: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(directory)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_split_output_json() {
+    let (split_output, output) = run_split_output("split_output_json", &[]);
+    common::assert_success(output);
+    assert_eq!(
+        file_names(&split_output),
+        vec!["cell_0.json", "cell_1.json", "cell_2.json", "cell_3.json"]
+    );
+    for index in 0..4 {
+        let cell: Value = serde_json::from_slice(
+            &std::fs::read(split_output.join(format!("cell_{}.json", index))).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(cell["index"], index);
+    }
+}
+
+#[test]
+fn test_split_output_geojson() {
+    let (split_output, output) = run_split_output("split_output_geojson", &["--format", "geojson"]);
+    common::assert_success(output);
+    assert_eq!(
+        file_names(&split_output),
+        vec![
+            "cell_0.geojson",
+            "cell_1.geojson",
+            "cell_2.geojson",
+            "cell_3.geojson"
+        ]
+    );
+    let collection: Value =
+        serde_json::from_slice(&std::fs::read(split_output.join("cell_0.geojson")).unwrap())
+            .unwrap();
+    assert_eq!(collection["features"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_split_output_unsupported_format() {
+    let (split_output, output) = run_split_output("split_output_csv", &["--format", "csv"]);
+    assert!(!output.status.success());
+    assert!(!split_output.exists());
+}
@@ -0,0 +1,46 @@
+//! Tests applying the bound with a specified number of threads with the command line tool.
+
+use serde_json::Value;
+
+mod common;
+
+/// Returns a point set with more sites than the streaming output clips at once.
+fn point_set() -> String {
+    let points: Vec<[f64; 2]> = (0..1500)
+        .map(|index| {
+            let index = index as f64;
+            [
+                (index * 0.37).sin() * 10.0 + index * 0.003,
+                (index * 0.73).cos() * 10.0,
+            ]
+        })
+        .collect();
+    serde_json::json!({ "points": points, "bbox": [-1.0, -1.0, 1.0, 1.0] }).to_string()
+}
+
+/// Runs the command line tool with the specified arguments on the point set
+/// passed via stdin and returns the parsed JSON output.
+///
+/// # Parameters
+///
+/// * `args` - the additional command line arguments
+fn run(args: &[&str]) -> Value {
+    let args = args.iter().copied().chain(["-"]);
+    let output = common::assert_success(common::run_with_stdin(args, point_set().as_bytes()));
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_threads_streaming_output() {
+    // The streaming output is compared with the collected output of a single thread.
+    let sequential = run(&["--threads", "1", "--stats"]);
+    assert_eq!(sequential.as_array().unwrap().len(), 1500);
+    for args in [
+        vec![],
+        vec!["--threads", "1"],
+        vec!["--threads", "3"],
+        vec!["--threads", "3", "--stats"],
+    ] {
+        assert_eq!(run(&args), sequential, "Deviating output for {:?}", args);
+    }
+}
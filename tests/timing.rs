@@ -0,0 +1,56 @@
+//! Tests writing the timing of the computation phases with the command line tool.
+
+use serde_json::Value;
+
+mod common;
+
+/// The point set used as input.
+const POINT_SET: &str =
+    r#"{"points": [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]], "bbox": [-1.0, -1.0, 1.0, 1.0]}"#;
+
+/// The phases, whose timing is reported.
+const PHASES: [&str; 4] = [
+    "deduplication",
+    "diagramm construction",
+    "cell iteration",
+    "clipping",
+];
+
+/// Runs the command line tool with the specified arguments on the point set
+/// passed via stdin and returns its stdout and stderr.
+///
+/// # Parameters
+///
+/// * `args` - the additional command line arguments
+fn run(args: &[&str]) -> (String, String) {
+    let args = args.iter().copied().chain(["-"]);
+    let output = common::assert_success(common::run_with_stdin(args, POINT_SET.as_bytes()));
+    (String::from_utf8(output.stdout).unwrap(), String::from_utf8(output.stderr).unwrap())
+}
+
+#[test]
+fn test_timing_stderr() {
+    // The streaming and the collecting output are timed separately.
+    for args in [vec!["--timing"], vec!["--timing", "--stats"]] {
+        let (stdout, stderr) = run(&args);
+        for phase in PHASES {
+            assert!(
+                stderr
+                    .lines()
+                    .any(|line| line.starts_with(&format!("Timing: {} took", phase))),
+                "Missing timing of {} in {}",
+                phase,
+                stderr
+            );
+        }
+        assert!(!stdout.contains("Timing"));
+        let cells: Value = serde_json::from_str(&stdout).unwrap();
+        assert_eq!(cells.as_array().unwrap().len(), 3);
+    }
+}
+
+#[test]
+fn test_timing_disabled() {
+    let (_, stderr) = run(&[]);
+    assert!(!stderr.contains("Timing"));
+}